@@ -13,6 +13,7 @@ use tracing::{debug, info, span, Level};
 // pub const ROLLUP_NAMESPACE: NamespaceId = NamespaceId([115, 111, 118, 45, 116, 101, 115, 116]);
 
 use crate::{
+    metrics::CELESTIA_FETCH_LATENCY_SECONDS,
     parse_pfb_namespace,
     share_commit::recreate_commitment,
     shares::{NamespaceGroup, Share},
@@ -62,8 +63,12 @@ async fn fetch_needed_shares_by_header(
         client.request::<RpcNamespacedSharesResponse, _>("share.GetSharesByNamespace", params)
     };
 
-    let (rollup_shares_resp, etx_shares_resp) =
-        tokio::join!(rollup_shares_future, etx_shares_future);
+    let (rollup_shares_resp, etx_shares_resp) = {
+        let _timer = CELESTIA_FETCH_LATENCY_SECONDS
+            .with_label_values(&["shares"])
+            .start_timer();
+        tokio::join!(rollup_shares_future, etx_shares_future)
+    };
 
     let rollup_shares = NamespaceGroup::Sparse(
         rollup_shares_resp?
@@ -141,11 +146,18 @@ impl DaService for CelestiaService {
         let rollup_namespace = self.rollup_namespace.clone();
         Box::pin(async move {
             let _span = span!(Level::TRACE, "fetching finalized block", height = height);
+            let _block_timer = CELESTIA_FETCH_LATENCY_SECONDS
+                .with_label_values(&["block"])
+                .start_timer();
             // Fetch the header and relevant shares via RPC
             info!("Fetching header at height={}...", height);
+            let header_timer = CELESTIA_FETCH_LATENCY_SECONDS
+                .with_label_values(&["header"])
+                .start_timer();
             let header = client
                 .request::<serde_json::Value, _>("header.GetByHeight", vec![height])
                 .await?;
+            header_timer.observe_duration();
             debug!(header_result = ?header);
             debug!("Fetching shares...");
             let (rollup_shares, tx_data) =
@@ -153,6 +165,9 @@ impl DaService for CelestiaService {
 
             debug!("Fetching EDS...");
             // Fetch entire extended data square
+            let eds_timer = CELESTIA_FETCH_LATENCY_SECONDS
+                .with_label_values(&["eds"])
+                .start_timer();
             let data_square = client
                 .request::<ExtendedDataSquare, _>(
                     "share.GetEDS",
@@ -161,6 +176,7 @@ impl DaService for CelestiaService {
                         .ok_or(BoxError::msg("missing 'dah' in block header"))?],
                 )
                 .await?;
+            eds_timer.observe_duration();
 
             let unmarshalled_header: CelestiaHeaderResponse = serde_json::from_value(header)?;
             let dah: DataAvailabilityHeader = unmarshalled_header.dah.try_into()?;
@@ -6,6 +6,8 @@ pub use celestia::*;
 
 #[cfg(feature = "native")]
 pub mod da_service;
+#[cfg(feature = "native")]
+mod metrics;
 pub mod pfb;
 pub mod share_commit;
 pub mod types;
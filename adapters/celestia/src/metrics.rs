@@ -0,0 +1,15 @@
+use once_cell::sync::Lazy;
+use prometheus::{exponential_buckets, register_histogram_vec, HistogramVec};
+
+pub static CELESTIA_FETCH_LATENCY_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        // metric name
+        "celestia_fetch_latency_seconds",
+        // metric description
+        "Latency of fetching a block from the Celestia node in seconds",
+        // metric labels (dimensions): "header", "shares", "eds" or "block" for the whole fetch
+        &["stage"],
+        exponential_buckets(/*start=*/ 1e-3, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
@@ -0,0 +1,101 @@
+//! Measures the guest-side cost of reading the witness formats, on the batches used by the demo-stf tests.
+//! Each witness is read by the `witness_decode` guest, which reports the number of zkVM cycles spent on
+//! each format. Every run generates a real proof, so this takes a while.
+
+use borsh::BorshSerialize;
+use demo_stf::app::NativeAppRunner;
+use demo_stf::genesis_config::{
+    create_demo_genesis_config, generate_address, DEMO_SEQUENCER_DA_ADDRESS, DEMO_SEQ_PUB_KEY_STR,
+    LOCKED_AMOUNT,
+};
+use demo_stf::runner_config::{Config, StorageConfig};
+use demo_stf::{ArrayWitness, CompactWitness};
+use methods::WITNESS_DECODE_ELF;
+use risc0_adapter::host::Risc0Host;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::Address;
+use sov_modules_stf_template::{Batch, RawTx};
+use sov_rollup_interface::mocks::{MockZkvm, TestBlob};
+use sov_rollup_interface::stf::{StateTransitionFunction, StateTransitionRunner};
+use sov_rollup_interface::zk::traits::ZkvmHost;
+
+// The test data generators refer to the runtime as `crate::runtime`
+use demo_stf::runtime;
+
+#[allow(dead_code)]
+#[path = "../../../demo-stf/src/tests/data_generation/mod.rs"]
+mod data_generation;
+
+/// Executes a batch on a freshly initialized chain and returns the witness of its slot
+fn execute_batch(
+    make_txs: impl FnOnce(DefaultPrivateKey, DefaultPrivateKey) -> Vec<RawTx>,
+) -> ArrayWitness {
+    let path = tempfile::tempdir().unwrap();
+    let mut runner = NativeAppRunner::<MockZkvm>::new(Config {
+        storage: StorageConfig {
+            path: path.path().to_path_buf(),
+            rocksdb: Default::default(),
+        },
+        save_tx_bodies: false,
+    });
+    let demo = runner.inner_mut();
+
+    let value_setter_admin = DefaultPrivateKey::generate();
+    let election_admin = DefaultPrivateKey::generate();
+    demo.init_chain(create_demo_genesis_config(
+        LOCKED_AMOUNT + 1,
+        generate_address::<DefaultContext>(DEMO_SEQ_PUB_KEY_STR),
+        DEMO_SEQUENCER_DA_ADDRESS.to_vec(),
+        &value_setter_admin,
+        &election_admin,
+    ));
+
+    let txs = make_txs(value_setter_admin, election_admin);
+    let blob = TestBlob::new(
+        Batch { txs }.try_to_vec().unwrap(),
+        Address::try_from(&DEMO_SEQUENCER_DA_ADDRESS[..]).unwrap(),
+    );
+    demo.begin_slot(Default::default());
+    demo.apply_blob(blob, None);
+    let (_, witness) = demo.end_slot();
+    witness
+}
+
+/// Runs the `witness_decode` guest and returns the cycles it spent reading each format
+fn decode_cycles(witness: ArrayWitness) -> (u64, u64) {
+    let mut host = Risc0Host::new(WITNESS_DECODE_ELF);
+    host.write_to_guest(&witness);
+    host.write_to_guest(&CompactWitness::from(witness));
+    let receipt = host.run().expect("The guest should decode both witnesses");
+
+    let (array_cycles, compact_cycles) = receipt.journal.split_at(8);
+    (
+        u64::from_le_bytes(array_cycles.try_into().unwrap()),
+        u64::from_le_bytes(compact_cycles.try_into().unwrap()),
+    )
+}
+
+fn main() {
+    let witnesses = [
+        (
+            "election_and_value_setter",
+            execute_batch(data_generation::simulate_da),
+        ),
+        (
+            "reverted_election",
+            execute_batch(|_, election_admin| {
+                data_generation::simulate_da_with_revert_msg(election_admin)
+            }),
+        ),
+    ];
+
+    for (name, witness) in witnesses {
+        let num_hints = witness.num_hints();
+        let (array_cycles, compact_cycles) = decode_cycles(witness);
+        println!(
+            "{}: {} hints. ArrayWitness: {} cycles, CompactWitness: {} cycles",
+            name, num_hints, array_cycles, compact_cycles
+        );
+    }
+}
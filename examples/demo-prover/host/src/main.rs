@@ -70,9 +70,7 @@ async fn main() -> Result<(), anyhow::Error> {
         demo.init_chain(genesis_config);
     }
 
-    demo.begin_slot(Default::default());
-    let (prev_state_root, _) = demo.end_slot();
-    let mut prev_state_root = prev_state_root.0;
+    let mut prev_state_root = demo.current_storage.get_state_root()?;
 
     for height in rollup_config.start_height..=rollup_config.start_height + 30 {
         let mut host = Risc0Host::new(ROLLUP_ELF);
@@ -0,0 +1,21 @@
+#![no_main]
+
+use demo_stf::{ArrayWitness, CompactWitness};
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+// Reads the same witness in both formats, and commits the number of cycles spent reading each one
+// as two little-endian u64s: first the `ArrayWitness`, then the `CompactWitness`.
+pub fn main() {
+    let start = env::get_cycle_count();
+    let array_witness: ArrayWitness = env::read();
+    let array_cycles = (env::get_cycle_count() - start) as u64;
+
+    let start = env::get_cycle_count();
+    let compact_witness: CompactWitness = env::read();
+    let compact_cycles = (env::get_cycle_count() - start) as u64;
+
+    assert_eq!(array_witness.num_hints(), compact_witness.num_hints());
+    env::commit_slice(&array_cycles.to_le_bytes());
+    env::commit_slice(&compact_cycles.to_le_bytes());
+}
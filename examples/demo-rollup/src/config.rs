@@ -1,6 +1,8 @@
 use demo_stf::runner_config::Config as RunnerConfig;
 use jupiter::da_service::DaServiceConfig;
 use serde::Deserialize;
+use sov_db::schema::types::WitnessRetention;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct RpcConfig {
@@ -8,12 +10,54 @@ pub struct RpcConfig {
     pub bind_port: u16,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MetricsConfig {
+    pub bind_host: String,
+    pub bind_port: u16,
+}
+
+/// Configuration of a process started with `--rpc-replica`, which serves the RPC from read-only
+/// secondaries of the databases of the node
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RpcReplicaConfig {
+    pub bind_host: String,
+    pub bind_port: u16,
+    /// Where the secondaries keep their own RocksDB info logs. Must differ from the node's storage path
+    pub secondary_path: PathBuf,
+    /// How often the secondaries catch up with the writes of the node, in milliseconds
+    #[serde(default = "default_catch_up_interval_ms")]
+    pub catch_up_interval_ms: u64,
+}
+
+fn default_catch_up_interval_ms() -> u64 {
+    1000
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct WitnessConfig {
+    /// How long the witnesses of committed slots are kept for deferred proving. Defaults to a bounded
+    /// number of recent slots
+    pub retention: Option<WitnessRetention>,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct RollupConfig {
     pub start_height: u64,
     pub da: DaServiceConfig,
     pub runner: RunnerConfig,
     pub rpc_config: RpcConfig,
+    /// Where to serve the prometheus metrics. The metrics endpoint is disabled if this is missing
+    pub metrics: Option<MetricsConfig>,
+    pub rpc_replica: Option<RpcReplicaConfig>,
+    #[serde(default)]
+    pub witness: WitnessConfig,
+}
+
+impl RollupConfig {
+    /// Returns how long to keep the witnesses of committed slots
+    pub fn witness_retention(&self) -> WitnessRetention {
+        self.witness.retention.unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -21,8 +65,9 @@ mod tests {
     use super::*;
 
     use demo_stf::runner_config::{from_toml_path, StorageConfig};
+    use sov_db::rocks_db_config::{ColumnFamilyConfig, Compression, RocksdbConfig, RocksdbConfigs};
+    use sov_db::schema::types::DEFAULT_RETAINED_WITNESSES;
     use std::io::Write;
-    use std::path::PathBuf;
     use tempfile::NamedTempFile;
 
     fn create_config_from(content: &str) -> NamedTempFile {
@@ -39,16 +84,44 @@ mod tests {
             celestia_rpc_auth_token = "SECRET_RPC_TOKEN"
             celestia_rpc_address = "http://localhost:11111/"
             max_celestia_response_body_size = 980
+            [runner]
+            save_tx_bodies = true
             [runner.storage]
             path = "/tmp"
+            [runner.storage.rocksdb.ledger]
+            max_open_files = 1000
+            [runner.storage.rocksdb.state.column_families.JmtNodes]
+            compression = "Zstd"
+            bloom_filter_bits_per_key = 16
             [rpc_config]
             bind_host = "127.0.0.1"
             bind_port = 12345
+            [metrics]
+            bind_host = "127.0.0.1"
+            bind_port = 9845
+            [rpc_replica]
+            bind_host = "127.0.0.1"
+            bind_port = 12346
+            secondary_path = "/tmp/replica"
         "#;
 
         let config_file = create_config_from(config);
 
         let config: RollupConfig = from_toml_path(config_file.path()).unwrap();
+        let mut rocksdb = RocksdbConfigs {
+            ledger: RocksdbConfig {
+                max_open_files: 1000,
+                ..Default::default()
+            },
+            state: RocksdbConfig::default(),
+        };
+        rocksdb.state.column_families.insert(
+            "JmtNodes".to_string(),
+            ColumnFamilyConfig {
+                compression: Compression::Zstd,
+                bloom_filter_bits_per_key: Some(16),
+            },
+        );
         let expected = RollupConfig {
             start_height: 31337,
             da: DaServiceConfig {
@@ -59,13 +132,51 @@ mod tests {
             runner: RunnerConfig {
                 storage: StorageConfig {
                     path: PathBuf::from("/tmp"),
+                    rocksdb,
                 },
+                save_tx_bodies: true,
             },
             rpc_config: RpcConfig {
                 bind_host: "127.0.0.1".to_string(),
                 bind_port: 12345,
             },
+            metrics: Some(MetricsConfig {
+                bind_host: "127.0.0.1".to_string(),
+                bind_port: 9845,
+            }),
+            rpc_replica: Some(RpcReplicaConfig {
+                bind_host: "127.0.0.1".to_string(),
+                bind_port: 12346,
+                secondary_path: PathBuf::from("/tmp/replica"),
+                catch_up_interval_ms: 1000,
+            }),
+            witness: WitnessConfig::default(),
         };
         assert_eq!(config, expected);
     }
+
+    #[test]
+    fn test_witness_retention_is_bounded_by_default() {
+        let config = r#"
+            start_height = 31337
+            [da]
+            celestia_rpc_auth_token = "SECRET_RPC_TOKEN"
+            celestia_rpc_address = "http://localhost:11111/"
+            max_celestia_response_body_size = 980
+            [runner.storage]
+            path = "/tmp"
+            [rpc_config]
+            bind_host = "127.0.0.1"
+            bind_port = 12345
+        "#;
+        let config_file = create_config_from(config);
+        let mut config: RollupConfig = from_toml_path(config_file.path()).unwrap();
+        assert_eq!(
+            config.witness_retention(),
+            WitnessRetention::KeepLatest(DEFAULT_RETAINED_WITNESSES)
+        );
+
+        config.witness.retention = Some(WitnessRetention::KeepAll);
+        assert_eq!(config.witness_retention(), WitnessRetention::KeepAll);
+    }
 }
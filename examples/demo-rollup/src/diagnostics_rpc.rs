@@ -0,0 +1,43 @@
+use jsonrpsee::RpcModule;
+use serde::Serialize;
+use sov_db::ledger_db::LedgerDB;
+use sov_db::rocks_db_config::ColumnFamilyStats;
+use sov_state::{DefaultStorageSpec, ProverStorage};
+
+/// The databases whose internals are exposed by the diagnostics RPC
+pub struct Diagnostics {
+    ledger_db: LedgerDB,
+    storage: ProverStorage<DefaultStorageSpec>,
+}
+
+/// The rocksdb stats of each column family of the node's databases
+#[derive(Debug, Serialize)]
+pub struct RocksdbStats {
+    pub ledger: Vec<ColumnFamilyStats>,
+    pub state: Vec<ColumnFamilyStats>,
+}
+
+/// Registers the following RPC methods
+/// - `diagnostics_rocksdbStats`
+///    Example Query: `curl -X POST -H "Content-Type: application/json" -d '{"jsonrpc":"2.0","method":"diagnostics_rocksdbStats","params":[],"id":1}' http://127.0.0.1:12345`
+fn register_diagnostics_rpc_methods(
+    rpc: &mut RpcModule<Diagnostics>,
+) -> Result<(), jsonrpsee::core::Error> {
+    rpc.register_method("diagnostics_rocksdbStats", move |_, diagnostics| {
+        Ok(RocksdbStats {
+            ledger: diagnostics.ledger_db.rocksdb_stats()?,
+            state: diagnostics.storage.rocksdb_stats()?,
+        })
+    })?;
+
+    Ok(())
+}
+
+pub fn get_diagnostics_rpc(
+    ledger_db: LedgerDB,
+    storage: ProverStorage<DefaultStorageSpec>,
+) -> RpcModule<Diagnostics> {
+    let mut rpc = RpcModule::new(Diagnostics { ledger_db, storage });
+    register_diagnostics_rpc_methods(&mut rpc).expect("Failed to register diagnostics RPC methods");
+    rpc
+}
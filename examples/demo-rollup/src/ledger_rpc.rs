@@ -1,9 +1,16 @@
-use jsonrpsee::RpcModule;
+use std::sync::Arc;
+
+use jsonrpsee::types::error::CallError;
+use jsonrpsee::{RpcModule, SubscriptionSink};
 use serde::{de::DeserializeOwned, Serialize};
-use sov_db::ledger_db::LedgerDB;
+use sov_db::ledger_db::{LedgerDB, SlotNotification};
 use sov_rollup_interface::rpc::{
-    BatchIdentifier, EventIdentifier, LedgerRpcProvider, SlotIdentifier, TxIdentifier,
+    BatchIdentifier, EventIdentifier, EventsByKeyQuery, LedgerRpcProvider, QueryMode,
+    SlotIdentifier, TxIdentifier, TxReceiptStatus,
 };
+use sov_rollup_interface::stf::EventKey;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{error, warn};
 
 use self::query_args::{extract_query_args, QueryArgs};
 
@@ -18,7 +25,30 @@ use self::query_args::{extract_query_args, QueryArgs};
 ///    Example Query: `curl -X POST -H "Content-Type: application/json" -d '{"jsonrpc":"2.0","method":"ledger_getBatches","params":[[1, 2], "Full"],"id":1}' http://127.0.0.1:12345`
 /// - ledger_getEvents
 ///    Example Query: `curl -X POST -H "Content-Type: application/json" -d '{"jsonrpc":"2.0","method":"ledger_getBatches","params":[1, 2],"id":1}' http://127.0.0.1:12345`
-fn register_ledger_rpc_methods<B: Serialize + DeserializeOwned, T: Serialize + DeserializeOwned>(
+/// - ledger_getEventsByKey
+///    Returns a page of the events with the given key, optionally restricted to a range of slots. Pass the `next_cursor`
+///    of a page as the `cursor` of the query to get the next one.
+///    Example Query: `curl -X POST -H "Content-Type: application/json" -d '{"jsonrpc":"2.0","method":"ledger_getEventsByKey","params":[{"key":[98,111,110,100],"start_slot":1,"end_slot":10,"limit":100}],"id":1}' http://127.0.0.1:12345`
+/// - ledger_getTxStatus
+///    Returns whether the transaction with the given hash is pending, included or reverted, along with the slot and
+///    batch which included it and the events it emitted.
+///    Example Query: `curl -X POST -H "Content-Type: application/json" -d '{"jsonrpc":"2.0","method":"ledger_getTxStatus","params":["0x1c3f..."],"id":1}' http://127.0.0.1:12345`
+///
+/// And the following subscriptions, which are only available over WebSocket. Subscribers which fall too far
+/// behind the node are disconnected, and should catch up using the methods above before subscribing again.
+/// - ledger_subscribeSlots (unsubscribe with ledger_unsubscribeSlots)
+///    Notifies each committed slot, in `Compact` mode.
+///    Example Query: `{"jsonrpc":"2.0","method":"ledger_subscribeSlots","params":[],"id":1}`
+/// - ledger_subscribeEvents (unsubscribe with ledger_unsubscribeEvents)
+///    Notifies each committed event with the given key.
+///    Example Query: `{"jsonrpc":"2.0","method":"ledger_subscribeEvents","params":[[98,111,110,100]],"id":1}`
+/// - ledger_subscribeTx (unsubscribe with ledger_unsubscribeTx)
+///    Notifies the transaction with the given hash once it's committed (immediately if it already is), then ends.
+///    Example Query: `{"jsonrpc":"2.0","method":"ledger_subscribeTx","params":["0x1c3f..."],"id":1}`
+fn register_ledger_rpc_methods<
+    B: Serialize + DeserializeOwned + Send + 'static,
+    T: Serialize + DeserializeOwned + TxReceiptStatus + Send + 'static,
+>(
     rpc: &mut RpcModule<LedgerDB>,
 ) -> Result<(), jsonrpsee::core::Error> {
     rpc.register_method("ledger_getHead", move |_, db| {
@@ -47,10 +77,163 @@ fn register_ledger_rpc_methods<B: Serialize + DeserializeOwned, T: Serialize + D
         db.get_events(&ids).map_err(|e| e.into())
     })?;
 
+    rpc.register_method("ledger_getEventsByKey", move |params, db| {
+        let query: EventsByKeyQuery = params.one()?;
+        db.get_events_by_key(&query).map_err(|e| e.into())
+    })?;
+
+    rpc.register_method("ledger_getTxStatus", move |params, db| {
+        let hash = match params.one()? {
+            TxIdentifier::Hash(hash) => hash,
+            _ => {
+                return Err(CallError::InvalidParams(anyhow::anyhow!(
+                    "ledger_getTxStatus expects a transaction hash"
+                ))
+                .into())
+            }
+        };
+        db.get_tx_status::<T>(&hash).map_err(|e| e.into())
+    })?;
+
+    rpc.register_subscription(
+        "ledger_subscribeSlots",
+        "ledger_slotNotification",
+        "ledger_unsubscribeSlots",
+        move |_, mut sink, db| {
+            sink.accept()?;
+            let mut slots = db.subscribe_slots();
+            tokio::spawn(async move {
+                while let Some(slot) = next_slot(&mut slots).await {
+                    let response =
+                        match db.get_slot_by_number::<B, T>(slot.number.0, QueryMode::Compact) {
+                            Ok(response) => response,
+                            Err(e) => {
+                                error!("Failed to read committed slot {}: {}", slot.number.0, e);
+                                return;
+                            }
+                        };
+                    if !send(&mut sink, &response) {
+                        return;
+                    }
+                }
+            });
+            Ok(())
+        },
+    )?;
+
+    rpc.register_subscription(
+        "ledger_subscribeEvents",
+        "ledger_eventNotification",
+        "ledger_unsubscribeEvents",
+        move |params, mut sink, db| {
+            let key: EventKey = match params.one() {
+                Ok(key) => key,
+                Err(e) => {
+                    sink.reject(e)?;
+                    return Ok(());
+                }
+            };
+            sink.accept()?;
+            let mut slots = db.subscribe_slots();
+            tokio::spawn(async move {
+                while let Some(slot) = next_slot(&mut slots).await {
+                    for event in slot.events.iter().filter(|e| e.event.key() == &key) {
+                        if !send(&mut sink, event) {
+                            return;
+                        }
+                    }
+                }
+            });
+            Ok(())
+        },
+    )?;
+
+    rpc.register_subscription(
+        "ledger_subscribeTx",
+        "ledger_txNotification",
+        "ledger_unsubscribeTx",
+        move |params, mut sink, db| {
+            let hash = match params.one() {
+                Ok(TxIdentifier::Hash(hash)) => hash,
+                Ok(_) => {
+                    sink.reject(CallError::InvalidParams(anyhow::anyhow!(
+                        "ledger_subscribeTx expects a transaction hash"
+                    )))?;
+                    return Ok(());
+                }
+                Err(e) => {
+                    sink.reject(e)?;
+                    return Ok(());
+                }
+            };
+            sink.accept()?;
+            // Subscribe before looking the transaction up, so that it can't be committed in between
+            let mut slots = db.subscribe_slots();
+            tokio::spawn(async move {
+                let tx = match db.get_tx_by_hash::<T>(&hash, QueryMode::Standard) {
+                    Ok(Some(tx)) => Ok(tx),
+                    Ok(None) => loop {
+                        let slot = match next_slot(&mut slots).await {
+                            Some(slot) => slot,
+                            None => return,
+                        };
+                        if let Some((_, number)) = slot.txs.iter().find(|(h, _)| h == &hash) {
+                            break db
+                                .get_tx_by_number::<T>(number.0, QueryMode::Standard)
+                                .and_then(|tx| {
+                                    tx.ok_or_else(|| anyhow::anyhow!("Committed tx is missing"))
+                                });
+                        }
+                    },
+                    Err(e) => Err(e),
+                };
+                match tx {
+                    Ok(tx) => {
+                        send(&mut sink, &tx);
+                    }
+                    Err(e) => error!("Failed to read tx 0x{}: {}", hex::encode(hash), e),
+                }
+            });
+            Ok(())
+        },
+    )?;
+
     Ok(())
 }
 
-pub fn get_ledger_rpc<B: Serialize + DeserializeOwned, T: Serialize + DeserializeOwned>(
+/// Waits for the next committed slot. Returns `None` if the subscriber fell too far behind to be notified
+/// of every slot, in which case the subscription should end.
+async fn next_slot(
+    slots: &mut broadcast::Receiver<Arc<SlotNotification>>,
+) -> Option<Arc<SlotNotification>> {
+    match slots.recv().await {
+        Ok(slot) => Some(slot),
+        Err(RecvError::Lagged(skipped)) => {
+            warn!(
+                "Ending a ledger subscription which fell {} slots behind",
+                skipped
+            );
+            None
+        }
+        Err(RecvError::Closed) => None,
+    }
+}
+
+/// Sends a notification to the subscriber. Returns `false` if the subscription should end.
+fn send<N: Serialize>(sink: &mut SubscriptionSink, notification: &N) -> bool {
+    match sink.send(notification) {
+        Ok(open) => open,
+        Err(e) => {
+            error!("Failed to serialize a ledger notification: {}", e);
+            false
+        }
+    }
+}
+
+pub fn get_ledger_rpc<
+    B: Serialize + DeserializeOwned + Send + 'static,
+    T: Serialize + DeserializeOwned + TxReceiptStatus + Send + 'static,
+>(
     ledger_db: LedgerDB,
 ) -> RpcModule<LedgerDB> {
     let mut rpc = RpcModule::new(ledger_db);
@@ -77,3 +260,49 @@ mod query_args {
         Ok(QueryArgs(ids, Default::default()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::types::EmptyServerParams;
+    use sov_db::ledger_db::SlotCommit;
+    use sov_rollup_interface::mocks::TestBlock;
+    use sov_rollup_interface::rpc::SlotResponse;
+    use sov_rollup_interface::stf::{BatchReceipt, Event, TransactionReceipt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscribe_slots() {
+        let path = tempfile::tempdir().unwrap();
+        let ledger_db = LedgerDB::with_path(path.path()).unwrap();
+        let rpc = get_ledger_rpc::<u32, ()>(ledger_db.clone());
+        let mut subscription = rpc
+            .subscribe("ledger_subscribeSlots", EmptyServerParams::new())
+            .await
+            .unwrap();
+
+        let mut slot = SlotCommit::new(TestBlock::new([1; 32], [0; 32]));
+        slot.add_batch(BatchReceipt {
+            batch_hash: [2; 32],
+            tx_receipts: vec![TransactionReceipt {
+                tx_hash: [3; 32],
+                body_to_save: None,
+                events: vec![Event::new("key", "value")],
+                receipt: (),
+            }],
+            inner: 7,
+        });
+        slot.set_state_root([4; 32]);
+        ledger_db.commit_slot(slot).unwrap();
+
+        let (response, _) = subscription
+            .next::<SlotResponse<u32, ()>>()
+            .await
+            .expect("The subscription is open")
+            .unwrap();
+        assert_eq!(response.number, 1);
+        assert_eq!(response.hash, [1; 32]);
+        assert_eq!(response.batch_range, 1..2);
+        assert_eq!(response.batches, None);
+    }
+}
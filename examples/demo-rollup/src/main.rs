@@ -1,5 +1,7 @@
 mod config;
+mod diagnostics_rpc;
 mod ledger_rpc;
+mod metrics;
 
 use crate::config::RollupConfig;
 use anyhow::Context;
@@ -19,11 +21,13 @@ use sov_db::ledger_db::{LedgerDB, SlotCommit};
 use sov_rollup_interface::da::DaVerifier;
 use sov_rollup_interface::services::da::{DaService, SlotData};
 use sov_rollup_interface::stf::{StateTransitionFunction, StateTransitionRunner};
-use sov_state::Storage;
+use sov_state::config::Config as StorageConfig;
+use sov_state::{DefaultStorageSpec, ProverStorage, Storage};
 use std::env;
 use std::net::SocketAddr;
+use std::time::{Duration, Instant};
 use tracing::Level;
-use tracing::{debug, info};
+use tracing::{debug, error, info};
 
 // RPC related imports
 use demo_stf::app::get_rpc_methods;
@@ -33,8 +37,28 @@ use sov_modules_api::RpcRunner;
 // You can change this constant to point your rollup at a different namespace
 const ROLLUP_NAMESPACE: NamespaceId = NamespaceId(ROLLUP_NAMESPACE_RAW);
 
-pub fn initialize_ledger(path: impl AsRef<std::path::Path>) -> LedgerDB {
-    LedgerDB::with_path(path).expect("Ledger DB failed to open")
+pub fn initialize_ledger(storage_config: &StorageConfig) -> LedgerDB {
+    LedgerDB::with_config(&storage_config.path, &storage_config.rocksdb.ledger)
+        .expect("Ledger DB failed to open")
+}
+
+/// Builds the module, ledger and diagnostics RPC methods served by the node
+fn build_rpc_methods(
+    ledger_db: &LedgerDB,
+    storage: ProverStorage<DefaultStorageSpec>,
+) -> jsonrpsee::RpcModule<()> {
+    let diagnostics_rpc_module =
+        diagnostics_rpc::get_diagnostics_rpc(ledger_db.clone(), storage.clone());
+    let mut methods = get_rpc_methods(storage);
+    let ledger_rpc_module =
+        ledger_rpc::get_ledger_rpc::<DemoBatchReceipt, DemoTxReceipt>(ledger_db.clone());
+    methods
+        .merge(ledger_rpc_module)
+        .expect("Failed to merge rpc modules");
+    methods
+        .merge(diagnostics_rpc_module)
+        .expect("Failed to merge rpc modules");
+    methods
 }
 
 async fn start_rpc_server(methods: impl Into<Methods>, address: SocketAddr) {
@@ -67,15 +91,80 @@ pub fn get_genesis_config() -> GenesisConfig<DefaultContext> {
     )
 }
 
+/// Slots are committed to the state before the ledger, so a crash in between leaves state changes
+/// which the ledger doesn't know about. Discards them, so that the slot is executed again.
+fn rollback_uncommitted_state(
+    ledger_db: &LedgerDB,
+    storage: &ProverStorage<DefaultStorageSpec>,
+) -> Result<(), anyhow::Error> {
+    if let Some(state_root) = ledger_db
+        .get_last_committed_slot()?
+        .and_then(|marker| marker.state_root)
+    {
+        storage.rollback_to_root(state_root)?;
+    }
+    Ok(())
+}
+
+/// Serves the RPC from read-only secondaries of the databases of a node running in another process,
+/// so that heavy query traffic doesn't contend with slot execution. The secondaries periodically catch
+/// up with the node.
+async fn run_rpc_replica(rollup_config: &RollupConfig) -> Result<(), anyhow::Error> {
+    let replica_config = rollup_config
+        .rpc_replica
+        .as_ref()
+        .context("Running as an RPC replica requires an [rpc_replica] section in the config")?;
+    let address = SocketAddr::new(replica_config.bind_host.parse()?, replica_config.bind_port);
+    let storage_config = &rollup_config.runner.storage;
+    let ledger_db = LedgerDB::secondary(
+        &storage_config.path,
+        &replica_config.secondary_path,
+        &storage_config.rocksdb.ledger,
+    )?;
+    let storage = ProverStorage::<DefaultStorageSpec>::secondary(
+        storage_config,
+        &replica_config.secondary_path,
+    )?;
+
+    let methods = build_rpc_methods(&ledger_db, storage.clone());
+    let _handle = tokio::spawn(async move {
+        start_rpc_server(methods, address).await;
+    });
+    info!("Serving RPC replica at {}", address);
+
+    let mut interval =
+        tokio::time::interval(Duration::from_millis(replica_config.catch_up_interval_ms));
+    loop {
+        interval.tick().await;
+        // The node commits a slot to the state before the ledger, so catching up the ledger first
+        // guarantees that the state of every slot served by the ledger is available
+        if let Err(e) = ledger_db
+            .catch_up_with_primary()
+            .and_then(|_| storage.catch_up_with_primary())
+        {
+            error!("Failed to catch up with the node: {}", e);
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
-    let rollup_config_path = env::args()
-        .nth(1)
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let is_rpc_replica = match args.iter().position(|arg| arg == "--rpc-replica") {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    };
+    let rollup_config_path = args
+        .into_iter()
+        .next()
         .unwrap_or_else(|| "rollup_config.toml".to_string());
     debug!("Starting demo rollup with config {}", rollup_config_path);
     let rollup_config: RollupConfig =
         from_toml_path(&rollup_config_path).context("Failed to read rollup configuration")?;
-    let rpc_config = rollup_config.rpc_config;
+    let rpc_config = &rollup_config.rpc_config;
     let address = SocketAddr::new(rpc_config.bind_host.parse()?, rpc_config.bind_port);
 
     // Initializing logging
@@ -86,8 +175,12 @@ async fn main() -> Result<(), anyhow::Error> {
         .map_err(|_err| eprintln!("Unable to set global default subscriber"))
         .expect("Cannot fail to set subscriber");
 
+    if is_rpc_replica {
+        return run_rpc_replica(&rollup_config).await;
+    }
+
     // Initialize the ledger database, which stores blocks, transactions, events, etc.
-    let ledger_db = initialize_ledger(&rollup_config.runner.storage.path);
+    let ledger_db = initialize_ledger(&rollup_config.runner.storage);
 
     // Our state transition function implements the StateTransitionRunner interface,
     // so we use that to initialize the STF
@@ -96,18 +189,20 @@ async fn main() -> Result<(), anyhow::Error> {
     // Our state transition also implements the RpcRunner interface,
     // so we use that to initialize the RPC server.
     let storage = demo_runner.get_storage();
+    rollback_uncommitted_state(&ledger_db, &storage)?;
     let is_storage_empty = storage.is_empty();
-    let mut methods = get_rpc_methods(storage);
-    let ledger_rpc_module =
-        ledger_rpc::get_ledger_rpc::<DemoBatchReceipt, DemoTxReceipt>(ledger_db.clone());
-    methods
-        .merge(ledger_rpc_module)
-        .expect("Failed to merge rpc modules");
+    let methods = build_rpc_methods(&ledger_db, storage);
 
     let _handle = tokio::spawn(async move {
         start_rpc_server(methods, address).await;
     });
 
+    if let Some(metrics_config) = rollup_config.metrics {
+        let metrics_address =
+            SocketAddr::new(metrics_config.bind_host.parse()?, metrics_config.bind_port);
+        let _metrics_handle = tokio::spawn(metrics::start_metrics_server(metrics_address));
+    }
+
     // Initialize the Celestia service using the DaService interface
     let da_service = CelestiaService::new(
         rollup_config.da.clone(),
@@ -131,11 +226,11 @@ async fn main() -> Result<(), anyhow::Error> {
         debug!("Chain is already initialized. Skipping initialization.");
     }
 
-    // HACK: Tell the rollup that you're running an empty DA layer block so that it will return the latest state root.
-    // This will be removed shortly.
-    demo.begin_slot(Default::default());
-    let (prev_state_root, _) = demo.end_slot();
-    let mut prev_state_root = prev_state_root.0;
+    // Resume from the latest committed state root. Executing a slot here would advance the slot height,
+    // which schedules the state migrations, on every restart.
+    let mut prev_state_root = demo.current_storage.get_state_root()?;
+
+    let witness_retention = rollup_config.witness_retention();
 
     // Start the main rollup loop
     let item_numbers = ledger_db.get_next_items_numbers();
@@ -164,19 +259,103 @@ async fn main() -> Result<(), anyhow::Error> {
             .is_ok());
         info!("Received {} blobs", blob_txs.len());
 
+        let slot_start = Instant::now();
+        metrics::BLOBS_PER_SLOT.observe(blob_txs.len() as f64);
         demo.begin_slot(Default::default());
         let mut data_to_commit = SlotCommit::new(filtered_block);
+        let mut num_txs = 0;
         for blob in blob_txs.clone() {
             let receipts = demo.apply_blob(blob, None);
             info!("receipts: {:?}", receipts);
+            metrics::record_batch_receipt(&receipts);
+            num_txs += receipts.tx_receipts.len();
             data_to_commit.add_batch(receipts);
         }
-        let (next_state_root, _witness) = demo.end_slot();
+        metrics::TXS_PER_SLOT.observe(num_txs as f64);
+        let (next_state_root, witness) = demo.end_slot();
+        // Keep the witness so that the slot can be proven later without re-executing it
+        data_to_commit.set_witness(prev_state_root, &witness)?;
+        data_to_commit.set_state_root(next_state_root.0);
 
         // Store the resulting receipts in the ledger database
         ledger_db.commit_slot(data_to_commit)?;
+        ledger_db.prune_witnesses(witness_retention)?;
         prev_state_root = next_state_root.0;
+        metrics::SLOT_PROCESSING_LATENCY_SECONDS.observe(slot_start.elapsed().as_secs_f64());
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sov_rollup_interface::mocks::TestBlock;
+    use sov_state::{Prefix, StateMap, WorkingSet};
+
+    /// Writes `value` to `key` and commits it to the state, returning the new root
+    fn execute_slot(storage: &ProverStorage<DefaultStorageSpec>, key: u32, value: u32) -> [u8; 32] {
+        let state_map = StateMap::<u32, u32>::new(Prefix::new(vec![0]));
+        let mut working_set = WorkingSet::new(storage.clone());
+        state_map.set(&key, &value, &mut working_set);
+        let (cache_log, witness) = working_set.freeze();
+        storage.validate_and_commit(cache_log, &witness).unwrap()
+    }
+
+    fn commit_to_ledger(ledger_db: &LedgerDB, seed: u8, state_root: [u8; 32]) {
+        let mut slot = SlotCommit::<_, DemoBatchReceipt, DemoTxReceipt>::new(TestBlock::new(
+            [seed; 32],
+            [seed - 1; 32],
+        ));
+        slot.set_state_root(state_root);
+        ledger_db.commit_slot(slot).unwrap();
+    }
+
+    fn read(storage: &ProverStorage<DefaultStorageSpec>, key: u32) -> Option<u32> {
+        let state_map = StateMap::<u32, u32>::new(Prefix::new(vec![0]));
+        state_map.get(&key, &mut WorkingSet::new(storage.clone()))
+    }
+
+    #[test]
+    fn test_crash_between_state_and_ledger_commits() {
+        let path = tempfile::tempdir().unwrap();
+        let (first_root, second_root) = {
+            let ledger_db = LedgerDB::with_path(path.path()).unwrap();
+            let storage = ProverStorage::<DefaultStorageSpec>::with_path(path.path()).unwrap();
+            let first_root = execute_slot(&storage, 1, 10);
+            commit_to_ledger(&ledger_db, 1, first_root);
+
+            // The node crashes after committing the second slot to the state, but before the ledger
+            let second_root = execute_slot(&storage, 2, 20);
+            (first_root, second_root)
+        };
+
+        let ledger_db = LedgerDB::with_path(path.path()).unwrap();
+        let storage = ProverStorage::<DefaultStorageSpec>::with_path(path.path()).unwrap();
+        assert_eq!(read(&storage, 2), Some(20));
+        rollback_uncommitted_state(&ledger_db, &storage).unwrap();
+
+        // Both databases are back at the first slot
+        assert_eq!(ledger_db.get_next_items_numbers().slot_number, 2);
+        assert_eq!(read(&storage, 1), Some(10));
+        assert_eq!(read(&storage, 2), None);
+
+        // Executing the second slot again reaches the same root, and both databases move forward together
+        let reexecuted_root = execute_slot(&storage, 2, 20);
+        assert_eq!(reexecuted_root, second_root);
+        assert_ne!(reexecuted_root, first_root);
+        commit_to_ledger(&ledger_db, 2, reexecuted_root);
+        assert_eq!(
+            ledger_db
+                .get_last_committed_slot()
+                .unwrap()
+                .and_then(|marker| marker.state_root),
+            Some(second_root)
+        );
+
+        // Restarting without a crash doesn't roll anything back
+        rollback_uncommitted_state(&ledger_db, &storage).unwrap();
+        assert_eq!(read(&storage, 2), Some(20));
+    }
+}
@@ -0,0 +1,188 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{header, Body, Request, Response, Server, StatusCode};
+use once_cell::sync::Lazy;
+use prometheus::{
+    exponential_buckets, linear_buckets, register_histogram, register_int_counter_vec, Encoder,
+    Histogram, IntCounterVec, TextEncoder,
+};
+use sov_modules_stf_template::{SequencerOutcome, TxEffect};
+use sov_rollup_interface::stf::BatchReceipt;
+use tracing::{error, info};
+
+pub static SLOT_PROCESSING_LATENCY_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "rollup_slot_processing_latency_seconds",
+        // metric description
+        "Time to execute and commit a slot in seconds, excluding the DA fetch",
+        exponential_buckets(/*start=*/ 1e-3, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static BLOBS_PER_SLOT: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "rollup_blobs_per_slot",
+        // metric description
+        "Number of rollup blobs in each slot",
+        linear_buckets(/*start=*/ 0.0, /*width=*/ 1.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static TXS_PER_SLOT: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "rollup_txs_per_slot",
+        // metric description
+        "Number of transactions in each slot",
+        exponential_buckets(/*start=*/ 1.0, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static TX_OUTCOMES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        // metric name
+        "rollup_tx_outcomes",
+        // metric description
+        "Number of executed transactions by effect",
+        // metric labels (dimensions)
+        &["effect"]
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_OUTCOMES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        // metric name
+        "rollup_sequencer_outcomes",
+        // metric description
+        "Number of applied blobs by sequencer outcome, and slashing reason if slashed",
+        // metric labels (dimensions)
+        &["outcome", "slashing_reason"]
+    )
+    .unwrap()
+});
+
+/// Records the outcomes of an applied blob and of its transactions
+pub fn record_batch_receipt(receipt: &BatchReceipt<SequencerOutcome, TxEffect>) {
+    let (outcome, slashing_reason) = match receipt.inner {
+        SequencerOutcome::Rewarded(_) => ("Rewarded", String::new()),
+        SequencerOutcome::Slashed(reason) => ("Slashed", format!("{:?}", reason)),
+        SequencerOutcome::Ignored => ("Ignored", String::new()),
+    };
+    SEQUENCER_OUTCOMES
+        .with_label_values(&[outcome, &slashing_reason])
+        .inc();
+    for tx_receipt in &receipt.tx_receipts {
+        let effect = match tx_receipt.receipt {
+            TxEffect::Reverted(_) => "Reverted",
+            TxEffect::Successful { .. } => "Successful",
+        };
+        TX_OUTCOMES.with_label_values(&[effect]).inc();
+    }
+}
+
+/// Serves the metrics of the default prometheus registry at `/metrics`, in the prometheus text format.
+/// This includes the metrics of the node's databases, DA service and storage.
+pub async fn start_metrics_server(address: SocketAddr) {
+    let make_service =
+        make_service_fn(|_| async { Ok::<_, Infallible>(service_fn(serve_metrics)) });
+    info!("Serving metrics at http://{}/metrics", address);
+    if let Err(e) = Server::bind(&address).serve(make_service).await {
+        error!("Metrics server failed: {}", e);
+    }
+}
+
+async fn serve_metrics(request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    if request.uri().path() != "/metrics" {
+        return Ok(Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .unwrap());
+    }
+
+    let encoder = TextEncoder::new();
+    let mut buffer = vec![];
+    let response = match encoder.encode(&prometheus::gather(), &mut buffer) {
+        Ok(()) => Response::builder()
+            .header(header::CONTENT_TYPE, encoder.format_type())
+            .body(Body::from(buffer)),
+        Err(e) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from(e.to_string())),
+    };
+    Ok(response.unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_modules_stf_template::{RevertReason, SlashingReason};
+    use sov_rollup_interface::stf::TransactionReceipt;
+
+    use super::*;
+
+    fn reverted() -> TxEffect {
+        TxEffect::Reverted(RevertReason {
+            module_address: None,
+            code: 0,
+            message: "Invalid nonce".to_string(),
+        })
+    }
+
+    fn tx_receipt(receipt: TxEffect) -> TransactionReceipt<TxEffect> {
+        TransactionReceipt {
+            tx_hash: [0; 32],
+            body_to_save: None,
+            events: vec![],
+            receipt,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_reports_outcomes() {
+        let slashed = SEQUENCER_OUTCOMES.with_label_values(&["Slashed", "InvalidBatchEncoding"]);
+        let reverted = TX_OUTCOMES.with_label_values(&["Reverted"]);
+        let (slashed_before, reverted_before) = (slashed.get(), reverted.get());
+
+        record_batch_receipt(&BatchReceipt {
+            batch_hash: [0; 32],
+            tx_receipts: vec![],
+            inner: SequencerOutcome::Slashed(SlashingReason::InvalidBatchEncoding),
+        });
+        record_batch_receipt(&BatchReceipt {
+            batch_hash: [1; 32],
+            tx_receipts: vec![
+                tx_receipt(reverted()),
+                tx_receipt(TxEffect::Successful {
+                    return_data: vec![],
+                }),
+                tx_receipt(reverted()),
+            ],
+            inner: SequencerOutcome::Rewarded(0),
+        });
+        assert_eq!(slashed.get(), slashed_before + 1);
+        assert_eq!(reverted.get(), reverted_before + 2);
+
+        let response = serve_metrics(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains(
+            r#"rollup_sequencer_outcomes{outcome="Slashed",slashing_reason="InvalidBatchEncoding"}"#
+        ));
+        assert!(body.contains(r#"rollup_tx_outcomes{effect="Successful"}"#));
+
+        let response = serve_metrics(Request::get("/other").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
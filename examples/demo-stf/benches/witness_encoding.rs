@@ -0,0 +1,106 @@
+//! Compares the size and native decoding time of the witness formats on the batches used by the demo-stf tests.
+//! Native decoding only approximates what a zkVM guest pays for reading its input. The cycle counts inside
+//! the guest are measured by the `witness_decode_cycles` bench of the demo prover.
+
+use borsh::BorshSerialize;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use demo_stf::app::NativeAppRunner;
+use demo_stf::genesis_config::{
+    create_demo_genesis_config, generate_address, DEMO_SEQUENCER_DA_ADDRESS, DEMO_SEQ_PUB_KEY_STR,
+    LOCKED_AMOUNT,
+};
+use demo_stf::runner_config::{Config, StorageConfig};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::Address;
+use sov_modules_stf_template::{Batch, RawTx};
+use sov_rollup_interface::mocks::{MockZkvm, TestBlob};
+use sov_rollup_interface::stf::{StateTransitionFunction, StateTransitionRunner};
+use sov_state::{ArrayWitness, CompactWitness};
+
+// The test data generators refer to the runtime as `crate::runtime`
+use demo_stf::runtime;
+
+#[allow(dead_code)]
+#[path = "../src/tests/data_generation/mod.rs"]
+mod data_generation;
+
+/// Executes a batch on a freshly initialized chain and returns the witness of its slot
+fn execute_batch(
+    make_txs: impl FnOnce(DefaultPrivateKey, DefaultPrivateKey) -> Vec<RawTx>,
+) -> ArrayWitness {
+    let path = sov_schema_db::temppath::TempPath::new();
+    let mut runner = NativeAppRunner::<MockZkvm>::new(Config {
+        storage: StorageConfig {
+            path: path.path().to_path_buf(),
+            rocksdb: Default::default(),
+        },
+        save_tx_bodies: false,
+    });
+    let demo = runner.inner_mut();
+
+    let value_setter_admin = DefaultPrivateKey::generate();
+    let election_admin = DefaultPrivateKey::generate();
+    demo.init_chain(create_demo_genesis_config(
+        LOCKED_AMOUNT + 1,
+        generate_address::<DefaultContext>(DEMO_SEQ_PUB_KEY_STR),
+        DEMO_SEQUENCER_DA_ADDRESS.to_vec(),
+        &value_setter_admin,
+        &election_admin,
+    ));
+
+    let txs = make_txs(value_setter_admin, election_admin);
+    let blob = TestBlob::new(
+        Batch { txs }.try_to_vec().unwrap(),
+        Address::try_from(&DEMO_SEQUENCER_DA_ADDRESS[..]).unwrap(),
+    );
+    demo.begin_slot(Default::default());
+    demo.apply_blob(blob, None);
+    let (_, witness) = demo.end_slot();
+    witness
+}
+
+fn generate_witnesses() -> Vec<(&'static str, ArrayWitness)> {
+    vec![
+        (
+            "election_and_value_setter",
+            execute_batch(data_generation::simulate_da),
+        ),
+        (
+            "reverted_election",
+            execute_batch(|_, election_admin| {
+                data_generation::simulate_da_with_revert_msg(election_admin)
+            }),
+        ),
+    ]
+}
+
+fn witness_encoding_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("witness_decode_native");
+    for (name, witness) in generate_witnesses() {
+        let array_bytes = bincode::serialize(&witness).unwrap();
+        let compact = CompactWitness::from(witness);
+        let compact_bytes = bincode::serialize(&compact).unwrap();
+        println!(
+            "{}: {} hints ({} distinct). ArrayWitness: {} bytes, CompactWitness: {} bytes",
+            name,
+            compact.num_hints(),
+            compact.num_distinct_hints(),
+            array_bytes.len(),
+            compact_bytes.len()
+        );
+
+        group.bench_with_input(BenchmarkId::new("array", name), &array_bytes, |b, bytes| {
+            b.iter(|| bincode::deserialize::<ArrayWitness>(bytes).unwrap())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("compact", name),
+            &compact_bytes,
+            |b, bytes| b.iter(|| bincode::deserialize::<CompactWitness>(bytes).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, witness_encoding_benchmark);
+criterion_main!(benches);
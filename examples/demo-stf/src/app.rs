@@ -2,6 +2,10 @@
 use crate::runner_config::Config;
 use crate::runtime::Runtime;
 #[cfg(feature = "native")]
+use sov_db::ledger_db::LedgerDB;
+#[cfg(feature = "native")]
+use sov_db::schema::types::SlotNumber;
+#[cfg(feature = "native")]
 pub use sov_modules_api::default_context::DefaultContext;
 pub use sov_modules_api::default_context::ZkDefaultContext;
 #[cfg(feature = "native")]
@@ -17,10 +21,14 @@ use sov_modules_stf_template::SequencerOutcome;
 use sov_modules_stf_template::TxEffect;
 #[cfg(feature = "native")]
 use sov_rollup_interface::stf::ProverConfig;
+#[cfg(feature = "native")]
+use sov_rollup_interface::stf::StateTransitionFunction;
 use sov_rollup_interface::stf::StateTransitionRunner;
 use sov_rollup_interface::stf::ZkConfig;
 use sov_rollup_interface::zk::traits::Zkvm;
 #[cfg(feature = "native")]
+use sov_state::ArrayWitness;
+#[cfg(feature = "native")]
 use sov_state::ProverStorage;
 use sov_state::Storage;
 use sov_state::ZkStorage;
@@ -58,7 +66,8 @@ impl<Vm: Zkvm> StateTransitionRunner<ProverConfig, Vm> for DemoAppRunner<Default
         let runtime = Runtime::new();
         let storage = ProverStorage::with_config(runtime_config.storage)
             .expect("Failed to open prover storage");
-        let app = AppTemplate::new(storage, runtime);
+        let app =
+            AppTemplate::new(storage, runtime).with_saved_tx_bodies(runtime_config.save_tx_bodies);
         Self(app)
     }
 
@@ -92,6 +101,26 @@ impl<Vm: Zkvm> StateTransitionRunner<ZkConfig, Vm> for DemoAppRunner<ZkDefaultCo
     }
 }
 
+#[cfg(feature = "native")]
+impl<Vm: Zkvm> ZkAppRunner<Vm> {
+    /// Creates a zk-mode runner for a slot which was already executed by a full node, and begins the slot
+    /// using the witness persisted in the ledger. The caller can then re-apply the slot's blobs and call
+    /// `end_slot` without access to the full node's state database.
+    pub fn from_persisted_witness(
+        ledger_db: &LedgerDB,
+        slot_number: u64,
+    ) -> Result<Self, anyhow::Error> {
+        let stored = ledger_db
+            .get_slot_witness(SlotNumber(slot_number))?
+            .ok_or_else(|| anyhow::anyhow!("No witness was stored for slot {}", slot_number))?;
+        let witness: ArrayWitness = stored.to_witness()?;
+
+        let mut runner = <Self as StateTransitionRunner<ZkConfig, Vm>>::new(stored.prev_state_root);
+        runner.inner_mut().begin_slot(witness);
+        Ok(runner)
+    }
+}
+
 #[cfg(feature = "native")]
 impl<Vm: Zkvm> RpcRunner for DemoAppRunner<DefaultContext, Vm> {
     type Context = DefaultContext;
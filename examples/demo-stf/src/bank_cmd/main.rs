@@ -146,7 +146,12 @@ impl SerializedTx {
         let call_msg = serde_json::from_str::<sov_bank::call::CallMessage<C>>(&call_data)?;
 
         if let sov_bank::call::CallMessage::CreateToken {
-            salt, token_name, ..
+            salt,
+            token_name,
+            symbol,
+            decimals,
+            initial_balance,
+            ..
         } = &call_msg
         {
             let token_address =
@@ -156,6 +161,11 @@ impl SerializedTx {
                 "This message will crate a new Token with Address: {}",
                 token_address
             );
+            println!(
+                "Initial balance: {} {}",
+                sov_bank::format_amount(*initial_balance, *decimals),
+                symbol
+            );
         }
 
         Ok(Runtime::<C>::encode_bank_call(call_msg))
@@ -172,6 +182,7 @@ mod test {
     };
     use demo_stf::runner_config::Config;
     use demo_stf::runtime::GenesisConfig;
+    use sov_bank::Amount;
     use sov_modules_api::Address;
     use sov_modules_stf_template::{Batch, RawTx, SequencerOutcome};
     use sov_rollup_interface::stf::StateTransitionRunner;
@@ -217,7 +228,11 @@ mod test {
 
             let path = path.as_ref().to_path_buf();
             let runner_config = Config {
-                storage: sov_state::config::Config { path },
+                storage: sov_state::config::Config {
+                    path,
+                    rocksdb: Default::default(),
+                },
+                save_tx_bodies: false,
             };
 
             Self {
@@ -300,7 +315,7 @@ mod test {
         demo: &mut DemoApp<DefaultContext, MockZkvm>,
         token_deployer_address: &Address,
         user_address: Address,
-    ) -> Option<u64> {
+    ) -> Option<Amount> {
         let token_address = create_token_address(token_deployer_address);
 
         let mut working_set = WorkingSet::new(demo.current_storage.clone());
@@ -326,7 +341,7 @@ mod test {
     }
 
     pub fn create_demo_config(
-        initial_sequencer_balance: u64,
+        initial_sequencer_balance: Amount,
         value_setter_admin_private_key: &DefaultPrivateKey,
         election_admin_private_key: &DefaultPrivateKey,
     ) -> GenesisConfig<DefaultContext> {
@@ -1,48 +1,56 @@
 /// Creates config for a rollup with some default settings, the config is used in demos and tests.
 use crate::runtime::GenesisConfig;
+use sov_bank::Amount;
 use sov_election::ElectionConfig;
 pub use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
 use sov_modules_api::Context;
 use sov_modules_api::Hasher;
+use sov_modules_api::ModuleInfo;
 use sov_modules_api::PublicKey;
 use sov_modules_api::Spec;
 pub use sov_state::config::Config as StorageConfig;
 use sov_value_setter::ValueSetterConfig;
 
 pub const DEMO_SEQUENCER_DA_ADDRESS: [u8; 32] = [1; 32];
-pub const LOCKED_AMOUNT: u64 = 200;
+pub const LOCKED_AMOUNT: Amount = 200;
 pub const DEMO_SEQ_PUB_KEY_STR: &str = "seq_pub_key";
 pub const DEMO_TOKEN_NAME: &str = "sov-demo-token";
+pub const DEMO_TOKEN_SYMBOL: &str = "DEMO";
 
 pub fn create_demo_genesis_config<C: Context>(
-    initial_sequencer_balance: u64,
+    initial_sequencer_balance: Amount,
     sequencer_address: C::Address,
     sequencer_da_address: Vec<u8>,
     value_setter_admin_private_key: &DefaultPrivateKey,
     election_admin_private_key: &DefaultPrivateKey,
 ) -> GenesisConfig<C> {
-    let token_config: sov_bank::TokenConfig<C> = sov_bank::TokenConfig {
+    // The native token pays for the bond of the sequencer
+    let native_token_config: sov_bank::TokenConfig<C> = sov_bank::TokenConfig {
         token_name: DEMO_TOKEN_NAME.to_owned(),
+        symbol: DEMO_TOKEN_SYMBOL.to_owned(),
+        decimals: 0,
+        uri: None,
+        supply_cap: None,
         address_and_balances: vec![(sequencer_address.clone(), initial_sequencer_balance)],
     };
 
+    // The sequencer registry locks the bond of the sequencer
+    let sequencer_module_address = sov_sequencer_registry::Sequencer::<C>::new()
+        .address()
+        .clone();
     let bank_config = sov_bank::BankConfig {
-        tokens: vec![token_config],
+        native_token: Some(native_token_config),
+        tokens: vec![],
+        authorized_modules: vec![sequencer_module_address],
     };
 
-    let token_address = sov_bank::create_token_address::<C>(
-        &bank_config.tokens[0].token_name,
-        &sov_bank::genesis::DEPLOYER,
-        sov_bank::genesis::SALT,
-    );
-
     let sequencer_config = sov_sequencer_registry::SequencerConfig {
         seq_rollup_address: sequencer_address,
         seq_da_address: sequencer_da_address,
         coins_to_lock: sov_bank::Coins {
             amount: LOCKED_AMOUNT,
-            token_address,
+            token_address: sov_bank::Bank::<C>::new().native_token(),
         },
     };
 
@@ -1,6 +1,7 @@
 use crate::runtime::Runtime;
 use sov_modules_api::{
     hooks::{ApplyBlobHooks, TxHooks},
+    migration::{RuntimeMigrations, ScheduledMigration},
     transaction::Transaction,
     Context, Spec,
 };
@@ -54,3 +55,12 @@ impl<C: Context> ApplyBlobHooks for Runtime<C> {
         self.sequencer.end_blob_hook(reward, working_set)
     }
 }
+
+impl<C: Context> RuntimeMigrations for Runtime<C> {
+    type Context = C;
+
+    fn scheduled_migrations() -> Vec<ScheduledMigration<Self>> {
+        // No module has changed its state layout yet.
+        Vec::new()
+    }
+}
@@ -7,4 +7,4 @@ pub mod runner_config;
 pub mod runtime;
 #[cfg(test)]
 pub mod tests;
-pub use sov_state::ArrayWitness;
+pub use sov_state::{ArrayWitness, CompactWitness};
@@ -20,6 +20,9 @@ pub fn from_toml_path<P: AsRef<Path>, R: DeserializeOwned>(path: P) -> anyhow::R
 #[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Config {
     pub storage: StorageConfig,
+    /// Whether to persist the body of each transaction in the ledger, so that it can be served over RPC
+    #[serde(default)]
+    pub save_tx_bodies: bool,
 }
 
 #[cfg(test)]
@@ -48,7 +51,9 @@ mod tests {
         let expected = Config {
             storage: StorageConfig {
                 path: PathBuf::from("/tmp"),
+                rocksdb: Default::default(),
             },
+            save_tx_bodies: false,
         };
         assert_eq!(config, expected);
     }
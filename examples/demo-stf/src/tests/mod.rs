@@ -1,4 +1,5 @@
 use borsh::BorshSerialize;
+use sov_bank::Amount;
 use sov_modules_api::{
     default_context::DefaultContext, default_signature::private_key::DefaultPrivateKey, Address,
 };
@@ -19,6 +20,7 @@ use crate::{
 mod data_generation;
 mod stf_tests;
 mod tx_revert_tests;
+mod zk_simulator_tests;
 pub(crate) type C = DefaultContext;
 
 pub type TestBlob = sov_rollup_interface::mocks::TestBlob<Address>;
@@ -38,7 +40,7 @@ pub fn create_new_demo(
 }
 
 pub fn create_demo_config(
-    initial_sequencer_balance: u64,
+    initial_sequencer_balance: Amount,
     value_setter_admin_private_key: &DefaultPrivateKey,
     election_admin_private_key: &DefaultPrivateKey,
 ) -> GenesisConfig<DefaultContext> {
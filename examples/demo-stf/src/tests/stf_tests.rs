@@ -1,18 +1,31 @@
 #[cfg(test)]
 pub mod test {
     use crate::{
-        genesis_config::{DEMO_SEQUENCER_DA_ADDRESS, LOCKED_AMOUNT},
+        app::{get_rpc_methods, ZkAppRunner},
+        genesis_config::{
+            generate_address, DEMO_SEQUENCER_DA_ADDRESS, DEMO_SEQ_PUB_KEY_STR, DEMO_TOKEN_NAME,
+            DEMO_TOKEN_SYMBOL, LOCKED_AMOUNT,
+        },
         runtime::Runtime,
         tests::{
             create_demo_config, create_new_demo, data_generation::simulate_da, has_tx_events,
             new_test_blob, C,
         },
     };
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use jsonrpsee::types::EmptyServerParams;
+    use sov_bank::query::TokenInfoResponse;
+    use sov_db::ledger_db::{LedgerDB, SlotCommit};
     use sov_modules_api::{
         default_context::DefaultContext, default_signature::private_key::DefaultPrivateKey,
+        transaction::Transaction, Address, AddressBech32, PublicKey, Spec,
+    };
+    use sov_modules_stf_template::{Batch, RawTx, SequencerOutcome, TxEffect};
+    use sov_rollup_interface::{
+        mocks::{MockZkvm, TestBlock},
+        rpc::{LedgerRpcProvider, QueryMode},
+        stf::{StateTransitionFunction, StateTransitionRunner},
     };
-    use sov_modules_stf_template::{Batch, SequencerOutcome};
-    use sov_rollup_interface::{mocks::MockZkvm, stf::StateTransitionFunction};
     use sov_state::{ProverStorage, WorkingSet};
 
     #[test]
@@ -71,6 +84,105 @@ pub mod test {
         }
     }
 
+    #[test]
+    fn test_saved_tx_bodies() {
+        for save_tx_bodies in [false, true] {
+            let path = sov_schema_db::temppath::TempPath::new();
+            let value_setter_admin_private_key = DefaultPrivateKey::generate();
+            let election_admin_private_key = DefaultPrivateKey::generate();
+            let config = create_demo_config(
+                LOCKED_AMOUNT + 1,
+                &value_setter_admin_private_key,
+                &election_admin_private_key,
+            );
+
+            let mut demo = create_new_demo(&path).with_saved_tx_bodies(save_tx_bodies);
+            StateTransitionFunction::<MockZkvm>::init_chain(&mut demo, config);
+            StateTransitionFunction::<MockZkvm>::begin_slot(&mut demo, Default::default());
+
+            let txs = simulate_da(value_setter_admin_private_key, election_admin_private_key);
+            let bodies: Vec<_> = txs.iter().map(|tx| Some(tx.data.clone())).collect();
+            let apply_blob_outcome = StateTransitionFunction::<MockZkvm>::apply_blob(
+                &mut demo,
+                new_test_blob(Batch { txs }, &DEMO_SEQUENCER_DA_ADDRESS),
+                None,
+            );
+            StateTransitionFunction::<MockZkvm>::end_slot(&mut demo);
+
+            let saved: Vec<_> = apply_blob_outcome
+                .tx_receipts
+                .into_iter()
+                .map(|receipt| receipt.body_to_save)
+                .collect();
+            if save_tx_bodies {
+                assert_eq!(saved, bodies);
+            } else {
+                assert_eq!(saved.len(), bodies.len());
+                assert!(saved.iter().all(Option::is_none));
+            }
+        }
+    }
+
+    #[test]
+    fn test_return_data_is_readable_from_the_ledger() {
+        let path = sov_schema_db::temppath::TempPath::new();
+        let ledger_db = LedgerDB::temporary();
+        let config = create_demo_config(
+            LOCKED_AMOUNT + 1,
+            &DefaultPrivateKey::generate(),
+            &DefaultPrivateKey::generate(),
+        );
+        let mut demo = create_new_demo(&path);
+        StateTransitionFunction::<MockZkvm>::init_chain(&mut demo, config);
+        StateTransitionFunction::<MockZkvm>::begin_slot(&mut demo, Default::default());
+
+        // Creating a token returns the address of the new token
+        let sender = DefaultPrivateKey::generate();
+        let sender_address = sender.pub_key().to_address::<<C as Spec>::Address>();
+        let message = Runtime::<C>::encode_bank_call(sov_bank::call::CallMessage::CreateToken {
+            salt: 11,
+            token_name: "ReturnedToken".to_owned(),
+            symbol: "RET".to_owned(),
+            decimals: 0,
+            uri: None,
+            initial_balance: 100,
+            minter_address: sender_address.clone(),
+            authorized_minters: vec![],
+            supply_cap: None,
+        });
+        let sig = Transaction::<C>::sign(&sender, &message, 0);
+        let tx = Transaction::<C>::new(message, sender.pub_key(), sig, 0);
+        let txs = vec![RawTx {
+            data: tx.try_to_vec().unwrap(),
+        }];
+
+        let apply_blob_outcome = StateTransitionFunction::<MockZkvm>::apply_blob(
+            &mut demo,
+            new_test_blob(Batch { txs }, &DEMO_SEQUENCER_DA_ADDRESS),
+            None,
+        );
+        StateTransitionFunction::<MockZkvm>::end_slot(&mut demo);
+        let tx_hash = apply_blob_outcome.tx_receipts[0].tx_hash;
+
+        let mut data_to_commit =
+            SlotCommit::<_, SequencerOutcome, TxEffect>::new(TestBlock::new([1; 32], [0; 32]));
+        data_to_commit.add_batch(apply_blob_outcome);
+        ledger_db.commit_slot(data_to_commit).unwrap();
+
+        let tx = ledger_db
+            .get_tx_by_hash::<TxEffect>(&tx_hash, QueryMode::Standard)
+            .unwrap()
+            .expect("The transaction was committed");
+        let return_data = match tx.custom_receipt {
+            TxEffect::Successful { return_data } => return_data,
+            TxEffect::Reverted(reason) => panic!("Token creation reverted: {:?}", reason),
+        };
+        assert_eq!(
+            Address::try_from_slice(&return_data).unwrap(),
+            sov_bank::create_token_address::<C>("ReturnedToken", sender_address.as_ref(), 11)
+        );
+    }
+
     #[test]
     fn test_demo_values_in_cache() {
         let path = sov_schema_db::temppath::TempPath::new();
@@ -174,6 +286,46 @@ pub mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_sequencer_bond_is_in_the_native_token() {
+        let path = sov_schema_db::temppath::TempPath::new();
+        let config = create_demo_config(
+            LOCKED_AMOUNT + 1,
+            &DefaultPrivateKey::generate(),
+            &DefaultPrivateKey::generate(),
+        );
+        let runtime = Runtime::<C>::new();
+        let native_token = runtime.bank.native_token();
+        assert_eq!(config.sequencer.coins_to_lock.token_address, native_token);
+
+        let mut demo = create_new_demo(&path);
+        StateTransitionFunction::<MockZkvm>::init_chain(&mut demo, config);
+
+        // The sequencer holds the native tokens it locks for each batch
+        let mut working_set = WorkingSet::new(demo.current_storage.clone());
+        let sequencer_address = generate_address::<C>(DEMO_SEQ_PUB_KEY_STR);
+        assert_eq!(
+            runtime
+                .bank
+                .get_balance_of(sequencer_address, native_token.clone(), &mut working_set),
+            Some(LOCKED_AMOUNT + 1)
+        );
+
+        // The native token is served by the RPC of the runtime
+        let rpc = get_rpc_methods(demo.current_storage.clone());
+        let response: TokenInfoResponse = rpc
+            .call("bank_nativeToken", EmptyServerParams::new())
+            .await
+            .unwrap();
+        let token_info = response
+            .token_info
+            .expect("The native token is created at genesis");
+        assert_eq!(token_info.address, AddressBech32::from(native_token));
+        assert_eq!(token_info.name, DEMO_TOKEN_NAME);
+        assert_eq!(token_info.symbol, DEMO_TOKEN_SYMBOL);
+        assert_eq!(token_info.total_supply, LOCKED_AMOUNT + 1);
+    }
+
     #[test]
     fn test_sequencer_insufficient_funds() {
         let path = sov_schema_db::temppath::TempPath::new();
@@ -208,4 +360,49 @@ pub mod test {
         // Assert that there are no events
         assert!(!has_tx_events(&apply_blob_outcome));
     }
+
+    #[test]
+    fn test_replay_slot_from_persisted_witness() {
+        let path = sov_schema_db::temppath::TempPath::new();
+        let ledger_db = LedgerDB::temporary();
+
+        let value_setter_admin_private_key = DefaultPrivateKey::generate();
+        let election_admin_private_key = DefaultPrivateKey::generate();
+
+        let config = create_demo_config(
+            LOCKED_AMOUNT + 1,
+            &value_setter_admin_private_key,
+            &election_admin_private_key,
+        );
+
+        let mut demo = create_new_demo(&path);
+        StateTransitionFunction::<MockZkvm>::init_chain(&mut demo, config);
+        StateTransitionFunction::<MockZkvm>::begin_slot(&mut demo, Default::default());
+        let (genesis_root, _) = StateTransitionFunction::<MockZkvm>::end_slot(&mut demo);
+
+        let txs = simulate_da(value_setter_admin_private_key, election_admin_private_key);
+        let blob = new_test_blob(Batch { txs }, &DEMO_SEQUENCER_DA_ADDRESS);
+
+        StateTransitionFunction::<MockZkvm>::begin_slot(&mut demo, Default::default());
+        StateTransitionFunction::<MockZkvm>::apply_blob(&mut demo, blob.clone(), None);
+        let (native_root, witness) = StateTransitionFunction::<MockZkvm>::end_slot(&mut demo);
+
+        let mut data_to_commit = SlotCommit::<_, (), ()>::new(TestBlock::new([1; 32], [0; 32]));
+        data_to_commit
+            .set_witness(genesis_root.0, &witness)
+            .unwrap();
+        ledger_db.commit_slot(data_to_commit).unwrap();
+
+        // Re-execute the slot in zk mode, using only the witness stored in the ledger
+        let mut zk_runner = ZkAppRunner::<MockZkvm>::from_persisted_witness(&ledger_db, 1).unwrap();
+        let zk_demo = zk_runner.inner_mut();
+        let apply_blob_outcome = zk_demo.apply_blob(blob, None);
+        assert!(
+            matches!(apply_blob_outcome.inner, SequencerOutcome::Rewarded(0),),
+            "Sequencer execution should have succeeded but failed "
+        );
+        let (zk_root, _) = zk_demo.end_slot();
+
+        assert_eq!(zk_root, native_root);
+    }
 }
@@ -3,10 +3,11 @@ use crate::{
     runtime::Runtime,
     tests::{data_generation::simulate_da_with_bad_serialization, has_tx_events},
 };
+use sov_bank::Amount;
 use sov_modules_api::{
-    default_context::DefaultContext, default_signature::private_key::DefaultPrivateKey,
+    default_context::DefaultContext, default_signature::private_key::DefaultPrivateKey, ModuleInfo,
 };
-use sov_modules_stf_template::{Batch, SequencerOutcome, SlashingReason};
+use sov_modules_stf_template::{Batch, SequencerOutcome, SlashingReason, TxEffect};
 use sov_rollup_interface::{mocks::MockZkvm, stf::StateTransitionFunction};
 use sov_state::{ProverStorage, WorkingSet};
 
@@ -16,8 +17,8 @@ use super::{
     new_test_blob,
 };
 
-const SEQUENCER_BALANCE_DELTA: u64 = 1;
-const SEQUENCER_BALANCE: u64 = LOCKED_AMOUNT + SEQUENCER_BALANCE_DELTA;
+const SEQUENCER_BALANCE_DELTA: Amount = 1;
+const SEQUENCER_BALANCE: Amount = LOCKED_AMOUNT + SEQUENCER_BALANCE_DELTA;
 
 #[test]
 fn test_tx_revert() {
@@ -53,6 +54,23 @@ fn test_tx_revert() {
         // Some events were observed
         assert!(has_tx_events(&apply_blob_outcome));
 
+        // The reverted transaction reports why the election module rejected it
+        let reasons: Vec<_> = apply_blob_outcome
+            .tx_receipts
+            .iter()
+            .filter_map(|receipt| match &receipt.receipt {
+                TxEffect::Reverted(reason) => Some(reason),
+                TxEffect::Successful { .. } => None,
+            })
+            .collect();
+        assert_eq!(reasons.len(), 1);
+        let election_address = Runtime::<DefaultContext>::new()
+            .election
+            .address()
+            .to_string();
+        assert_eq!(reasons[0].module_address, Some(election_address));
+        assert!(!reasons[0].message.is_empty());
+
         StateTransitionFunction::<MockZkvm>::end_slot(&mut demo);
     }
 
@@ -0,0 +1,130 @@
+use sov_modules_api::default_context::ZkDefaultContext;
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_stf_template::zk_simulator::{simulate_slot, SimulationError};
+use sov_modules_stf_template::{Batch, SequencerOutcome};
+use sov_rollup_interface::{mocks::MockZkvm, stf::StateTransitionFunction};
+use sov_state::ArrayWitness;
+
+use super::{create_demo_config, create_new_demo, data_generation::simulate_da, new_test_blob};
+use crate::genesis_config::{DEMO_SEQUENCER_DA_ADDRESS, LOCKED_AMOUNT};
+use crate::runtime::Runtime;
+
+/// Executes a slot natively, returning the blob, the state roots before and after the slot, and its witness.
+fn execute_natively() -> (super::TestBlob, [u8; 32], [u8; 32], ArrayWitness) {
+    let path = sov_schema_db::temppath::TempPath::new();
+    let value_setter_admin_private_key = DefaultPrivateKey::generate();
+    let election_admin_private_key = DefaultPrivateKey::generate();
+
+    let config = create_demo_config(
+        LOCKED_AMOUNT + 1,
+        &value_setter_admin_private_key,
+        &election_admin_private_key,
+    );
+
+    let mut demo = create_new_demo(&path);
+    StateTransitionFunction::<MockZkvm>::init_chain(&mut demo, config);
+    StateTransitionFunction::<MockZkvm>::begin_slot(&mut demo, Default::default());
+    let (genesis_root, _) = StateTransitionFunction::<MockZkvm>::end_slot(&mut demo);
+
+    let txs = simulate_da(value_setter_admin_private_key, election_admin_private_key);
+    let blob = new_test_blob(Batch { txs }, &DEMO_SEQUENCER_DA_ADDRESS);
+
+    StateTransitionFunction::<MockZkvm>::begin_slot(&mut demo, Default::default());
+    StateTransitionFunction::<MockZkvm>::apply_blob(&mut demo, blob.clone(), None);
+    let (next_root, witness) = StateTransitionFunction::<MockZkvm>::end_slot(&mut demo);
+
+    (blob, genesis_root.0, next_root.0, witness)
+}
+
+/// Round-trips the witness through serde, applying `edit` to its hints
+fn edit_hints(
+    witness: &ArrayWitness,
+    edit: impl FnOnce(&mut Vec<serde_json::Value>),
+) -> ArrayWitness {
+    let mut json = serde_json::to_value(witness).unwrap();
+    edit(json["hints"].as_array_mut().unwrap());
+    serde_json::from_value(json).unwrap()
+}
+
+#[test]
+fn test_simulator_replays_native_witness() {
+    let (blob, prev_root, next_root, witness) = execute_natively();
+
+    let slot = simulate_slot::<_, MockZkvm>(
+        Runtime::<ZkDefaultContext>::new(),
+        prev_root,
+        witness,
+        vec![blob],
+    )
+    .unwrap();
+
+    assert_eq!(slot.state_root, next_root);
+    assert_eq!(slot.batch_receipts.len(), 1);
+    assert_eq!(slot.batch_receipts[0].inner, SequencerOutcome::Rewarded(0));
+}
+
+#[test]
+fn test_simulator_reports_missing_hints() {
+    let (blob, prev_root, _, witness) = execute_natively();
+
+    // The very first read of the slot has no hint to consume
+    let err = simulate_slot::<_, MockZkvm>(
+        Runtime::<ZkDefaultContext>::new(),
+        prev_root,
+        ArrayWitness::default(),
+        vec![blob.clone()],
+    )
+    .unwrap_err();
+    match err {
+        SimulationError::HintMismatch(mismatch) => {
+            assert_eq!(mismatch.hint_index, 0);
+            assert!(mismatch.key.is_some());
+        }
+        other => panic!("Expected a hint mismatch, got {:?}", other),
+    }
+
+    // The last hint is the multiproof of the whole slot, which isn't tied to a single key
+    let num_hints = witness.num_hints();
+    let truncated = edit_hints(&witness, |hints| {
+        hints.pop();
+    });
+    let err = simulate_slot::<_, MockZkvm>(
+        Runtime::<ZkDefaultContext>::new(),
+        prev_root,
+        truncated,
+        vec![blob],
+    )
+    .unwrap_err();
+    match err {
+        SimulationError::HintMismatch(mismatch) => {
+            assert_eq!(mismatch.hint_index, num_hints - 1);
+            assert_eq!(mismatch.key, None);
+        }
+        other => panic!("Expected a hint mismatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_simulator_reports_unused_hints() {
+    let (blob, prev_root, _, witness) = execute_natively();
+    let num_hints = witness.num_hints();
+
+    let extended = edit_hints(&witness, |hints| {
+        hints.push(serde_json::json!([0u8]));
+    });
+    let err = simulate_slot::<_, MockZkvm>(
+        Runtime::<ZkDefaultContext>::new(),
+        prev_root,
+        extended,
+        vec![blob],
+    )
+    .unwrap_err();
+
+    assert!(matches!(
+        err,
+        SimulationError::UnusedHints {
+            first_unused_index,
+            num_hints: total,
+        } if first_unused_index == num_hints && total == num_hints + 1
+    ));
+}
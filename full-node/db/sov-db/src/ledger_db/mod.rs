@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     path::Path,
     sync::{Arc, Mutex},
 };
@@ -6,21 +7,28 @@ use std::{
 use serde::Serialize;
 use sov_rollup_interface::{
     db::SeekKeyEncoder,
+    rpc::EventResponse,
     services::da::SlotData,
     stf::{BatchReceipt, Event},
 };
-use sov_schema_db::{Schema, DB};
+use sov_schema_db::{Schema, SchemaBatch, DB};
+use tokio::sync::broadcast;
 
 use crate::{
-    rocks_db_config::gen_rocksdb_options,
+    rocks_db_config::{
+        collect_stats, gen_cf_descriptors, gen_rocksdb_options, gen_secondary_rocksdb_options,
+        ColumnFamilyStats, RocksdbConfig,
+    },
     schema::{
         tables::{
-            BatchByHash, BatchByNumber, EventByKey, EventByNumber, SlotByHash, SlotByNumber,
-            TxByHash, TxByNumber, LEDGER_TABLES,
+            BatchByHash, BatchByNumber, EventByKey, EventByNumber, LastCommittedSlot,
+            LedgerSchemaVersion, ProofBySlotRange, SlotByHash, SlotByNumber, SlotWitnessByNumber,
+            TxByHash, TxByNumber, LEDGER_SCHEMA_VERSION, LEDGER_TABLES,
         },
         types::{
-            split_tx_for_storage, BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot,
-            StoredTransaction, TxNumber,
+            split_tx_for_storage, BatchNumber, DbHash, EventNumber, SlotCommitMarker, SlotNumber,
+            StoredBatch, StoredProof, StoredSlot, StoredTransaction, StoredWitness, TxNumber,
+            WitnessRetention,
         },
     },
 };
@@ -28,6 +36,9 @@ use crate::{
 mod rpc;
 
 const LEDGER_DB_PATH_SUFFIX: &str = "ledger";
+/// The number of slot notifications buffered for each subscriber. Subscribers which fall further
+/// behind miss notifications.
+const SLOT_NOTIFICATION_CAPACITY: usize = 64;
 
 #[derive(Clone)]
 /// A database which stores the ledger history (slots, transactions, events, etc).
@@ -38,6 +49,19 @@ pub struct LedgerDB {
     /// requires transactions to be executed before being committed.
     db: Arc<DB>,
     next_item_numbers: Arc<Mutex<ItemNumbers>>,
+    slot_notifications: broadcast::Sender<Arc<SlotNotification>>,
+    /// The hashes of the transactions accepted by the sequencer which aren't committed yet
+    pending_txs: Arc<Mutex<HashSet<DbHash>>>,
+}
+
+/// Published to the subscribers of [`LedgerDB::subscribe_slots`] after each slot is committed
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotNotification {
+    pub number: SlotNumber,
+    /// The hash and number of each transaction in the slot, in order
+    pub txs: Vec<(DbHash, TxNumber)>,
+    /// The events emitted by the transactions in the slot, in order
+    pub events: Vec<EventResponse>,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -51,6 +75,8 @@ pub struct ItemNumbers {
 pub struct SlotCommit<S: SlotData, B, T> {
     slot_data: S,
     batch_receipts: Vec<BatchReceipt<B, T>>,
+    witness: Option<StoredWitness>,
+    state_root: Option<DbHash>,
     num_txs: usize,
     num_events: usize,
 }
@@ -60,6 +86,8 @@ impl<S: SlotData, B, T> SlotCommit<S, B, T> {
         Self {
             slot_data,
             batch_receipts: vec![],
+            witness: None,
+            state_root: None,
             num_txs: 0,
             num_events: 0,
         }
@@ -73,33 +101,91 @@ impl<S: SlotData, B, T> SlotCommit<S, B, T> {
         self.batch_receipts.push(batch);
         self.num_events += events_this_batch;
     }
+
+    /// Persist the witness generated while executing this slot alongside the slot,
+    /// so that it can be proven later by a separate process.
+    pub fn set_witness<W: Serialize>(
+        &mut self,
+        prev_state_root: DbHash,
+        witness: &W,
+    ) -> Result<(), anyhow::Error> {
+        self.witness = Some(StoredWitness::new(prev_state_root, witness)?);
+        Ok(())
+    }
+
+    /// Record the state root after executing this slot in the commit marker, so that state changes
+    /// made by slots which never reached the ledger can be detected and rolled back after a crash.
+    pub fn set_state_root(&mut self, state_root: DbHash) {
+        self.state_root = Some(state_root);
+    }
 }
 
 impl LedgerDB {
     pub fn with_path(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        Self::with_config(path, &Default::default())
+    }
+
+    /// Opens the ledger stored in `path`, tuning rocksdb with the provided config
+    pub fn with_config(
+        path: impl AsRef<Path>,
+        config: &RocksdbConfig,
+    ) -> Result<Self, anyhow::Error> {
         let path = path.as_ref().join(LEDGER_DB_PATH_SUFFIX);
-        let inner = DB::open(
+        let inner = DB::open_cf(
+            &gen_rocksdb_options(config, false),
             path,
             "ledger-db",
-            LEDGER_TABLES.iter().copied(),
-            &gen_rocksdb_options(&Default::default(), false),
+            gen_cf_descriptors(config, LEDGER_TABLES.iter().copied())?,
         )?;
 
-        let next_item_numbers = ItemNumbers {
-            slot_number: Self::last_version_written(&inner, SlotByNumber)?.unwrap_or_default() + 1,
-            batch_number: Self::last_version_written(&inner, BatchByNumber)?.unwrap_or_default()
-                + 1,
-            tx_number: Self::last_version_written(&inner, TxByNumber)?.unwrap_or_default() + 1,
-            event_number: Self::last_version_written(&inner, EventByNumber)?.unwrap_or_default()
-                + 1,
-        };
+        if Self::check_schema_version(&inner)?.is_none() {
+            inner.put::<LedgerSchemaVersion>(&(), &LEDGER_SCHEMA_VERSION)?;
+        }
+        let next_item_numbers = Self::committed_item_numbers(&inner)?;
 
         Ok(Self {
             db: Arc::new(inner),
             next_item_numbers: Arc::new(Mutex::new(next_item_numbers)),
+            slot_notifications: broadcast::channel(SLOT_NOTIFICATION_CAPACITY).0,
+            pending_txs: Default::default(),
         })
     }
 
+    /// Opens the ledger stored in `path` as a read-only secondary of the process which writes it.
+    /// The secondary keeps its own info logs in `secondary_path`, and only sees the writes of the primary
+    /// after calling [`LedgerDB::catch_up_with_primary`].
+    pub fn secondary(
+        path: impl AsRef<Path>,
+        secondary_path: impl AsRef<Path>,
+        config: &RocksdbConfig,
+    ) -> Result<Self, anyhow::Error> {
+        let inner = DB::open_cf_as_secondary(
+            &gen_secondary_rocksdb_options(config),
+            path.as_ref().join(LEDGER_DB_PATH_SUFFIX),
+            secondary_path.as_ref().join(LEDGER_DB_PATH_SUFFIX),
+            "ledger-db-secondary",
+            LEDGER_TABLES.to_vec(),
+        )?;
+        // A new ledger is versioned by its primary
+        Self::check_schema_version(&inner)?;
+        let next_item_numbers = Self::committed_item_numbers(&inner)?;
+
+        Ok(Self {
+            db: Arc::new(inner),
+            next_item_numbers: Arc::new(Mutex::new(next_item_numbers)),
+            slot_notifications: broadcast::channel(SLOT_NOTIFICATION_CAPACITY).0,
+            pending_txs: Default::default(),
+        })
+    }
+
+    /// Catches a secondary ledger up with the slots committed by the primary
+    pub fn catch_up_with_primary(&self) -> Result<(), anyhow::Error> {
+        let mut next_item_numbers = self.next_item_numbers.lock().unwrap();
+        self.db.try_catch_up_with_primary()?;
+        *next_item_numbers = Self::committed_item_numbers(&self.db)?;
+        Ok(())
+    }
+
     /// A rocksdb instance which stores its data in a tempdir
     #[cfg(any(test, feature = "temp"))]
     pub fn temporary() -> Self {
@@ -107,10 +193,213 @@ impl LedgerDB {
         Self::with_path(path).unwrap()
     }
 
+    /// Fails if the ledger was written with a different [`LEDGER_SCHEMA_VERSION`], since its tables can't
+    /// be decoded. Returns `None` if the ledger is empty and hasn't been versioned yet.
+    fn check_schema_version(db: &DB) -> Result<Option<u64>, anyhow::Error> {
+        match db.get::<LedgerSchemaVersion>(&())? {
+            Some(version) if version == LEDGER_SCHEMA_VERSION => Ok(Some(version)),
+            Some(version) => anyhow::bail!(
+                "The ledger was written with schema version {}, but this node reads version {}. Delete the ledger and resync it",
+                version,
+                LEDGER_SCHEMA_VERSION
+            ),
+            None => {
+                anyhow::ensure!(
+                    Self::last_version_written(db, SlotByNumber)?.is_none(),
+                    "The ledger was written before its schema was versioned, and its events can't be read. Delete the ledger and resync it"
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    fn committed_item_numbers(db: &DB) -> Result<ItemNumbers, anyhow::Error> {
+        // Items after the last fully committed slot can only have been left by a crash, so they are ignored
+        // and will be overwritten. Ledgers written before the marker existed fall back to the last items written.
+        let next_item_numbers = match db.get::<LastCommittedSlot>(&())? {
+            Some(marker) => ItemNumbers {
+                slot_number: marker.slot_number.0 + 1,
+                batch_number: marker.next_batch_number.0,
+                tx_number: marker.next_tx_number.0,
+                event_number: marker.next_event_number.0,
+            },
+            None => ItemNumbers {
+                slot_number: Self::last_version_written(db, SlotByNumber)?.unwrap_or_default() + 1,
+                batch_number: Self::last_version_written(db, BatchByNumber)?.unwrap_or_default()
+                    + 1,
+                tx_number: Self::last_version_written(db, TxByNumber)?.unwrap_or_default() + 1,
+                event_number: Self::last_version_written(db, EventByNumber)?.unwrap_or_default()
+                    + 1,
+            },
+        };
+
+        Ok(next_item_numbers)
+    }
+
+    /// Subscribes to the slots committed from now on. Only slots committed through this `LedgerDB`
+    /// (or its clones) are published, so secondaries never receive notifications.
+    pub fn subscribe_slots(&self) -> broadcast::Receiver<Arc<SlotNotification>> {
+        self.slot_notifications.subscribe()
+    }
+
+    /// Marks a transaction accepted by the sequencer as pending, until a slot including it is committed or
+    /// it's dropped with [`LedgerDB::remove_pending_tx`]. Pending transactions are only known to the
+    /// process which accepted them, so secondaries never report them.
+    pub fn add_pending_tx(&self, hash: DbHash) {
+        self.pending_txs.lock().unwrap().insert(hash);
+    }
+
+    /// Forgets a pending transaction which the sequencer dropped without submitting it
+    pub fn remove_pending_tx(&self, hash: &DbHash) {
+        self.pending_txs.lock().unwrap().remove(hash);
+    }
+
+    pub(crate) fn is_pending_tx(&self, hash: &DbHash) -> bool {
+        self.pending_txs.lock().unwrap().contains(hash)
+    }
+
     pub fn get_next_items_numbers(&self) -> ItemNumbers {
         self.next_item_numbers.lock().unwrap().clone()
     }
 
+    /// Returns the rocksdb stats of each table of the ledger
+    pub fn rocksdb_stats(&self) -> Result<Vec<ColumnFamilyStats>, anyhow::Error> {
+        collect_stats(&self.db, LEDGER_TABLES.iter().copied())
+    }
+
+    /// Returns the marker of the last slot which was fully committed, if any.
+    pub fn get_last_committed_slot(&self) -> Result<Option<SlotCommitMarker>, anyhow::Error> {
+        self.db.get::<LastCommittedSlot>(&())
+    }
+
+    /// Gets the slot with the provided number, if it has been committed.
+    pub fn get_slot(&self, number: SlotNumber) -> Result<Option<StoredSlot>, anyhow::Error> {
+        self.db.get::<SlotByNumber>(&number)
+    }
+
+    /// Stores the witness generated while executing the slot with the provided number, so that
+    /// the slot can be proven later without being re-executed.
+    /// Prefer [`SlotCommit::set_witness`] when the witness is available before the slot is committed.
+    pub fn put_slot_witness<W: Serialize>(
+        &self,
+        slot_number: SlotNumber,
+        prev_state_root: DbHash,
+        witness: &W,
+    ) -> Result<(), anyhow::Error> {
+        self.db.put::<SlotWitnessByNumber>(
+            &slot_number,
+            &StoredWitness::new(prev_state_root, witness)?,
+        )
+    }
+
+    /// Gets the (compressed) witness of the slot with the provided number, if one was stored.
+    pub fn get_slot_witness(
+        &self,
+        slot_number: SlotNumber,
+    ) -> Result<Option<StoredWitness>, anyhow::Error> {
+        self.db.get::<SlotWitnessByNumber>(&slot_number)
+    }
+
+    /// Deletes the stored witnesses which are no longer needed according to the retention policy.
+    /// Returns the number of witnesses deleted.
+    pub fn prune_witnesses(&self, retention: WitnessRetention) -> Result<u64, anyhow::Error> {
+        // Witnesses of all slots strictly before this one will be deleted
+        let first_slot_to_keep = match retention {
+            WitnessRetention::KeepAll => return Ok(0),
+            WitnessRetention::KeepLatest(num_slots) => self
+                .get_next_items_numbers()
+                .slot_number
+                .saturating_sub(num_slots),
+            // Only prune up to the first slot without a proof, starting from the oldest stored witness.
+            // Proofs stored after a gap don't count, since the gap still has to be proven.
+            WitnessRetention::UntilProven => {
+                let mut iter = self.db.iter::<SlotWitnessByNumber>()?;
+                iter.seek_to_first();
+                match iter.next() {
+                    Some(entry) => self.get_first_unproven_slot(entry?.0)?.0,
+                    None => return Ok(0),
+                }
+            }
+        };
+
+        let batch = SchemaBatch::new();
+        let mut num_deleted = 0;
+        let mut iter = self.db.iter::<SlotWitnessByNumber>()?;
+        iter.seek_to_first();
+        for entry in iter {
+            let (slot_number, _) = entry?;
+            if slot_number.0 >= first_slot_to_keep {
+                break;
+            }
+            batch.delete::<SlotWitnessByNumber>(&slot_number)?;
+            num_deleted += 1;
+        }
+        self.db.write_schemas(batch)?;
+        Ok(num_deleted)
+    }
+
+    /// Stores a proof covering the slots `first_slot` through `last_slot` (inclusive).
+    pub fn put_proof(
+        &self,
+        first_slot: SlotNumber,
+        last_slot: SlotNumber,
+        proof: &StoredProof,
+    ) -> Result<(), anyhow::Error> {
+        self.db
+            .put::<ProofBySlotRange>(&(first_slot, last_slot), proof)
+    }
+
+    /// Gets the proof covering exactly the slots `first_slot` through `last_slot` (inclusive), if it exists.
+    pub fn get_proof(
+        &self,
+        first_slot: SlotNumber,
+        last_slot: SlotNumber,
+    ) -> Result<Option<StoredProof>, anyhow::Error> {
+        self.db.get::<ProofBySlotRange>(&(first_slot, last_slot))
+    }
+
+    /// Returns the last slot covered by the most recent proof, or `None` if no proofs have been stored.
+    pub fn get_last_proven_slot(&self) -> Result<Option<SlotNumber>, anyhow::Error> {
+        let mut iter = self.db.iter::<ProofBySlotRange>()?;
+        iter.seek_to_last();
+
+        match iter.next() {
+            Some(Ok(((_, last_slot), _))) => Ok(Some(last_slot)),
+            Some(Err(e)) => Err(e),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the first slot at or after `from` which isn't covered by a stored proof. Unlike
+    /// [`LedgerDB::get_last_proven_slot`], this stops at the first gap, so ranges whose proof failed
+    /// or is still being generated are never skipped.
+    pub fn get_first_unproven_slot(&self, from: SlotNumber) -> Result<SlotNumber, anyhow::Error> {
+        let mut first_unproven = from;
+
+        // Proofs are sorted by their first slot, so the last proof starting at or before `from`
+        // is the only one which can cover it
+        let mut iter = self.db.iter::<ProofBySlotRange>()?;
+        iter.seek_for_prev(&(from, SlotNumber(u64::MAX)))?;
+        if let Some(entry) = iter.next() {
+            let ((first_slot, last_slot), _) = entry?;
+            if first_slot <= from && last_slot >= first_unproven {
+                first_unproven = SlotNumber(last_slot.0 + 1);
+            }
+        }
+
+        // Then follow the proofs starting after `from` for as long as they're contiguous
+        let mut iter = self.db.iter::<ProofBySlotRange>()?;
+        iter.seek(&(SlotNumber(from.0 + 1), SlotNumber(0)))?;
+        for entry in iter {
+            let ((first_slot, last_slot), _) = entry?;
+            if first_slot > first_unproven {
+                break;
+            }
+            first_unproven = std::cmp::max(first_unproven, SlotNumber(last_slot.0 + 1));
+        }
+        Ok(first_unproven)
+    }
+
     /// Gets all slots with numbers `range.start` to `range.end`. If `range.end` is outside
     /// the range of the database, the result will smaller than the requested range.
     /// Note that this method blindly preallocates for the requested range, so it should not be exposed
@@ -165,108 +454,141 @@ impl LedgerDB {
         Ok(out)
     }
 
-    fn put_slot(&self, slot: &StoredSlot, slot_number: &SlotNumber) -> Result<(), anyhow::Error> {
-        self.db.put::<SlotByNumber>(slot_number, slot)?;
-        self.db.put::<SlotByHash>(&slot.hash, slot_number)
+    fn put_slot(
+        batch: &SchemaBatch,
+        slot: &StoredSlot,
+        slot_number: &SlotNumber,
+    ) -> Result<(), anyhow::Error> {
+        batch.put::<SlotByNumber>(slot_number, slot)?;
+        batch.put::<SlotByHash>(&slot.hash, slot_number)
     }
 
     fn put_batch(
-        &self,
-        batch: &StoredBatch,
+        batch: &SchemaBatch,
+        stored_batch: &StoredBatch,
         batch_number: &BatchNumber,
     ) -> Result<(), anyhow::Error> {
-        self.db.put::<BatchByNumber>(batch_number, batch)?;
-        self.db.put::<BatchByHash>(&batch.hash, batch_number)
+        batch.put::<BatchByNumber>(batch_number, stored_batch)?;
+        batch.put::<BatchByHash>(&stored_batch.hash, batch_number)
     }
 
     fn put_transaction(
-        &self,
+        batch: &SchemaBatch,
         tx: &StoredTransaction,
         tx_number: &TxNumber,
     ) -> Result<(), anyhow::Error> {
-        self.db.put::<TxByNumber>(tx_number, tx)?;
-        self.db.put::<TxByHash>(&tx.hash, tx_number)
+        batch.put::<TxByNumber>(tx_number, tx)?;
+        batch.put::<TxByHash>(&tx.hash, tx_number)
     }
 
     fn put_event(
-        &self,
+        batch: &SchemaBatch,
         event: &Event,
         event_number: &EventNumber,
         tx_number: TxNumber,
     ) -> Result<(), anyhow::Error> {
-        self.db.put::<EventByNumber>(event_number, event)?;
-        self.db
-            .put::<EventByKey>(&(event.key().clone(), tx_number, *event_number), &())
+        batch.put::<EventByNumber>(event_number, event)?;
+        batch.put::<EventByKey>(&(event.key().clone(), tx_number, *event_number), &())
     }
 
-    /// Commits a slot to the database by inserting its events, transactions, and batches before
-    /// inserting the slot metadata.
+    /// Commits a slot to the database, together with its events, transactions, batches and witness.
+    /// Everything is written in a single atomic batch, which also advances the marker of the last
+    /// committed slot, so a crash can never leave a partially committed slot behind.
     pub fn commit_slot<S: SlotData, B: Serialize, T: Serialize>(
         &self,
         data_to_commit: SlotCommit<S, B, T>,
     ) -> Result<(), anyhow::Error> {
-        // TODO: expose a batch API on the db to commit everything atomically
-        // Create a scope to ensure that the lock is released before we commit to the db
-        let mut current_item_numbers = {
-            let mut next_item_numbers = self.next_item_numbers.lock().unwrap();
-            let item_numbers = next_item_numbers.clone();
-            next_item_numbers.slot_number += 1;
-            next_item_numbers.batch_number += data_to_commit.batch_receipts.len() as u64;
-            next_item_numbers.tx_number += data_to_commit.num_txs as u64;
-            next_item_numbers.event_number += data_to_commit.num_events as u64;
-            item_numbers
-            // The lock is released here
+        // Hold the lock until the slot is written, so that the item numbers only advance if the commit succeeds
+        let mut next_item_numbers = self.next_item_numbers.lock().unwrap();
+        let mut current_item_numbers = next_item_numbers.clone();
+        let schema_batch = SchemaBatch::new();
+        let mut notification = SlotNotification {
+            number: SlotNumber(current_item_numbers.slot_number),
+            txs: vec![],
+            events: vec![],
         };
 
         let first_batch_number = current_item_numbers.batch_number;
         let last_batch_number = first_batch_number + data_to_commit.batch_receipts.len() as u64;
-        // Insert data from "bottom up" to ensure consistency if the application crashes during insertion
         for batch_receipt in data_to_commit.batch_receipts.into_iter() {
             let first_tx_number = current_item_numbers.tx_number;
             let last_tx_number = first_tx_number + batch_receipt.tx_receipts.len() as u64;
-            // Insert transactions and events from each batch before inserting the batch
             for tx in batch_receipt.tx_receipts.into_iter() {
                 let (tx_to_store, events) =
                     split_tx_for_storage(tx, current_item_numbers.event_number);
                 for event in events.into_iter() {
-                    self.put_event(
+                    Self::put_event(
+                        &schema_batch,
                         &event,
                         &EventNumber(current_item_numbers.event_number),
                         TxNumber(current_item_numbers.tx_number),
                     )?;
+                    notification.events.push(EventResponse {
+                        number: current_item_numbers.event_number,
+                        tx_number: current_item_numbers.tx_number,
+                        event,
+                    });
                     current_item_numbers.event_number += 1;
                 }
-                self.put_transaction(&tx_to_store, &TxNumber(current_item_numbers.tx_number))?;
+                Self::put_transaction(
+                    &schema_batch,
+                    &tx_to_store,
+                    &TxNumber(current_item_numbers.tx_number),
+                )?;
+                notification
+                    .txs
+                    .push((tx_to_store.hash, TxNumber(current_item_numbers.tx_number)));
                 current_item_numbers.tx_number += 1;
             }
 
-            // Insert batch
             let batch_to_store = StoredBatch {
                 hash: batch_receipt.batch_hash,
                 txs: TxNumber(first_tx_number)..TxNumber(last_tx_number),
-                custom_receipt: bincode::serialize(&batch_receipt.inner)
-                    .expect("serialization to vec is infallible")
-                    .into(),
+                custom_receipt: bincode::serialize(&batch_receipt.inner)?.into(),
             };
-            self.put_batch(
+            Self::put_batch(
+                &schema_batch,
                 &batch_to_store,
                 &BatchNumber(current_item_numbers.batch_number),
             )?;
             current_item_numbers.batch_number += 1;
         }
 
-        // Once all batches are inserted, Insert slot
+        let slot_number = SlotNumber(current_item_numbers.slot_number);
+        if let Some(witness) = data_to_commit.witness {
+            schema_batch.put::<SlotWitnessByNumber>(&slot_number, &witness)?;
+        }
+
         let slot_to_store = StoredSlot {
             hash: data_to_commit.slot_data.hash(),
             // TODO: Add a method to the slotdata trait allowing additional data to be stored
             extra_data: vec![].into(),
             batches: BatchNumber(first_batch_number)..BatchNumber(last_batch_number),
         };
-        self.put_slot(
-            &slot_to_store,
-            &SlotNumber(current_item_numbers.slot_number),
+        Self::put_slot(&schema_batch, &slot_to_store, &slot_number)?;
+        current_item_numbers.slot_number += 1;
+
+        schema_batch.put::<LastCommittedSlot>(
+            &(),
+            &SlotCommitMarker {
+                slot_number,
+                next_batch_number: BatchNumber(current_item_numbers.batch_number),
+                next_tx_number: TxNumber(current_item_numbers.tx_number),
+                next_event_number: EventNumber(current_item_numbers.event_number),
+                state_root: data_to_commit.state_root,
+            },
         )?;
 
+        self.db.write_schemas(schema_batch)?;
+        *next_item_numbers = current_item_numbers;
+        {
+            let mut pending_txs = self.pending_txs.lock().unwrap();
+            for (hash, _) in &notification.txs {
+                pending_txs.remove(hash);
+            }
+        }
+        // Sending only fails if there are no subscribers
+        let _ = self.slot_notifications.send(Arc::new(notification));
         Ok(())
     }
 
@@ -284,3 +606,503 @@ impl LedgerDB {
         };
     }
 }
+
+#[cfg(test)]
+mod ledger_db_tests {
+    use serde::{Deserialize, Serialize};
+    use sov_rollup_interface::mocks::TestBlock;
+    use sov_rollup_interface::rpc::{
+        EventsByKeyQuery, LedgerRpcProvider, TxReceiptStatus, TxStatus,
+    };
+    use sov_rollup_interface::stf::{BatchReceipt, Event, TransactionReceipt};
+    use sov_schema_db::SchemaBatch;
+
+    use super::{LedgerDB, SlotCommit};
+    use crate::schema::tables::{
+        BatchByNumber, EventByNumber, LedgerSchemaVersion, TxByNumber, LEDGER_SCHEMA_VERSION,
+    };
+    use crate::schema::types::{
+        BatchNumber, EventNumber, SlotNumber, StoredProof, TxNumber, WitnessRetention,
+    };
+
+    fn commit_slot_with_witness(db: &LedgerDB, witness: &Vec<u32>) {
+        let mut slot = SlotCommit::<_, (), ()>::new(TestBlock::new([1; 32], [0; 32]));
+        slot.set_witness([2; 32], witness).unwrap();
+        db.commit_slot(slot).unwrap();
+    }
+
+    #[test]
+    fn test_witness_round_trip() {
+        let db = LedgerDB::temporary();
+        let witness: Vec<u32> = (0..1000).collect();
+        commit_slot_with_witness(&db, &witness);
+
+        let stored = db.get_slot_witness(SlotNumber(1)).unwrap().unwrap();
+        assert_eq!(stored.prev_state_root, [2; 32]);
+        assert!(stored.compressed_witness.as_ref().len() < stored.decompress().unwrap().len());
+        assert_eq!(stored.to_witness::<Vec<u32>>().unwrap(), witness);
+    }
+
+    #[test]
+    fn test_prune_witnesses() {
+        let db = LedgerDB::temporary();
+        for i in 0..5 {
+            commit_slot_with_witness(&db, &vec![i]);
+        }
+
+        // Nothing has been proven yet, so every witness is still needed
+        assert_eq!(
+            db.prune_witnesses(WitnessRetention::UntilProven).unwrap(),
+            0
+        );
+        let proof = StoredProof {
+            proof: vec![0].into(),
+        };
+        // Slots 3 and 4 are proven, but slots 1 and 2 aren't, for example because their proof failed
+        db.put_proof(SlotNumber(3), SlotNumber(4), &proof).unwrap();
+        assert_eq!(
+            db.prune_witnesses(WitnessRetention::UntilProven).unwrap(),
+            0
+        );
+        assert!(db.get_slot_witness(SlotNumber(1)).unwrap().is_some());
+
+        db.put_proof(SlotNumber(1), SlotNumber(2), &proof).unwrap();
+        assert_eq!(
+            db.prune_witnesses(WitnessRetention::UntilProven).unwrap(),
+            4
+        );
+        assert!(db.get_slot_witness(SlotNumber(4)).unwrap().is_none());
+        assert!(db.get_slot_witness(SlotNumber(5)).unwrap().is_some());
+        // Pruning again starts from the oldest remaining witness
+        assert_eq!(
+            db.prune_witnesses(WitnessRetention::UntilProven).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_prune_witnesses_keep_latest() {
+        let db = LedgerDB::temporary();
+        for i in 0..5 {
+            commit_slot_with_witness(&db, &vec![i]);
+        }
+        assert_eq!(db.prune_witnesses(WitnessRetention::KeepAll).unwrap(), 0);
+        assert_eq!(
+            db.prune_witnesses(WitnessRetention::KeepLatest(1)).unwrap(),
+            4
+        );
+        assert!(db.get_slot_witness(SlotNumber(4)).unwrap().is_none());
+        assert!(db.get_slot_witness(SlotNumber(5)).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_first_unproven_slot_stops_at_gaps() {
+        let db = LedgerDB::temporary();
+        let proof = StoredProof {
+            proof: vec![0].into(),
+        };
+        assert_eq!(
+            db.get_first_unproven_slot(SlotNumber(1)).unwrap(),
+            SlotNumber(1)
+        );
+
+        // Slots 3 and 4 failed to prove, but the following ranges succeeded
+        for (first, last) in [(1, 2), (5, 6), (7, 8)] {
+            db.put_proof(SlotNumber(first), SlotNumber(last), &proof)
+                .unwrap();
+        }
+        assert_eq!(db.get_last_proven_slot().unwrap(), Some(SlotNumber(8)));
+        assert_eq!(
+            db.get_first_unproven_slot(SlotNumber(1)).unwrap(),
+            SlotNumber(3)
+        );
+        assert_eq!(
+            db.get_first_unproven_slot(SlotNumber(2)).unwrap(),
+            SlotNumber(3)
+        );
+        assert_eq!(
+            db.get_first_unproven_slot(SlotNumber(3)).unwrap(),
+            SlotNumber(3)
+        );
+        assert_eq!(
+            db.get_first_unproven_slot(SlotNumber(6)).unwrap(),
+            SlotNumber(9)
+        );
+
+        db.put_proof(SlotNumber(3), SlotNumber(4), &proof).unwrap();
+        assert_eq!(
+            db.get_first_unproven_slot(SlotNumber(1)).unwrap(),
+            SlotNumber(9)
+        );
+    }
+
+    #[test]
+    fn test_secondary_catches_up() {
+        let path = sov_schema_db::temppath::TempPath::new();
+        let secondary_path = sov_schema_db::temppath::TempPath::new();
+        let primary = LedgerDB::with_path(&path).unwrap();
+        commit_slot_with_witness(&primary, &vec![1]);
+
+        let secondary = LedgerDB::secondary(&path, &secondary_path, &Default::default()).unwrap();
+        assert!(secondary.get_slot(SlotNumber(1)).unwrap().is_some());
+        assert_eq!(secondary.get_next_items_numbers().slot_number, 2);
+
+        // Slots committed after opening are only visible once caught up
+        commit_slot_with_witness(&primary, &vec![2]);
+        assert!(secondary.get_slot(SlotNumber(2)).unwrap().is_none());
+        secondary.catch_up_with_primary().unwrap();
+        assert!(secondary.get_slot(SlotNumber(2)).unwrap().is_some());
+        assert_eq!(secondary.get_next_items_numbers().slot_number, 3);
+
+        assert!(secondary
+            .commit_slot(SlotCommit::<_, (), ()>::new(TestBlock::new(
+                [1; 32], [0; 32]
+            )))
+            .is_err());
+    }
+
+    /// A slot with a single batch of two transactions, each emitting a "bonded_prover" and an "other" event
+    fn slot_with_events(seed: u8) -> SlotCommit<TestBlock, (), ()> {
+        let mut slot = SlotCommit::new(TestBlock::new([seed; 32], [seed - 1; 32]));
+        slot.add_batch(BatchReceipt {
+            batch_hash: [seed; 32],
+            tx_receipts: (0..2)
+                .map(|i| TransactionReceipt {
+                    tx_hash: [seed * 2 + i; 32],
+                    body_to_save: None,
+                    events: vec![
+                        Event::new("other", "value"),
+                        Event::new("bonded_prover", &format!("{seed}-{i}")),
+                    ],
+                    receipt: (),
+                })
+                .collect(),
+            inner: (),
+        });
+        slot
+    }
+
+    #[test]
+    fn test_get_events_by_key() {
+        let db = LedgerDB::temporary();
+        for seed in 1..=3 {
+            db.commit_slot(slot_with_events(seed)).unwrap();
+        }
+        let query = EventsByKeyQuery {
+            key: Event::new("bonded_prover", "").key().clone(),
+            start_slot: None,
+            end_slot: None,
+            cursor: None,
+            limit: Some(4),
+        };
+
+        // Page through all the events with the key
+        let first_page = db.get_events_by_key(&query).unwrap();
+        assert_eq!(first_page.events.len(), 4);
+        let second_page = db
+            .get_events_by_key(&EventsByKeyQuery {
+                cursor: first_page.next_cursor,
+                ..query.clone()
+            })
+            .unwrap();
+        assert_eq!(second_page.events.len(), 2);
+        assert!(second_page.next_cursor.is_none());
+        let values: Vec<_> = first_page
+            .events
+            .iter()
+            .chain(second_page.events.iter())
+            .map(|e| e.event.clone())
+            .collect();
+        let expected: Vec<_> = (1..=3)
+            .flat_map(|seed| {
+                (0..2).map(move |i| Event::new("bonded_prover", &format!("{seed}-{i}")))
+            })
+            .collect();
+        assert_eq!(values, expected);
+        for event in first_page.events.iter().chain(second_page.events.iter()) {
+            let tx = db.get_tx_by_number::<()>(event.tx_number, Default::default());
+            let event_range = tx.unwrap().unwrap().event_range;
+            assert!(event_range.contains(&event.number));
+        }
+
+        // Only the events of the second slot
+        let page = db
+            .get_events_by_key(&EventsByKeyQuery {
+                start_slot: Some(2),
+                end_slot: Some(2),
+                ..query.clone()
+            })
+            .unwrap();
+        let values: Vec<_> = page.events.into_iter().map(|e| e.event).collect();
+        assert_eq!(
+            values,
+            vec![
+                Event::new("bonded_prover", "2-0"),
+                Event::new("bonded_prover", "2-1")
+            ]
+        );
+
+        // Slots past the head have no events
+        let page = db
+            .get_events_by_key(&EventsByKeyQuery {
+                start_slot: Some(4),
+                ..query.clone()
+            })
+            .unwrap();
+        assert!(page.events.is_empty());
+
+        assert!(db
+            .get_events_by_key(&EventsByKeyQuery {
+                limit: Some(10_000),
+                ..query
+            })
+            .is_err());
+    }
+
+    /// A batch receipt which fails to serialize if `fail` is set, simulating a crash in the middle of a slot
+    struct FaultyReceipt {
+        fail: bool,
+    }
+
+    impl Serialize for FaultyReceipt {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if self.fail {
+                return Err(serde::ser::Error::custom("injected fault"));
+            }
+            serializer.serialize_unit()
+        }
+    }
+
+    fn slot_with_batches(faults: &[bool]) -> SlotCommit<TestBlock, FaultyReceipt, ()> {
+        let mut slot = SlotCommit::new(TestBlock::new([1; 32], [0; 32]));
+        for (i, fail) in faults.iter().enumerate() {
+            slot.add_batch(BatchReceipt {
+                batch_hash: [i as u8; 32],
+                tx_receipts: vec![TransactionReceipt {
+                    tx_hash: [i as u8; 32],
+                    body_to_save: None,
+                    events: vec![Event::new("key", "value")],
+                    receipt: (),
+                }],
+                inner: FaultyReceipt { fail: *fail },
+            });
+        }
+        slot.set_state_root([faults.len() as u8; 32]);
+        slot
+    }
+
+    #[test]
+    fn test_subscribe_slots() {
+        let db = LedgerDB::temporary();
+        db.commit_slot(slot_with_events(1)).unwrap();
+        let mut receiver = db.subscribe_slots();
+        db.commit_slot(slot_with_events(2)).unwrap();
+
+        // Only the slots committed after subscribing are received
+        let notification = receiver.try_recv().unwrap();
+        assert_eq!(notification.number, SlotNumber(2));
+        assert_eq!(
+            notification.txs,
+            vec![([4; 32], TxNumber(3)), ([5; 32], TxNumber(4))]
+        );
+        let events: Vec<_> = notification
+            .events
+            .iter()
+            .map(|e| (e.number, e.tx_number))
+            .collect();
+        assert_eq!(events, vec![(5, 3), (6, 3), (7, 4), (8, 4)]);
+        assert_eq!(
+            notification.events[3].event,
+            Event::new("bonded_prover", "2-1")
+        );
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_get_tx_status() {
+        let db = LedgerDB::temporary();
+        db.commit_slot(SlotCommit::<_, (), ()>::new(TestBlock::new(
+            [1; 32], [0; 32],
+        )))
+        .unwrap();
+        for seed in 2..=4 {
+            db.commit_slot(slot_with_events(seed)).unwrap();
+        }
+
+        assert_eq!(
+            db.get_tx_status::<()>(&[0; 32]).unwrap(),
+            TxStatus::NotFound
+        );
+        // The second tx of the second batch, in the third slot
+        let inclusion = match db.get_tx_status::<()>(&[7; 32]).unwrap() {
+            TxStatus::Included(inclusion) => inclusion,
+            status => panic!("Unexpected status {:?}", status),
+        };
+        assert_eq!(inclusion.slot_number, 3);
+        assert_eq!(inclusion.slot_hash, [3; 32]);
+        assert_eq!(inclusion.batch_number, 2);
+        assert_eq!(inclusion.batch_hash, [3; 32]);
+        assert_eq!(inclusion.tx_number, 4);
+        assert_eq!(inclusion.tx.hash, [7; 32]);
+        assert_eq!(
+            inclusion.events,
+            vec![
+                Event::new("other", "value"),
+                Event::new("bonded_prover", "3-1")
+            ]
+        );
+
+        for (hash, slot_number) in [([4; 32], 2), ([9; 32], 4)] {
+            match db.get_tx_status::<()>(&hash).unwrap() {
+                TxStatus::Included(inclusion) => assert_eq!(inclusion.slot_number, slot_number),
+                status => panic!("Unexpected status {:?}", status),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct TestTxReceipt {
+        reverted: bool,
+    }
+
+    impl TxReceiptStatus for TestTxReceipt {
+        fn is_reverted(&self) -> bool {
+            self.reverted
+        }
+    }
+
+    #[test]
+    fn test_pending_and_reverted_tx_status() {
+        let db = LedgerDB::temporary();
+        for hash in [[1; 32], [2; 32], [3; 32]] {
+            db.add_pending_tx(hash);
+        }
+        assert_eq!(
+            db.get_tx_status::<TestTxReceipt>(&[1; 32]).unwrap(),
+            TxStatus::Pending
+        );
+        // Transactions dropped by the sequencer are forgotten
+        db.remove_pending_tx(&[3; 32]);
+        assert_eq!(
+            db.get_tx_status::<TestTxReceipt>(&[3; 32]).unwrap(),
+            TxStatus::NotFound
+        );
+
+        let mut slot = SlotCommit::new(TestBlock::new([1; 32], [0; 32]));
+        slot.add_batch(BatchReceipt {
+            batch_hash: [1; 32],
+            tx_receipts: [([1; 32], true), ([2; 32], false)]
+                .into_iter()
+                .map(|(tx_hash, reverted)| TransactionReceipt {
+                    tx_hash,
+                    body_to_save: None,
+                    events: vec![],
+                    receipt: TestTxReceipt { reverted },
+                })
+                .collect(),
+            inner: (),
+        });
+        db.commit_slot(slot).unwrap();
+
+        match db.get_tx_status::<TestTxReceipt>(&[1; 32]).unwrap() {
+            TxStatus::Reverted(inclusion) => {
+                assert_eq!(inclusion.slot_number, 1);
+                assert_eq!(inclusion.tx_number, 1);
+                assert_eq!(
+                    inclusion.tx.custom_receipt,
+                    TestTxReceipt { reverted: true }
+                );
+            }
+            status => panic!("Unexpected status {:?}", status),
+        }
+        match db.get_tx_status::<TestTxReceipt>(&[2; 32]).unwrap() {
+            TxStatus::Included(inclusion) => assert_eq!(inclusion.tx_number, 2),
+            status => panic!("Unexpected status {:?}", status),
+        }
+        // Committed transactions are no longer pending
+        assert!(!db.is_pending_tx(&[1; 32]));
+        assert!(!db.is_pending_tx(&[2; 32]));
+    }
+
+    #[test]
+    fn test_failed_commit_writes_nothing() {
+        let path = sov_schema_db::temppath::TempPath::new();
+        let db = LedgerDB::with_path(&path).unwrap();
+        db.commit_slot(slot_with_batches(&[false])).unwrap();
+        let numbers_before = db.get_next_items_numbers();
+
+        // The second batch fails after the first batch, its transaction and its event were processed
+        assert!(db.commit_slot(slot_with_batches(&[false, true])).is_err());
+
+        let numbers = db.get_next_items_numbers();
+        assert_eq!(numbers.slot_number, numbers_before.slot_number);
+        assert_eq!(numbers.batch_number, numbers_before.batch_number);
+        assert_eq!(numbers.tx_number, numbers_before.tx_number);
+        assert_eq!(numbers.event_number, numbers_before.event_number);
+        assert!(db.get_slot(SlotNumber(2)).unwrap().is_none());
+        assert!(db
+            .db
+            .get::<BatchByNumber>(&BatchNumber(2))
+            .unwrap()
+            .is_none());
+        assert!(db.db.get::<TxByNumber>(&TxNumber(2)).unwrap().is_none());
+        assert!(db
+            .db
+            .get::<EventByNumber>(&EventNumber(2))
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            db.get_last_committed_slot().unwrap().unwrap().slot_number,
+            SlotNumber(1)
+        );
+
+        // After a restart, the next slot is committed right after the last complete one
+        drop(db);
+        let db = LedgerDB::with_path(&path).unwrap();
+        db.commit_slot(slot_with_batches(&[false, false])).unwrap();
+        let marker = db.get_last_committed_slot().unwrap().unwrap();
+        assert_eq!(marker.slot_number, SlotNumber(2));
+        assert_eq!(marker.next_batch_number, BatchNumber(4));
+        assert_eq!(marker.next_tx_number, TxNumber(4));
+        assert_eq!(marker.next_event_number, EventNumber(4));
+        assert_eq!(marker.state_root, Some([2; 32]));
+        assert_eq!(
+            db.get_slot(SlotNumber(2)).unwrap().unwrap().batches,
+            BatchNumber(2)..BatchNumber(4)
+        );
+    }
+
+    #[test]
+    fn test_schema_version_is_checked() {
+        let path = sov_schema_db::temppath::TempPath::new();
+        let db = LedgerDB::with_path(&path).unwrap();
+        let delete_version = |db: &LedgerDB| {
+            let batch = SchemaBatch::new();
+            batch.delete::<LedgerSchemaVersion>(&()).unwrap();
+            db.db.write_schemas(batch).unwrap();
+        };
+        assert_eq!(
+            LedgerDB::check_schema_version(&db.db).unwrap(),
+            Some(LEDGER_SCHEMA_VERSION)
+        );
+        delete_version(&db);
+        assert_eq!(LedgerDB::check_schema_version(&db.db).unwrap(), None);
+
+        db.commit_slot(slot_with_batches(&[false])).unwrap();
+        db.db
+            .put::<LedgerSchemaVersion>(&(), &LEDGER_SCHEMA_VERSION)
+            .unwrap();
+        drop(db);
+        let db = LedgerDB::with_path(&path).unwrap();
+
+        // Ledgers written with another encoding of the tables, or before they were versioned, must be resynced
+        db.db
+            .put::<LedgerSchemaVersion>(&(), &(LEDGER_SCHEMA_VERSION - 1))
+            .unwrap();
+        assert!(LedgerDB::check_schema_version(&db.db).is_err());
+        delete_version(&db);
+        assert!(LedgerDB::check_schema_version(&db.db).is_err());
+        drop(db);
+        assert!(LedgerDB::with_path(&path).is_err());
+    }
+}
@@ -1,16 +1,20 @@
+use std::ops::Range;
+
 use serde::de::DeserializeOwned;
 use sov_rollup_interface::{
     rpc::{
-        BatchIdAndOffset, BatchIdentifier, BatchResponse, EventIdentifier, ItemOrHash,
-        LedgerRpcProvider, QueryMode, SlotIdAndOffset, SlotIdentifier, SlotResponse, TxIdAndOffset,
-        TxIdentifier, TxResponse,
+        BatchIdAndOffset, BatchIdentifier, BatchResponse, EventCursor, EventIdentifier,
+        EventResponse, EventsByKeyQuery, EventsPage, ItemOrHash, LedgerRpcProvider, QueryMode,
+        SlotIdAndOffset, SlotIdentifier, SlotResponse, TxIdAndOffset, TxIdentifier, TxInclusion,
+        TxReceiptStatus, TxResponse, TxStatus,
     },
     stf::Event,
 };
 
 use crate::schema::{
     tables::{
-        BatchByHash, BatchByNumber, EventByNumber, SlotByHash, SlotByNumber, TxByHash, TxByNumber,
+        BatchByHash, BatchByNumber, EventByKey, EventByNumber, SlotByHash, SlotByNumber, TxByHash,
+        TxByNumber,
     },
     types::{BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot, TxNumber},
 };
@@ -140,6 +144,69 @@ impl LedgerRpcProvider for LedgerDB {
         Ok(out)
     }
 
+    fn get_events_by_key(&self, query: &EventsByKeyQuery) -> Result<EventsPage, anyhow::Error> {
+        let limit = query.limit.unwrap_or(MAX_EVENTS_PER_REQUEST);
+        anyhow::ensure!(
+            limit <= MAX_EVENTS_PER_REQUEST,
+            "requested too many events. Requested: {}. Max: {}",
+            limit,
+            MAX_EVENTS_PER_REQUEST
+        );
+        if let (Some(start), Some(end)) = (query.start_slot, query.end_slot) {
+            anyhow::ensure!(start <= end, "start_slot must be <= end_slot");
+        }
+
+        // The index is ordered by transaction within each key, so the range of slots is converted
+        // into a range of transactions
+        let mut start = match query.start_slot {
+            Some(slot) => (self.first_tx_from_slot(SlotNumber(slot))?, EventNumber(0)),
+            None => (TxNumber(0), EventNumber(0)),
+        };
+        if let Some(cursor) = query.cursor {
+            start = start.max((TxNumber(cursor.tx_number), EventNumber(cursor.event_number)));
+        }
+        let end_tx = match query.end_slot {
+            Some(slot) => Some(self.first_tx_from_slot(SlotNumber(slot.saturating_add(1)))?),
+            None => None,
+        };
+
+        let mut iter = self.db.iter::<EventByKey>()?;
+        iter.seek(&(query.key.clone(), start.0, start.1))?;
+        let mut events = Vec::new();
+        let mut next_cursor = None;
+        for entry in iter {
+            let ((key, tx_number, event_number), ()) = entry?;
+            if key != query.key || end_tx.map_or(false, |end| tx_number >= end) {
+                break;
+            }
+            if events.len() as u64 == limit {
+                next_cursor = Some(EventCursor {
+                    tx_number: tx_number.0,
+                    event_number: event_number.0,
+                });
+                break;
+            }
+            let event = self
+                .db
+                .get::<EventByNumber>(&event_number)?
+                .ok_or_else(|| {
+                    anyhow::format_err!(
+                        "Bug! Event {} is indexed by key but missing",
+                        event_number.0
+                    )
+                })?;
+            events.push(EventResponse {
+                number: event_number.0,
+                tx_number: tx_number.0,
+                event,
+            });
+        }
+        Ok(EventsPage {
+            events,
+            next_cursor,
+        })
+    }
+
     fn get_head<B: DeserializeOwned, T: DeserializeOwned>(
         &self,
     ) -> Result<Option<SlotResponse<B, T>>, anyhow::Error> {
@@ -189,6 +256,77 @@ impl LedgerRpcProvider for LedgerDB {
             .map(|mut txs: Vec<Option<TxResponse<T>>>| txs.pop().unwrap_or(None))
     }
 
+    fn get_tx_status<T: DeserializeOwned + TxReceiptStatus>(
+        &self,
+        hash: &[u8; 32],
+    ) -> Result<TxStatus<T>, anyhow::Error> {
+        // A transaction stops being pending only once its slot is committed, so it's either reported as pending
+        // or found in the ledger
+        let pending = self.is_pending_tx(hash);
+        let next_items = self.get_next_items_numbers();
+        let tx_number = match self.db.get::<TxByHash>(hash)? {
+            // Transactions committed after reading `next_items` are reported once the commit completes
+            Some(number) if number.0 < next_items.tx_number => number,
+            _ if pending => return Ok(TxStatus::Pending),
+            _ => return Ok(TxStatus::NotFound),
+        };
+        let tx = self
+            .db
+            .get::<TxByNumber>(&tx_number)?
+            .ok_or_else(|| anyhow::format_err!("Bug! Tx {} is missing", tx_number.0))?;
+
+        let batch_number = find_containing(next_items.batch_number, tx_number.0, |number| {
+            Ok(self
+                .db
+                .get::<BatchByNumber>(&BatchNumber(number))?
+                .map(|batch| batch.txs.start.0..batch.txs.end.0))
+        })?
+        .ok_or_else(|| anyhow::format_err!("Bug! No batch contains tx {}", tx_number.0))?;
+        let slot_number = find_containing(next_items.slot_number, batch_number, |number| {
+            Ok(self
+                .db
+                .get::<SlotByNumber>(&SlotNumber(number))?
+                .map(|slot| slot.batches.start.0..slot.batches.end.0))
+        })?
+        .ok_or_else(|| anyhow::format_err!("Bug! No slot contains batch {}", batch_number))?;
+        let batch_hash = self
+            .db
+            .get::<BatchByNumber>(&BatchNumber(batch_number))?
+            .ok_or_else(|| anyhow::format_err!("Bug! Batch {} is missing", batch_number))?
+            .hash;
+        let slot_hash = self
+            .db
+            .get::<SlotByNumber>(&SlotNumber(slot_number))?
+            .ok_or_else(|| anyhow::format_err!("Bug! Slot {} is missing", slot_number))?
+            .hash;
+
+        let mut events = Vec::with_capacity((tx.events.end.0 - tx.events.start.0) as usize);
+        for number in tx.events.start.0..tx.events.end.0 {
+            events.push(
+                self.db
+                    .get::<EventByNumber>(&EventNumber(number))?
+                    .ok_or_else(|| anyhow::format_err!("Bug! Event {} is missing", number))?,
+            );
+        }
+
+        let tx: TxResponse<T> = tx.try_into()?;
+        let reverted = tx.custom_receipt.is_reverted();
+        let inclusion = TxInclusion {
+            slot_number,
+            slot_hash,
+            batch_number,
+            batch_hash,
+            tx_number: tx_number.0,
+            tx,
+            events,
+        };
+        Ok(if reverted {
+            TxStatus::Reverted(inclusion)
+        } else {
+            TxStatus::Included(inclusion)
+        })
+    }
+
     // Get X by number
     fn get_slot_by_number<B: DeserializeOwned, T: DeserializeOwned>(
         &self,
@@ -275,7 +413,48 @@ impl LedgerRpcProvider for LedgerDB {
     }
 }
 
+/// Binary searches the items numbered `1..end` for the one whose range contains `target`. Items are assigned
+/// consecutive ranges as they're committed, so the ranges are sorted by item number.
+fn find_containing(
+    end: u64,
+    target: u64,
+    range_of: impl Fn(u64) -> Result<Option<Range<u64>>, anyhow::Error>,
+) -> Result<Option<u64>, anyhow::Error> {
+    let (mut low, mut high) = (1, end);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let range =
+            range_of(mid)?.ok_or_else(|| anyhow::format_err!("Bug! Item {} is missing", mid))?;
+        if target < range.start {
+            high = mid;
+        } else if target >= range.end {
+            low = mid + 1;
+        } else {
+            return Ok(Some(mid));
+        }
+    }
+    Ok(None)
+}
+
 impl LedgerDB {
+    /// Returns the number of the first transaction in `slot` or in any later slot. If there is none,
+    /// this is the number which will be assigned to the next transaction.
+    fn first_tx_from_slot(&self, slot: SlotNumber) -> Result<TxNumber, anyhow::Error> {
+        let next_items = self.get_next_items_numbers();
+        let first_batch = match self.db.get::<SlotByNumber>(&slot)? {
+            Some(stored_slot) => stored_slot.batches.start,
+            // Slots are numbered from 1, so only slot 0 is missing before the latest slot
+            None if slot.0 < next_items.slot_number => return Ok(TxNumber(0)),
+            None => return Ok(TxNumber(next_items.tx_number)),
+        };
+        // Batches are numbered sequentially, so the first batch of the slot (or of a later slot if the slot is empty)
+        // starts with the first transaction from the slot onwards
+        Ok(match self.db.get::<BatchByNumber>(&first_batch)? {
+            Some(batch) => batch.txs.start,
+            None => TxNumber(next_items.tx_number),
+        })
+    }
+
     fn resolve_slot_identifier(
         &self,
         slot_id: &SlotIdentifier,
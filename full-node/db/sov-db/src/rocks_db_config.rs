@@ -1,13 +1,27 @@
 // Adapted from Aptos-Core.
-// Modified to remove serde dependency
+// Modified to add per column family options
 
-use rocksdb::Options;
+use std::collections::BTreeMap;
+
+use rocksdb::{BlockBasedOptions, Cache, ColumnFamilyDescriptor, DBCompressionType, Options};
+use serde::{Deserialize, Serialize};
+use sov_schema_db::DB;
+
+/// The RocksDB options of each database of the node. The ledger and the state are stored in separate
+/// RocksDB instances with very different access patterns, so they are tuned separately.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RocksdbConfigs {
+    pub ledger: RocksdbConfig,
+    pub state: RocksdbConfig,
+}
 
 /// Port selected RocksDB options for tuning underlying rocksdb instance of our state db.
 /// The current default values are taken from Aptos. TODO: tune rocksdb for our workload.
 /// see <https://github.com/facebook/rocksdb/blob/master/include/rocksdb/options.h>
 /// for detailed explanations.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct RocksdbConfig {
     pub max_open_files: i32,
     pub max_total_wal_size: u64,
@@ -15,6 +29,9 @@ pub struct RocksdbConfig {
     pub block_cache_size: u64,
     pub block_size: u64,
     pub cache_index_and_filter_blocks: bool,
+    /// Options of individual column families, keyed by table name (e.g. "JmtNodes"). A column family
+    /// listed here replaces its built-in options entirely; see [`ColumnFamilyConfig::default_for`].
+    pub column_families: BTreeMap<String, ColumnFamilyConfig>,
 }
 
 impl Default for RocksdbConfig {
@@ -34,6 +51,69 @@ impl Default for RocksdbConfig {
             block_size: 4 * (1u64 << 10),
             // Whether cache index and filter blocks into block cache.
             cache_index_and_filter_blocks: false,
+            column_families: BTreeMap::new(),
+        }
+    }
+}
+
+impl RocksdbConfig {
+    /// Returns the options of the column family `name`, falling back to its built-in options.
+    pub fn column_family(&self, name: &str) -> ColumnFamilyConfig {
+        self.column_families
+            .get(name)
+            .copied()
+            .unwrap_or_else(|| ColumnFamilyConfig::default_for(name))
+    }
+}
+
+/// The compression algorithm used for the sst files of a column family
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+pub enum Compression {
+    None,
+    Snappy,
+    Lz4,
+    Zstd,
+}
+
+impl From<Compression> for DBCompressionType {
+    fn from(compression: Compression) -> Self {
+        match compression {
+            Compression::None => DBCompressionType::None,
+            Compression::Snappy => DBCompressionType::Snappy,
+            Compression::Lz4 => DBCompressionType::Lz4,
+            Compression::Zstd => DBCompressionType::Zstd,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColumnFamilyConfig {
+    pub compression: Compression,
+    /// The number of bits per key of the bloom filter, or `None` to disable it. Bloom filters speed up
+    /// point lookups of missing keys, but don't help the prefix scans done through iterators.
+    pub bloom_filter_bits_per_key: Option<u32>,
+}
+
+impl Default for ColumnFamilyConfig {
+    fn default() -> Self {
+        Self {
+            compression: Compression::Lz4,
+            bloom_filter_bits_per_key: None,
+        }
+    }
+}
+
+impl ColumnFamilyConfig {
+    /// The built-in options of a column family. Tables which are only accessed by exact key get a
+    /// bloom filter, while tables which are scanned (like `JmtValues` and `EventByKey`) don't.
+    pub fn default_for(name: &str) -> Self {
+        match name {
+            "JmtNodes" | "KeyHashToKey" | "SlotByHash" | "BatchByHash" | "TxByHash" => Self {
+                bloom_filter_bits_per_key: Some(10),
+                ..Default::default()
+            },
+            _ => Self::default(),
         }
     }
 }
@@ -50,3 +130,115 @@ pub fn gen_rocksdb_options(config: &RocksdbConfig, readonly: bool) -> Options {
 
     db_opts
 }
+
+/// Generates the options of a secondary instance, which follows a database opened as primary by
+/// another process.
+pub fn gen_secondary_rocksdb_options(config: &RocksdbConfig) -> Options {
+    let mut db_opts = gen_rocksdb_options(config, true);
+    // Secondary instances must keep all files open, since the primary may delete them at any time
+    db_opts.set_max_open_files(-1);
+    db_opts
+}
+
+/// Generates the descriptors of the provided column families. All of them share a single block cache.
+pub fn gen_cf_descriptors<'a>(
+    config: &RocksdbConfig,
+    column_families: impl IntoIterator<Item = &'a str>,
+) -> anyhow::Result<Vec<ColumnFamilyDescriptor>> {
+    let cache = Cache::new_lru_cache(config.block_cache_size as usize)?;
+    Ok(column_families
+        .into_iter()
+        .map(|name| {
+            let cf_config = config.column_family(name);
+
+            let mut table_opts = BlockBasedOptions::default();
+            table_opts.set_block_size(config.block_size as usize);
+            table_opts.set_block_cache(&cache);
+            table_opts.set_cache_index_and_filter_blocks(config.cache_index_and_filter_blocks);
+            if let Some(bits_per_key) = cf_config.bloom_filter_bits_per_key {
+                table_opts.set_bloom_filter(bits_per_key as f64, false);
+            }
+
+            let mut cf_opts = Options::default();
+            cf_opts.set_compression_type(cf_config.compression.into());
+            cf_opts.set_block_based_table_factory(&table_opts);
+            ColumnFamilyDescriptor::new(name, cf_opts)
+        })
+        .collect())
+}
+
+/// The RocksDB properties reported for each column family by [`collect_stats`]. See
+/// <https://github.com/facebook/rocksdb/blob/main/include/rocksdb/db.h> for their meaning.
+pub const STATS_PROPERTIES: &[&str] = &[
+    "rocksdb.estimate-num-keys",
+    "rocksdb.estimate-live-data-size",
+    "rocksdb.total-sst-files-size",
+    "rocksdb.cur-size-all-mem-tables",
+    "rocksdb.block-cache-usage",
+    "rocksdb.estimate-pending-compaction-bytes",
+];
+
+/// The values of [`STATS_PROPERTIES`] for a single column family
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnFamilyStats {
+    pub column_family: String,
+    pub properties: BTreeMap<String, u64>,
+}
+
+/// Reads the [`STATS_PROPERTIES`] of the provided column families
+pub fn collect_stats<'a>(
+    db: &DB,
+    column_families: impl IntoIterator<Item = &'a str>,
+) -> anyhow::Result<Vec<ColumnFamilyStats>> {
+    column_families
+        .into_iter()
+        .map(|cf_name| {
+            let properties = STATS_PROPERTIES
+                .iter()
+                .map(|property| Ok((property.to_string(), db.get_property(cf_name, property)?)))
+                .collect::<anyhow::Result<_>>()?;
+            Ok(ColumnFamilyStats {
+                column_family: cf_name.to_string(),
+                properties,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_family_overrides() {
+        let config: RocksdbConfigs = serde_json::from_str(
+            r#"{
+                "state": {
+                    "max_open_files": 100,
+                    "column_families": {
+                        "JmtNodes": { "compression": "Zstd", "bloom_filter_bits_per_key": 16 }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.ledger, RocksdbConfig::default());
+        assert_eq!(config.state.max_open_files, 100);
+        assert_eq!(
+            config.state.column_family("JmtNodes"),
+            ColumnFamilyConfig {
+                compression: Compression::Zstd,
+                bloom_filter_bits_per_key: Some(16),
+            }
+        );
+        assert_eq!(
+            config.state.column_family("KeyHashToKey"),
+            ColumnFamilyConfig::default_for("KeyHashToKey")
+        );
+        assert_eq!(
+            config.state.column_family("EventByKey"),
+            ColumnFamilyConfig::default()
+        );
+    }
+}
@@ -13,18 +13,28 @@
 //! - TxHash -> TxNumber
 //!
 //! Event Tables:
-//! - (EventKey, TxNumber) -> EventNumber
+//! - (EventKey, TxNumber, EventNumber) -> ()
 //! - EventNumber -> (EventKey, EventValue)
+//!
+//! Commit Tables:
+//! - () -> SlotCommitMarker
+//!
+//! Proving Tables:
+//! - SlotNumber -> StoredWitness
+//! - (SlotNumber, SlotNumber) -> StoredProof
+//!
+//! Metadata Tables:
+//! - () -> u64
 
 use super::types::{
-    BatchNumber, DbHash, EventNumber, JmtValue, SlotNumber, StateKey, StoredBatch, StoredSlot,
-    StoredTransaction, TxNumber,
+    BatchNumber, DbHash, EventNumber, JmtValue, SlotCommitMarker, SlotNumber, StateKey,
+    StoredBatch, StoredProof, StoredSlot, StoredTransaction, StoredWitness, TxNumber,
 };
 
 use borsh::{maybestd, BorshDeserialize, BorshSerialize};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use jmt::{
-    storage::{Node, NodeKey},
+    storage::{LeafNode, Node, NodeKey},
     Version,
 };
 use sov_rollup_interface::db::errors::CodecError;
@@ -37,6 +47,8 @@ pub const STATE_TABLES: &[&str] = &[
     KeyHashToKey::table_name(),
     JmtValues::table_name(),
     JmtNodes::table_name(),
+    LatestVersion::table_name(),
+    RightmostLeafByVersion::table_name(),
 ];
 
 pub const LEDGER_TABLES: &[&str] = &[
@@ -48,8 +60,17 @@ pub const LEDGER_TABLES: &[&str] = &[
     TxByNumber::table_name(),
     EventByKey::table_name(),
     EventByNumber::table_name(),
+    SlotWitnessByNumber::table_name(),
+    ProofBySlotRange::table_name(),
+    LastCommittedSlot::table_name(),
+    LedgerSchemaVersion::table_name(),
 ];
 
+/// The version of the on-disk format of the ledger tables, which must be bumped whenever the encoding
+/// of a table changes. Version 1 writes the numbers in the keys of [`EventByKey`] in big-endian order.
+/// A ledger written with another version can't be read, and must be deleted and resynced from the DA layer.
+pub const LEDGER_SCHEMA_VERSION: u64 = 1;
+
 /// Macro to define a table that implements [`sov_rollup_interface::db::Schema`].
 /// KeyCodec<Schema> and ValueCodec<Schema> must be implemented separately.
 ///
@@ -172,11 +193,134 @@ define_table_with_default_codec!(
     (EventByNumber) EventNumber => Event
 );
 
-define_table_with_default_codec!(
+define_table_without_codec!(
     /// A "secondary index" for event data by key
     (EventByKey) (EventKey, TxNumber, EventNumber) => ()
 );
 
+impl KeyEncoder<EventByKey> for (EventKey, TxNumber, EventNumber) {
+    fn encode_key(&self) -> sov_rollup_interface::db::Result<Vec<u8>> {
+        // The numbers are written in big-endian order after the length-prefixed key, so that the events
+        // with a given key are ordered by transaction and can be paged through with a prefix seek
+        let mut out = self.0.try_to_vec()?;
+        out.write_u64::<BigEndian>(self.1 .0)
+            .expect("serialization to vec is infallible");
+        out.write_u64::<BigEndian>(self.2 .0)
+            .expect("serialization to vec is infallible");
+        Ok(out)
+    }
+}
+
+impl KeyDecoder<EventByKey> for (EventKey, TxNumber, EventNumber) {
+    fn decode_key(data: &[u8]) -> sov_rollup_interface::db::Result<Self> {
+        let mut reader = data;
+        let key = EventKey::deserialize(&mut reader)?;
+        let tx_number = TxNumber(reader.read_u64::<BigEndian>()?);
+        let event_number = EventNumber(reader.read_u64::<BigEndian>()?);
+        if !reader.is_empty() {
+            return Err(CodecError::InvalidKeyLength {
+                expected: data.len() - reader.len(),
+                got: data.len(),
+            });
+        }
+        Ok((key, tx_number, event_number))
+    }
+}
+
+impl ValueCodec<EventByKey> for () {
+    fn encode_value(&self) -> sov_rollup_interface::db::Result<Vec<u8>> {
+        Ok(vec![])
+    }
+
+    fn decode_value(_data: &[u8]) -> sov_rollup_interface::db::Result<Self> {
+        Ok(())
+    }
+}
+
+define_table_with_default_codec!(
+    /// A single entry marking the last slot which was fully committed
+    (LastCommittedSlot) () => SlotCommitMarker
+);
+
+define_table_with_default_codec!(
+    /// A single entry holding the [`LEDGER_SCHEMA_VERSION`] the ledger was written with
+    (LedgerSchemaVersion) () => u64
+);
+
+define_table_without_codec!(
+    /// The compressed witness produced while executing each slot, for use by the prover
+    (SlotWitnessByNumber) SlotNumber => StoredWitness
+);
+
+impl KeyEncoder<SlotWitnessByNumber> for SlotNumber {
+    fn encode_key(&self) -> sov_rollup_interface::db::Result<Vec<u8>> {
+        // Write the slot number in big-endian order so that old witnesses can be pruned with a forward scan
+        Ok(self.0.to_be_bytes().to_vec())
+    }
+}
+
+impl KeyDecoder<SlotWitnessByNumber> for SlotNumber {
+    fn decode_key(data: &[u8]) -> sov_rollup_interface::db::Result<Self> {
+        let bytes: [u8; 8] = data.try_into().map_err(|_| CodecError::InvalidKeyLength {
+            expected: std::mem::size_of::<u64>(),
+            got: data.len(),
+        })?;
+        Ok(SlotNumber(u64::from_be_bytes(bytes)))
+    }
+}
+
+impl ValueCodec<SlotWitnessByNumber> for StoredWitness {
+    fn encode_value(&self) -> sov_rollup_interface::db::Result<Vec<u8>> {
+        self.try_to_vec().map_err(CodecError::from)
+    }
+
+    fn decode_value(data: &[u8]) -> sov_rollup_interface::db::Result<Self> {
+        Ok(Self::deserialize_reader(&mut &data[..])?)
+    }
+}
+
+define_table_without_codec!(
+    /// Proofs of contiguous ranges of slots, keyed by the (inclusive) first and last slot covered
+    (ProofBySlotRange) (SlotNumber, SlotNumber) => StoredProof
+);
+
+impl KeyEncoder<ProofBySlotRange> for (SlotNumber, SlotNumber) {
+    fn encode_key(&self) -> sov_rollup_interface::db::Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(2 * std::mem::size_of::<u64>());
+        // Write the slot numbers in big-endian order so that ranges are sorted by their first slot
+        out.write_u64::<BigEndian>(self.0 .0)
+            .expect("serialization to vec is infallible");
+        out.write_u64::<BigEndian>(self.1 .0)
+            .expect("serialization to vec is infallible");
+        Ok(out)
+    }
+}
+
+impl KeyDecoder<ProofBySlotRange> for (SlotNumber, SlotNumber) {
+    fn decode_key(data: &[u8]) -> sov_rollup_interface::db::Result<Self> {
+        if data.len() != 2 * std::mem::size_of::<u64>() {
+            return Err(CodecError::InvalidKeyLength {
+                expected: 2 * std::mem::size_of::<u64>(),
+                got: data.len(),
+            });
+        }
+        let mut cursor = maybestd::io::Cursor::new(data);
+        let first = cursor.read_u64::<BigEndian>()?;
+        let last = cursor.read_u64::<BigEndian>()?;
+        Ok((SlotNumber(first), SlotNumber(last)))
+    }
+}
+
+impl ValueCodec<ProofBySlotRange> for StoredProof {
+    fn encode_value(&self) -> sov_rollup_interface::db::Result<Vec<u8>> {
+        self.try_to_vec().map_err(CodecError::from)
+    }
+
+    fn decode_value(data: &[u8]) -> sov_rollup_interface::db::Result<Self> {
+        Ok(Self::deserialize_reader(&mut &data[..])?)
+    }
+}
+
 define_table_without_codec!(
     /// The source of truth for JMT nodes
     (JmtNodes) NodeKey => Node
@@ -249,3 +393,41 @@ define_table_with_default_codec!(
     /// which requires the ability to fetch values by hash.
     (KeyHashToKey) [u8;32] => StateKey
 );
+
+define_table_with_default_codec!(
+    /// A single entry storing the latest version whose nodes and values were fully written
+    (LatestVersion) () => Version
+);
+
+define_table_without_codec!(
+    /// The leaf with the largest key hash among the nodes written at each version. Restoring a tree
+    /// from chunks writes all of its nodes at a single version, so this is where a restore resumes.
+    (RightmostLeafByVersion) Version => (NodeKey, LeafNode)
+);
+
+impl KeyEncoder<RightmostLeafByVersion> for Version {
+    fn encode_key(&self) -> sov_rollup_interface::db::Result<Vec<u8>> {
+        // Write the version in big-endian order so that the latest version sorts last
+        Ok(self.to_be_bytes().to_vec())
+    }
+}
+
+impl KeyDecoder<RightmostLeafByVersion> for Version {
+    fn decode_key(data: &[u8]) -> sov_rollup_interface::db::Result<Self> {
+        let bytes: [u8; 8] = data.try_into().map_err(|_| CodecError::InvalidKeyLength {
+            expected: std::mem::size_of::<Version>(),
+            got: data.len(),
+        })?;
+        Ok(Version::from_be_bytes(bytes))
+    }
+}
+
+impl ValueCodec<RightmostLeafByVersion> for (NodeKey, LeafNode) {
+    fn encode_value(&self) -> sov_rollup_interface::db::Result<Vec<u8>> {
+        self.try_to_vec().map_err(CodecError::from)
+    }
+
+    fn decode_value(data: &[u8]) -> sov_rollup_interface::db::Result<Self> {
+        Ok(Self::deserialize_reader(&mut &data[..])?)
+    }
+}
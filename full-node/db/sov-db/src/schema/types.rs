@@ -58,6 +58,21 @@ pub struct StoredSlot {
     pub extra_data: DbBytes,
     pub batches: std::ops::Range<BatchNumber>,
 }
+
+/// Marks the last slot whose data was fully committed to the ledger. It is written in the same atomic
+/// batch as the slot, so it never refers to a partially written slot.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct SlotCommitMarker {
+    pub slot_number: SlotNumber,
+    /// The numbers which will be assigned to the first batch, transaction and event of the next slot
+    pub next_batch_number: BatchNumber,
+    pub next_tx_number: TxNumber,
+    pub next_event_number: EventNumber,
+    /// The state root after executing the slot, if it was provided.
+    /// State changes made after this root can be rolled back if the node crashed before committing them to the ledger.
+    pub state_root: Option<DbHash>,
+}
+
 /// The on-disk format for a batch. Stores the hash and identifies the range of transactions
 /// included in the batch
 #[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize)]
@@ -79,6 +94,66 @@ impl<B: DeserializeOwned, T> TryFrom<StoredBatch> for BatchResponse<B, T> {
     }
 }
 
+/// The on-disk format of a proof. The range of slots covered by the proof is stored
+/// in the key, so only the serialized proof itself is kept here.
+#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct StoredProof {
+    pub proof: DbBytes,
+}
+
+/// The on-disk format of the witness of a slot. Witnesses consist mostly of merkle proofs
+/// and serialized tree nodes, so they are stored lz4-compressed. The state root before the slot
+/// is stored alongside the witness, since it's required to replay the slot in zk mode.
+#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct StoredWitness {
+    pub prev_state_root: DbHash,
+    pub compressed_witness: DbBytes,
+}
+
+impl StoredWitness {
+    /// Serializes and compresses the provided witness.
+    pub fn new<W: Serialize>(prev_state_root: DbHash, witness: &W) -> Result<Self, anyhow::Error> {
+        let serialized = bincode::serialize(witness)?;
+        Ok(Self {
+            prev_state_root,
+            compressed_witness: DbBytes::new(lz4_flex::compress_prepend_size(&serialized)),
+        })
+    }
+
+    /// Returns the uncompressed, serialized witness.
+    pub fn decompress(&self) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(lz4_flex::decompress_size_prepended(
+            self.compressed_witness.as_ref(),
+        )?)
+    }
+
+    /// Decompresses and deserializes the witness.
+    pub fn to_witness<W: DeserializeOwned>(&self) -> Result<W, anyhow::Error> {
+        Ok(bincode::deserialize(&self.decompress()?)?)
+    }
+}
+
+/// How long the witnesses of committed slots are kept in the ledger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WitnessRetention {
+    /// Never delete witnesses
+    KeepAll,
+    /// Keep the witnesses of the given number of most recent slots
+    KeepLatest(u64),
+    /// Delete witnesses once a proof covering their slot, and every slot before it, has been stored.
+    /// Witnesses are kept forever if nothing proves the slots, so only use this with a prover running.
+    UntilProven,
+}
+
+/// The number of witnesses kept by the default [`WitnessRetention`]
+pub const DEFAULT_RETAINED_WITNESSES: u64 = 1000;
+
+impl Default for WitnessRetention {
+    fn default() -> Self {
+        Self::KeepLatest(DEFAULT_RETAINED_WITNESSES)
+    }
+}
+
 /// The on-disk format of a transaction. Includes the txhash, the serialized tx data,
 /// and identifies the events emitted by this transaction
 #[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize, Clone)]
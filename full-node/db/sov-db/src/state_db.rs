@@ -1,20 +1,26 @@
 use std::{
+    collections::{BTreeMap, BTreeSet},
     path::Path,
     sync::{Arc, Mutex},
 };
 
 use jmt::{
-    storage::{TreeReader, TreeWriter},
-    KeyHash, Version,
+    storage::{LeafNode, Node, NodeBatch, NodeKey, TreeReader, TreeWriter},
+    JellyfishMerkleTree, KeyHash, SimpleHasher, Version,
 };
 
-use sov_schema_db::DB;
+use sov_schema_db::{SchemaBatch, DB};
 
 use crate::{
-    rocks_db_config::gen_rocksdb_options,
+    rocks_db_config::{
+        collect_stats, gen_cf_descriptors, gen_rocksdb_options, gen_secondary_rocksdb_options,
+        ColumnFamilyStats, RocksdbConfig,
+    },
     schema::{
-        tables::{JmtNodes, JmtValues, KeyHashToKey, STATE_TABLES},
-        types::StateKey,
+        tables::{
+            JmtNodes, JmtValues, KeyHashToKey, LatestVersion, RightmostLeafByVersion, STATE_TABLES,
+        },
+        types::{DbHash, StateKey},
     },
 };
 
@@ -28,12 +34,20 @@ const STATE_DB_PATH_SUFFIX: &str = "state";
 
 impl StateDB {
     pub fn with_path(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        Self::with_config(path, &Default::default())
+    }
+
+    /// Opens the state stored in `path`, tuning rocksdb with the provided config
+    pub fn with_config(
+        path: impl AsRef<Path>,
+        config: &RocksdbConfig,
+    ) -> Result<Self, anyhow::Error> {
         let path = path.as_ref().join(STATE_DB_PATH_SUFFIX);
-        let inner = DB::open(
+        let inner = DB::open_cf(
+            &gen_rocksdb_options(config, false),
             path,
             "state-db",
-            STATE_TABLES.iter().copied(),
-            &gen_rocksdb_options(&Default::default(), false),
+            gen_cf_descriptors(config, STATE_TABLES.iter().copied())?,
         )?;
 
         let next_version = Self::last_version_written(&inner)?.unwrap_or_default() + 1;
@@ -44,6 +58,38 @@ impl StateDB {
         })
     }
 
+    /// Opens the state stored in `path` as a read-only secondary of the process which writes it.
+    /// The secondary keeps its own info logs in `secondary_path`, and only sees the writes of the primary
+    /// after calling [`StateDB::catch_up_with_primary`].
+    pub fn secondary(
+        path: impl AsRef<Path>,
+        secondary_path: impl AsRef<Path>,
+        config: &RocksdbConfig,
+    ) -> Result<Self, anyhow::Error> {
+        let inner = DB::open_cf_as_secondary(
+            &gen_secondary_rocksdb_options(config),
+            path.as_ref().join(STATE_DB_PATH_SUFFIX),
+            secondary_path.as_ref().join(STATE_DB_PATH_SUFFIX),
+            "state-db-secondary",
+            STATE_TABLES.to_vec(),
+        )?;
+        let next_version = Self::last_version_written(&inner)?.unwrap_or_default() + 1;
+
+        Ok(Self {
+            db: Arc::new(inner),
+            next_version: Arc::new(Mutex::new(next_version)),
+        })
+    }
+
+    /// Catches a secondary state up with the versions written by the primary, so that reads are served
+    /// from its latest version.
+    pub fn catch_up_with_primary(&self) -> Result<(), anyhow::Error> {
+        let mut next_version = self.next_version.lock().unwrap();
+        self.db.try_catch_up_with_primary()?;
+        *next_version = Self::last_version_written(&self.db)?.unwrap_or_default() + 1;
+        Ok(())
+    }
+
     /// A rocksdb instance which stores its data in a tempdir
     #[cfg(any(test, feature = "temp"))]
     pub fn temporary() -> Self {
@@ -51,6 +97,11 @@ impl StateDB {
         Self::with_path(path).unwrap()
     }
 
+    /// Returns the rocksdb stats of each table of the state
+    pub fn rocksdb_stats(&self) -> Result<Vec<ColumnFamilyStats>, anyhow::Error> {
+        collect_stats(&self.db, STATE_TABLES.iter().copied())
+    }
+
     pub fn put_preimage(&self, key_hash: KeyHash, key: &Vec<u8>) -> Result<(), anyhow::Error> {
         self.db.put::<KeyHashToKey>(&key_hash.0, key)
     }
@@ -85,9 +136,10 @@ impl StateDB {
         value: Option<Vec<u8>>,
         next_version: Version,
     ) -> anyhow::Result<()> {
-        self.put_preimage(key_hash, &key)?;
-        self.db.put::<JmtValues>(&(key, next_version), &value)?;
-        Ok(())
+        let batch = SchemaBatch::new();
+        batch.put::<KeyHashToKey>(&key_hash.0, &key)?;
+        batch.put::<JmtValues>(&(key, next_version), &value)?;
+        self.db.write_schemas(batch)
     }
 
     pub fn inc_next_version(&self) {
@@ -100,7 +152,162 @@ impl StateDB {
         *version
     }
 
+    /// Rolls the state back to the most recent version whose root is `root`, deleting the nodes and
+    /// values of all later versions. This undoes the state changes of slots which were executed but never
+    /// committed to the ledger, for example because the node crashed in between.
+    /// Returns the version the state was rolled back to.
+    ///
+    /// This scans every node and value in the database, so it should only be used during recovery.
+    pub fn rollback_to_root<H: SimpleHasher>(&self, root: DbHash) -> anyhow::Result<Version> {
+        let mut next_version = self.next_version.lock().unwrap();
+        let latest_version = *next_version - 1;
+        let jmt = JellyfishMerkleTree::<_, H>::new(self);
+        let mut target = None;
+        for version in (0..=latest_version).rev() {
+            if jmt.get_root_hash_option(version)?.map(|r| r.0) == Some(root) {
+                target = Some(version);
+                break;
+            }
+        }
+        let target = target.ok_or_else(|| {
+            anyhow::format_err!(
+                "State does not contain root 0x{} at any version",
+                hex::encode(root)
+            )
+        })?;
+        if target == latest_version {
+            return Ok(target);
+        }
+
+        let batch = SchemaBatch::new();
+        let mut nodes = self.db.iter::<JmtNodes>()?;
+        nodes.seek_to_first();
+        for entry in nodes {
+            let (node_key, _) = entry?;
+            if node_key.version() > target {
+                batch.delete::<JmtNodes>(&node_key)?;
+            }
+        }
+        let mut values = self.db.iter::<JmtValues>()?;
+        values.seek_to_first();
+        let mut rolled_back_keys = BTreeSet::new();
+        for entry in values {
+            let ((key, version), _) = entry?;
+            if version > target {
+                batch.delete::<JmtValues>(&(key.clone(), version))?;
+                rolled_back_keys.insert(key);
+            }
+        }
+        // Keys which were first written after the target version must not keep their preimage
+        for key in rolled_back_keys {
+            if !self.has_value_at_or_before(&key, target)? {
+                batch.delete::<KeyHashToKey>(&H::hash(&key))?;
+            }
+        }
+        let mut rightmost_leaves = self.db.iter::<RightmostLeafByVersion>()?;
+        rightmost_leaves.seek(&(target + 1))?;
+        for entry in rightmost_leaves {
+            let (version, _) = entry?;
+            batch.delete::<RightmostLeafByVersion>(&version)?;
+        }
+        batch.put::<LatestVersion>(&(), &target)?;
+        self.db.write_schemas(batch)?;
+
+        *next_version = target + 1;
+        Ok(target)
+    }
+
+    /// Returns true if `key` was written, or deleted, at `version` or earlier
+    fn has_value_at_or_before(&self, key: &StateKey, version: Version) -> anyhow::Result<bool> {
+        let mut iter = self.db.iter::<JmtValues>()?;
+        iter.seek_for_prev(&(key, version))?;
+        match iter.next() {
+            Some(entry) => {
+                let ((found_key, _), _) = entry?;
+                Ok(&found_key == key)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Writes the preimages of the updated keys together with the nodes and values of `node_batch`,
+    /// in a single atomic write. Unlike [`StateDB::put_preimage`], a failed update doesn't leave the
+    /// preimages of keys which were never written behind.
+    pub fn write_node_batch_with_preimages(
+        &self,
+        node_batch: &NodeBatch,
+        preimages: &[(KeyHash, Vec<u8>)],
+    ) -> anyhow::Result<()> {
+        let batch = SchemaBatch::new();
+        for (key_hash, key) in preimages {
+            batch.put::<KeyHashToKey>(&key_hash.0, key)?;
+        }
+        self.put_node_batch(&batch, node_batch, preimages)?;
+        self.db.write_schemas(batch)
+    }
+
+    /// Adds the nodes and values of `node_batch` to `batch`, together with the latest version they belong
+    /// to and the rightmost leaf written at each version. The preimages of the values are looked up in
+    /// `new_preimages` first, and then in the database.
+    fn put_node_batch(
+        &self,
+        batch: &SchemaBatch,
+        node_batch: &NodeBatch,
+        new_preimages: &[(KeyHash, Vec<u8>)],
+    ) -> anyhow::Result<()> {
+        let mut rightmost_leaves: BTreeMap<Version, (&NodeKey, &LeafNode)> = BTreeMap::new();
+        for (node_key, node) in node_batch.nodes() {
+            batch.put::<JmtNodes>(node_key, node)?;
+            if let Node::Leaf(leaf) = node {
+                let rightmost = rightmost_leaves
+                    .entry(node_key.version())
+                    .or_insert((node_key, leaf));
+                if leaf.key_hash().0 > rightmost.1.key_hash().0 {
+                    *rightmost = (node_key, leaf);
+                }
+            }
+        }
+
+        // A restore writes its nodes in several batches at the same version
+        for (version, (node_key, leaf)) in rightmost_leaves {
+            let is_rightmost = match self.db.get::<RightmostLeafByVersion>(&version)? {
+                Some((_, existing)) => leaf.key_hash().0 > existing.key_hash().0,
+                None => true,
+            };
+            if is_rightmost {
+                batch.put::<RightmostLeafByVersion>(&version, &(node_key.clone(), leaf.clone()))?;
+            }
+        }
+
+        let new_preimages: BTreeMap<[u8; 32], &Vec<u8>> = new_preimages
+            .iter()
+            .map(|(key_hash, key)| (key_hash.0, key))
+            .collect();
+        for ((version, key_hash), value) in node_batch.values() {
+            let key_preimage = match new_preimages.get(&key_hash.0) {
+                Some(key) => (*key).clone(),
+                None => self
+                    .db
+                    .get::<KeyHashToKey>(&key_hash.0)?
+                    .ok_or(anyhow::format_err!(
+                        "Could not find preimage for key hash {key_hash:?}"
+                    ))?,
+            };
+            batch.put::<JmtValues>(&(key_preimage, *version), value)?;
+        }
+
+        // Every update writes a new root node, so the latest version is the highest one in the batch
+        if let Some(version) = node_batch.nodes().keys().map(|key| key.version()).max() {
+            batch.put::<LatestVersion>(&(), &version)?;
+        }
+        Ok(())
+    }
+
     fn last_version_written(db: &DB) -> anyhow::Result<Option<Version>> {
+        if let Some(version) = db.get::<LatestVersion>(&())? {
+            return Ok(Some(version));
+        }
+        // Databases written before the latest version was recorded
         let mut iter = db.iter::<JmtValues>()?;
         iter.seek_to_last();
 
@@ -132,29 +339,24 @@ impl TreeReader for StateDB {
         }
     }
 
-    fn get_rightmost_leaf(
-        &self,
-    ) -> anyhow::Result<Option<(jmt::storage::NodeKey, jmt::storage::LeafNode)>> {
-        todo!()
+    /// Returns the rightmost leaf written at the latest version, or `None` if that version wrote no leaves,
+    /// e.g. because it only deleted keys. As `jmt` assumes, this is the rightmost leaf of the tree while a
+    /// tree is being restored, since all of its nodes are written at the same version.
+    fn get_rightmost_leaf(&self) -> anyhow::Result<Option<(NodeKey, LeafNode)>> {
+        match Self::last_version_written(&self.db)? {
+            Some(version) => self.db.get::<RightmostLeafByVersion>(&version),
+            None => Ok(None),
+        }
     }
 }
 
 impl TreeWriter for StateDB {
-    fn write_node_batch(&self, node_batch: &jmt::storage::NodeBatch) -> anyhow::Result<()> {
-        for (node_key, node) in node_batch.nodes() {
-            self.db.put::<JmtNodes>(node_key, node)?;
-        }
-
-        for ((version, key_hash), value) in node_batch.values() {
-            let key_preimage =
-                self.db
-                    .get::<KeyHashToKey>(&key_hash.0)?
-                    .ok_or(anyhow::format_err!(
-                        "Could not find preimage for key hash {key_hash:?}"
-                    ))?;
-            self.db.put::<JmtValues>(&(key_preimage, *version), value)?;
-        }
-        Ok(())
+    /// Writes the nodes and values of the batch atomically, together with the latest version they belong to
+    /// and the rightmost leaf written at each version.
+    fn write_node_batch(&self, node_batch: &NodeBatch) -> anyhow::Result<()> {
+        let batch = SchemaBatch::new();
+        self.put_node_batch(&batch, node_batch, &[])?;
+        self.db.write_schemas(batch)
     }
 }
 
@@ -162,10 +364,16 @@ impl TreeWriter for StateDB {
 mod state_db_tests {
     use jmt::{
         storage::{NodeBatch, TreeReader, TreeWriter},
-        KeyHash,
+        JellyfishMerkleTree, KeyHash, SimpleHasher, Version,
     };
+    use sha2::Sha256;
 
     use super::StateDB;
+    use crate::rocks_db_config::{
+        ColumnFamilyConfig, Compression, RocksdbConfig, STATS_PROPERTIES,
+    };
+    use crate::schema::tables::KeyHashToKey;
+    use crate::schema::tables::STATE_TABLES;
 
     #[test]
     fn test_simple() {
@@ -185,4 +393,184 @@ mod state_db_tests {
         let found = db.get_value_option_by_key(0, &key).unwrap().unwrap();
         assert_eq!(found, value);
     }
+
+    #[test]
+    fn test_with_config() {
+        let path = sov_schema_db::temppath::TempPath::new();
+        let key_hash = KeyHash([1u8; 32]);
+        let key = vec![2u8; 100];
+        let value = [8u8; 150];
+
+        let mut config = RocksdbConfig::default();
+        config.column_families.insert(
+            "JmtValues".to_string(),
+            ColumnFamilyConfig {
+                compression: Compression::Zstd,
+                bloom_filter_bits_per_key: Some(16),
+            },
+        );
+        {
+            let db = StateDB::with_config(&path, &config).unwrap();
+            db.put_preimage(key_hash, &key).unwrap();
+            let mut batch = NodeBatch::default();
+            batch.extend(vec![], vec![((0, key_hash), Some(value.to_vec()))]);
+            db.write_node_batch(&batch).unwrap();
+        }
+
+        // Column family options aren't persisted, so the db can be reopened with different ones
+        let db = StateDB::with_path(&path).unwrap();
+        assert_eq!(db.get_value(0, key_hash).unwrap(), value);
+
+        let stats = db.rocksdb_stats().unwrap();
+        let column_families: Vec<&str> = stats.iter().map(|s| s.column_family.as_str()).collect();
+        assert_eq!(column_families, STATE_TABLES);
+        for cf_stats in stats {
+            assert_eq!(cf_stats.properties.len(), STATS_PROPERTIES.len());
+        }
+    }
+
+    #[test]
+    fn test_failed_node_batch_writes_nothing() {
+        let db = StateDB::temporary();
+        let jmt = JellyfishMerkleTree::<_, Sha256>::new(&db);
+        let key_hash = KeyHash([1u8; 32]);
+        let (root, tree_update) = jmt
+            .put_value_set(vec![(key_hash, Some(vec![1u8]))], 0)
+            .unwrap();
+
+        // The preimage of the key is missing, so writing its value fails after the nodes were processed
+        assert!(db.write_node_batch(&tree_update.node_batch).is_err());
+        assert!(jmt.get_root_hash_option(0).unwrap().is_none());
+
+        db.put_preimage(key_hash, &vec![2u8]).unwrap();
+        db.write_node_batch(&tree_update.node_batch).unwrap();
+        assert_eq!(jmt.get_root_hash(0).unwrap(), root);
+    }
+
+    #[test]
+    fn test_preimages_are_written_with_the_node_batch() {
+        let db = StateDB::temporary();
+        let jmt = JellyfishMerkleTree::<_, Sha256>::new(&db);
+        let key = vec![2u8; 4];
+        let key_hash = KeyHash(Sha256::hash(&key));
+        let (root, tree_update) = jmt
+            .put_value_set(vec![(key_hash, Some(vec![1u8]))], 0)
+            .unwrap();
+
+        db.write_node_batch_with_preimages(&tree_update.node_batch, &[(key_hash, key.clone())])
+            .unwrap();
+        assert_eq!(jmt.get_root_hash(0).unwrap(), root);
+        assert_eq!(db.get_value(0, key_hash).unwrap(), vec![1u8]);
+        assert_eq!(db.db.get::<KeyHashToKey>(&key_hash.0).unwrap(), Some(key));
+
+        // A batch which fails to write doesn't store the preimages it was given either
+        let other_key = vec![3u8; 4];
+        let other_hash = KeyHash(Sha256::hash(&other_key));
+        let (_, tree_update) = jmt
+            .put_value_set(
+                vec![
+                    (other_hash, Some(vec![2u8])),
+                    (KeyHash([7; 32]), Some(vec![3u8])),
+                ],
+                1,
+            )
+            .unwrap();
+        assert!(db
+            .write_node_batch_with_preimages(&tree_update.node_batch, &[(other_hash, other_key)])
+            .is_err());
+        assert_eq!(db.db.get::<KeyHashToKey>(&other_hash.0).unwrap(), None);
+        assert!(jmt.get_root_hash_option(1).unwrap().is_none());
+    }
+
+    /// Writes the key `[version; 4]` at the given version, returning the new root
+    fn write_version(db: &StateDB, version: Version) -> [u8; 32] {
+        let key = vec![version as u8; 4];
+        let key_hash = KeyHash(Sha256::hash(&key));
+        db.put_preimage(key_hash, &key).unwrap();
+        let (root, tree_update) = JellyfishMerkleTree::<_, Sha256>::new(db)
+            .put_value_set(vec![(key_hash, Some(key.clone()))], version)
+            .unwrap();
+        db.write_node_batch(&tree_update.node_batch).unwrap();
+        root.0
+    }
+
+    #[test]
+    fn test_rollback_to_root() {
+        let path = sov_schema_db::temppath::TempPath::new();
+        let roots: Vec<[u8; 32]> = {
+            let db = StateDB::with_path(&path).unwrap();
+            (0..3).map(|version| write_version(&db, version)).collect()
+        };
+
+        // Simulates a crash after version 2 was written, but before its slot was committed to the ledger
+        let db = StateDB::with_path(&path).unwrap();
+        assert_eq!(db.get_next_version(), 3);
+        assert_eq!(db.rollback_to_root::<Sha256>(roots[1]).unwrap(), 1);
+        assert_eq!(db.get_next_version(), 2);
+        assert!(JellyfishMerkleTree::<_, Sha256>::new(&db)
+            .get_root_hash_option(2)
+            .unwrap()
+            .is_none());
+        assert_eq!(db.get_value_option_by_key(2, &vec![2u8; 4]).unwrap(), None);
+        assert_eq!(
+            db.get_value_option_by_key(2, &vec![1u8; 4]).unwrap(),
+            Some(vec![1u8; 4])
+        );
+        // The key written only by the rolled back version is no longer indexed by its hash
+        let preimage = |key: Vec<u8>| db.db.get::<KeyHashToKey>(&Sha256::hash(key)).unwrap();
+        assert_eq!(preimage(vec![2u8; 4]), None);
+        assert_eq!(preimage(vec![1u8; 4]), Some(vec![1u8; 4]));
+
+        // The rollback survives a restart, and unknown roots are rejected
+        drop(db);
+        let db = StateDB::with_path(&path).unwrap();
+        assert_eq!(db.get_next_version(), 2);
+        assert!(db.rollback_to_root::<Sha256>([0; 32]).is_err());
+    }
+
+    #[test]
+    fn test_rightmost_leaf() {
+        let db = StateDB::temporary();
+        assert!(db.get_rightmost_leaf().unwrap().is_none());
+
+        // Writes the keys at version 0 in a separate batch, like each chunk of a restore
+        let write_chunk = |keys: &[KeyHash]| {
+            let scratch = StateDB::temporary();
+            let (_, tree_update) = JellyfishMerkleTree::<_, Sha256>::new(&scratch)
+                .put_value_set(keys.iter().map(|key| (*key, Some(vec![1u8]))), 0)
+                .unwrap();
+            for key in keys {
+                db.put_preimage(*key, &key.0.to_vec()).unwrap();
+            }
+            db.write_node_batch(&tree_update.node_batch).unwrap();
+        };
+
+        write_chunk(&[KeyHash([3; 32]), KeyHash([9; 32]), KeyHash([5; 32])]);
+        let (node_key, leaf) = db.get_rightmost_leaf().unwrap().unwrap();
+        assert_eq!(leaf.key_hash(), KeyHash([9; 32]));
+        assert_eq!(node_key.version(), 0);
+
+        write_chunk(&[KeyHash([1; 32])]);
+        assert_eq!(
+            db.get_rightmost_leaf().unwrap().unwrap().1.key_hash(),
+            KeyHash([9; 32])
+        );
+        write_chunk(&[KeyHash([0xff; 32])]);
+        assert_eq!(
+            db.get_rightmost_leaf().unwrap().unwrap().1.key_hash(),
+            KeyHash([0xff; 32])
+        );
+
+        // Only the leaves written at the latest version are considered
+        write_version(&db, 1);
+        let (node_key, _) = db.get_rightmost_leaf().unwrap().unwrap();
+        assert_eq!(node_key.version(), 1);
+
+        // A version which only deletes keys writes no leaves, so it has no rightmost leaf
+        let (_, tree_update) = JellyfishMerkleTree::<_, Sha256>::new(&db)
+            .put_value_set(vec![(KeyHash([0xff; 32]), None)], 2)
+            .unwrap();
+        db.write_node_batch(&tree_update.node_batch).unwrap();
+        assert!(db.get_rightmost_leaf().unwrap().is_none());
+    }
 }
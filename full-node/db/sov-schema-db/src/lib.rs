@@ -163,6 +163,12 @@ impl DB {
         Ok(Self::log_construct(name, inner))
     }
 
+    /// Catches a secondary db up with the writes made by the primary instance since it was opened or
+    /// last caught up. Returns an error if the db wasn't opened as a secondary.
+    pub fn try_catch_up_with_primary(&self) -> Result<()> {
+        Ok(self.inner.try_catch_up_with_primary()?)
+    }
+
     fn log_construct(name: &'static str, inner: rocksdb::DB) -> DB {
         info!(rocksdb_name = name, "Opened RocksDB.");
         DB { name, inner }
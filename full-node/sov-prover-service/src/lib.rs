@@ -0,0 +1,455 @@
+//! A service which generates proofs for slots that have already been executed by the full node.
+//!
+//! The full node stores each committed slot together with the witness produced while executing it.
+//! The prover service polls the [`LedgerDB`] for newly committed slots, groups them into ranges of
+//! `slots_per_proof` slots, and hands each range to a pool of workers. Finished proofs are stored
+//! back into the ledger, keyed by the range of slots they cover, and their status can be queried over RPC.
+//! Ranges which fail to prove are retried with an increasing delay.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use sov_db::ledger_db::LedgerDB;
+use sov_db::schema::types::{SlotNumber, StoredProof};
+use tracing::{debug, error, info, warn};
+
+pub mod rpc;
+
+/// The longest the scheduler waits before retrying a failed range, or polling the ledger again after an error
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ProverServiceConfig {
+    /// The number of proofs which can be generated concurrently
+    pub num_workers: usize,
+    /// The number of consecutive slots covered by each proof
+    pub slots_per_proof: u64,
+    /// The first slot to prove if the ledger doesn't contain any proofs yet
+    pub start_slot: u64,
+    /// How often to check the ledger for newly committed slots, in milliseconds
+    pub poll_interval_ms: u64,
+}
+
+impl Default for ProverServiceConfig {
+    fn default() -> Self {
+        Self {
+            num_workers: 1,
+            slots_per_proof: 1,
+            // Slot numbers in the ledger start at 1
+            start_slot: 1,
+            poll_interval_ms: 1000,
+        }
+    }
+}
+
+/// The data needed to prove a single slot without re-executing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotInput {
+    pub number: u64,
+    /// The hash of the DA layer block
+    pub hash: [u8; 32],
+    /// The state root before the slot was executed
+    pub prev_state_root: [u8; 32],
+    /// The serialized witness generated when the slot was executed
+    pub witness: Vec<u8>,
+}
+
+/// A request to prove the slots `first_slot` through `last_slot` (inclusive).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvingJob {
+    pub first_slot: u64,
+    pub last_slot: u64,
+    /// The inputs of each slot in the range, in order
+    pub slots: Vec<SlotInput>,
+}
+
+/// A proof system capable of proving a range of slots. Implementations typically feed the slot
+/// inputs to a zkVM guest running the state transition function and serialize the resulting receipt.
+pub trait SlotProver: Send + Sync + 'static {
+    /// Generate a proof for the provided job, returning the serialized proof.
+    fn prove(&self, job: &ProvingJob) -> Result<Vec<u8>, anyhow::Error>;
+}
+
+/// The status of a proof for a range of slots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProofStatus {
+    /// The range is waiting for a free worker
+    Queued,
+    /// A worker is currently proving the range
+    Proving,
+    /// The proof has been stored in the ledger
+    Completed,
+    /// The last attempt to generate the proof failed. The range is queued again after a delay,
+    /// and it's also rescheduled when the service restarts, since proving resumes from the first
+    /// slot without a proof.
+    Failed(String),
+}
+
+type SlotRange = (u64, u64);
+
+/// A range handed to the workers, together with the number of times it has already failed to prove
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ScheduledRange {
+    range: SlotRange,
+    failed_attempts: u32,
+}
+
+/// A cheaply cloneable handle for querying the status of proofs, used to serve RPC requests.
+#[derive(Clone)]
+pub struct ProofStatusProvider {
+    ledger_db: LedgerDB,
+    start_slot: u64,
+    /// The status of every range which has been scheduled but not yet written to the ledger
+    pending: Arc<Mutex<HashMap<SlotRange, ProofStatus>>>,
+}
+
+impl ProofStatusProvider {
+    fn new(ledger_db: LedgerDB, start_slot: u64) -> Self {
+        Self {
+            ledger_db,
+            start_slot,
+            pending: Default::default(),
+        }
+    }
+
+    /// Returns the status of the proof covering exactly `first_slot` through `last_slot`,
+    /// or `None` if no such proof has been scheduled.
+    pub fn get_status(
+        &self,
+        first_slot: u64,
+        last_slot: u64,
+    ) -> Result<Option<ProofStatus>, anyhow::Error> {
+        if let Some(status) = self.pending.lock().unwrap().get(&(first_slot, last_slot)) {
+            return Ok(Some(status.clone()));
+        }
+        Ok(self
+            .get_proof(first_slot, last_slot)?
+            .map(|_| ProofStatus::Completed))
+    }
+
+    /// Returns the serialized proof covering exactly `first_slot` through `last_slot`, if it has been generated.
+    pub fn get_proof(
+        &self,
+        first_slot: u64,
+        last_slot: u64,
+    ) -> Result<Option<Vec<u8>>, anyhow::Error> {
+        Ok(self
+            .ledger_db
+            .get_proof(SlotNumber(first_slot), SlotNumber(last_slot))?
+            .map(|stored| stored.proof.as_ref().to_vec()))
+    }
+
+    /// Returns the last slot such that every slot from `start_slot` up to it has been proven, or `None`
+    /// if the first range hasn't been proven yet. Proofs stored after a failed range aren't counted.
+    pub fn get_last_proven_slot(&self) -> Result<Option<u64>, anyhow::Error> {
+        let first_unproven = self
+            .ledger_db
+            .get_first_unproven_slot(SlotNumber(self.start_slot))?;
+        if first_unproven.0 > self.start_slot {
+            Ok(Some(first_unproven.0 - 1))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn set_status(&self, range: SlotRange, status: ProofStatus) {
+        self.pending.lock().unwrap().insert(range, status);
+    }
+
+    fn clear_status(&self, range: SlotRange) {
+        self.pending.lock().unwrap().remove(&range);
+    }
+}
+
+pub struct ProverService<P: SlotProver> {
+    ledger_db: LedgerDB,
+    prover: Arc<P>,
+    config: ProverServiceConfig,
+}
+
+impl<P: SlotProver> ProverService<P> {
+    pub fn new(ledger_db: LedgerDB, prover: P, config: ProverServiceConfig) -> Self {
+        Self {
+            ledger_db,
+            prover: Arc::new(prover),
+            config,
+        }
+    }
+
+    /// Starts the scheduler and the worker threads. Proving resumes from the first slot at or after
+    /// `start_slot` which isn't covered by a proof in the ledger, so ranges which failed before a
+    /// restart are proven again.
+    pub fn start(self) -> Result<ProverServiceHandle, anyhow::Error> {
+        anyhow::ensure!(
+            self.config.num_workers > 0,
+            "The prover service needs at least one worker"
+        );
+        anyhow::ensure!(
+            self.config.slots_per_proof > 0,
+            "Each proof must cover at least one slot"
+        );
+
+        let next_slot_to_schedule = self
+            .ledger_db
+            .get_first_unproven_slot(SlotNumber(self.config.start_slot))?
+            .0;
+        info!(
+            "Starting prover service with {} workers from slot {}",
+            self.config.num_workers, next_slot_to_schedule
+        );
+
+        let status = ProofStatusProvider::new(self.ledger_db.clone(), self.config.start_slot);
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::channel::<ScheduledRange>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let (failure_sender, failure_receiver) = mpsc::channel::<ScheduledRange>();
+
+        let mut threads = Vec::with_capacity(self.config.num_workers + 1);
+        for _ in 0..self.config.num_workers {
+            let worker = Worker {
+                ledger_db: self.ledger_db.clone(),
+                prover: self.prover.clone(),
+                status: status.clone(),
+                failures: failure_sender.clone(),
+            };
+            let receiver = receiver.clone();
+            threads.push(std::thread::spawn(move || worker.run(receiver)));
+        }
+
+        let scheduler = Scheduler {
+            ledger_db: self.ledger_db,
+            status: status.clone(),
+            slots_per_proof: self.config.slots_per_proof,
+            next_slot_to_schedule,
+            poll_interval: Duration::from_millis(self.config.poll_interval_ms),
+            failures: failure_receiver,
+            retries: Vec::new(),
+        };
+        let scheduler_shutdown = shutdown.clone();
+        threads.push(std::thread::spawn(move || {
+            scheduler.run(sender, scheduler_shutdown)
+        }));
+
+        Ok(ProverServiceHandle {
+            status,
+            shutdown,
+            threads,
+        })
+    }
+}
+
+/// A handle to a running [`ProverService`].
+pub struct ProverServiceHandle {
+    status: ProofStatusProvider,
+    shutdown: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl ProverServiceHandle {
+    /// Returns a handle which can be used to query the status of proofs, for example over RPC.
+    pub fn status_provider(&self) -> ProofStatusProvider {
+        self.status.clone()
+    }
+
+    /// Stops scheduling new ranges and waits for the workers to finish the jobs already queued.
+    pub fn shutdown(self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        for thread in self.threads {
+            if thread.join().is_err() {
+                error!("A prover service thread panicked");
+            }
+        }
+    }
+}
+
+/// Returns how long to wait after `failed_attempts` consecutive failures, doubling the poll interval
+/// each time up to [`MAX_RETRY_DELAY`].
+fn retry_delay(poll_interval: Duration, failed_attempts: u32) -> Duration {
+    let multiplier = 1u32 << std::cmp::min(failed_attempts, 16);
+    std::cmp::min(poll_interval.saturating_mul(multiplier), MAX_RETRY_DELAY)
+}
+
+struct Scheduler {
+    ledger_db: LedgerDB,
+    status: ProofStatusProvider,
+    slots_per_proof: u64,
+    next_slot_to_schedule: u64,
+    poll_interval: Duration,
+    /// Ranges which the workers failed to prove
+    failures: mpsc::Receiver<ScheduledRange>,
+    /// Failed ranges waiting to be queued again, with the time at which they're due
+    retries: Vec<(Instant, ScheduledRange)>,
+}
+
+impl Scheduler {
+    fn run(mut self, sender: mpsc::Sender<ScheduledRange>, shutdown: Arc<AtomicBool>) {
+        let mut consecutive_errors = 0;
+        while !shutdown.load(Ordering::SeqCst) {
+            let result = self
+                .requeue_failed_ranges(&sender)
+                .and_then(|_| self.schedule_committed_slots(&sender));
+            let delay = match result {
+                Ok(()) => {
+                    consecutive_errors = 0;
+                    self.poll_interval
+                }
+                // Reading the ledger can fail transiently, so keep polling with an increasing delay
+                // rather than silently stopping the service
+                Err(e) => {
+                    consecutive_errors += 1;
+                    let delay = retry_delay(self.poll_interval, consecutive_errors);
+                    error!(
+                        "Failed to schedule proving jobs, retrying in {:?}: {}",
+                        delay, e
+                    );
+                    delay
+                }
+            };
+            std::thread::sleep(delay);
+        }
+        // Dropping the sender lets the workers exit once the queue is drained
+    }
+
+    /// Queues the failed ranges whose retry delay has elapsed.
+    fn requeue_failed_ranges(
+        &mut self,
+        sender: &mpsc::Sender<ScheduledRange>,
+    ) -> Result<(), anyhow::Error> {
+        let now = Instant::now();
+        for failed in self.failures.try_iter() {
+            let delay = retry_delay(self.poll_interval, failed.failed_attempts);
+            let (first_slot, last_slot) = failed.range;
+            warn!(
+                "Retrying the proof of slots {} to {} in {:?}",
+                first_slot, last_slot, delay
+            );
+            self.retries.push((now + delay, failed));
+        }
+
+        let (due, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.retries)
+            .into_iter()
+            .partition(|(due_at, _)| *due_at <= now);
+        self.retries = waiting;
+        for (_, retry) in due {
+            self.status.set_status(retry.range, ProofStatus::Queued);
+            sender.send(retry)?;
+        }
+        Ok(())
+    }
+
+    /// Queues every full range of slots which has been committed to the ledger since the last poll.
+    fn schedule_committed_slots(
+        &mut self,
+        sender: &mpsc::Sender<ScheduledRange>,
+    ) -> Result<(), anyhow::Error> {
+        // A slot is committed atomically, and its number only advances the item numbers of the ledger
+        // once it is written, so every slot below the next slot number is readable.
+        let next_uncommitted_slot = self.ledger_db.get_next_items_numbers().slot_number;
+        while self.next_slot_to_schedule + self.slots_per_proof <= next_uncommitted_slot {
+            let range = (
+                self.next_slot_to_schedule,
+                self.next_slot_to_schedule + self.slots_per_proof - 1,
+            );
+            // After a restart, ranges following a failed one may already have been proven
+            if self
+                .ledger_db
+                .get_proof(SlotNumber(range.0), SlotNumber(range.1))?
+                .is_none()
+            {
+                debug!("Scheduling proof for slots {} to {}", range.0, range.1);
+                self.status.set_status(range, ProofStatus::Queued);
+                sender.send(ScheduledRange {
+                    range,
+                    failed_attempts: 0,
+                })?;
+            }
+            self.next_slot_to_schedule = range.1 + 1;
+        }
+        Ok(())
+    }
+}
+
+struct Worker<P> {
+    ledger_db: LedgerDB,
+    prover: Arc<P>,
+    status: ProofStatusProvider,
+    /// Sends failed ranges back to the scheduler to be retried
+    failures: mpsc::Sender<ScheduledRange>,
+}
+
+impl<P: SlotProver> Worker<P> {
+    fn run(self, receiver: Arc<Mutex<mpsc::Receiver<ScheduledRange>>>) {
+        loop {
+            // The lock is released as soon as a job has been received
+            let next_job = receiver.lock().unwrap().recv();
+            let scheduled = match next_job {
+                Ok(scheduled) => scheduled,
+                // The scheduler has shut down and the queue is empty
+                Err(_) => return,
+            };
+            let range = scheduled.range;
+
+            self.status.set_status(range, ProofStatus::Proving);
+            match self.prove_range(range) {
+                Ok(()) => {
+                    info!("Proved slots {} to {}", range.0, range.1);
+                    self.status.clear_status(range);
+                }
+                Err(e) => {
+                    error!("Failed to prove slots {} to {}: {}", range.0, range.1, e);
+                    self.status
+                        .set_status(range, ProofStatus::Failed(e.to_string()));
+                    // The scheduler is gone during shutdown, in which case the range is
+                    // rescheduled on the next start
+                    let _ = self.failures.send(ScheduledRange {
+                        range,
+                        failed_attempts: scheduled.failed_attempts + 1,
+                    });
+                }
+            }
+        }
+    }
+
+    fn prove_range(&self, (first_slot, last_slot): SlotRange) -> Result<(), anyhow::Error> {
+        let mut slots = Vec::with_capacity((last_slot - first_slot + 1) as usize);
+        for number in first_slot..=last_slot {
+            let slot = self
+                .ledger_db
+                .get_slot(SlotNumber(number))?
+                .ok_or_else(|| anyhow::anyhow!("Slot {} has not been committed", number))?;
+            let witness = self
+                .ledger_db
+                .get_slot_witness(SlotNumber(number))?
+                .ok_or_else(|| anyhow::anyhow!("No witness was stored for slot {}", number))?;
+            slots.push(SlotInput {
+                number,
+                hash: slot.hash,
+                prev_state_root: witness.prev_state_root,
+                witness: witness.decompress()?,
+            });
+        }
+
+        let job = ProvingJob {
+            first_slot,
+            last_slot,
+            slots,
+        };
+        let proof = self.prover.prove(&job)?;
+
+        // Write the proof before clearing the pending status, so that it's never reported as missing
+        self.ledger_db.put_proof(
+            SlotNumber(first_slot),
+            SlotNumber(last_slot),
+            &StoredProof {
+                proof: proof.into(),
+            },
+        )
+    }
+}
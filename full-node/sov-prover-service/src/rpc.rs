@@ -0,0 +1,52 @@
+use jsonrpsee::RpcModule;
+use serde::{Deserialize, Serialize};
+
+use crate::ProofStatusProvider;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofResponse {
+    pub first_slot: u64,
+    pub last_slot: u64,
+    #[serde(with = "hex::serde")]
+    pub proof: Vec<u8>,
+}
+
+/// Registers the following RPC methods
+/// - `prover_getProofStatus`
+///    Example Query: `curl -X POST -H "Content-Type: application/json" -d '{"jsonrpc":"2.0","method":"prover_getProofStatus","params":[1, 4],"id":1}' http://127.0.0.1:12345`
+/// - `prover_getProof`
+///    Example Query: `curl -X POST -H "Content-Type: application/json" -d '{"jsonrpc":"2.0","method":"prover_getProof","params":[1, 4],"id":1}' http://127.0.0.1:12345`
+/// - `prover_getLastProvenSlot`
+///    Example Query: `curl -X POST -H "Content-Type: application/json" -d '{"jsonrpc":"2.0","method":"prover_getLastProvenSlot","params":[],"id":1}' http://127.0.0.1:12345`
+fn register_prover_rpc_methods(
+    rpc: &mut RpcModule<ProofStatusProvider>,
+) -> Result<(), jsonrpsee::core::Error> {
+    rpc.register_method("prover_getProofStatus", move |params, status| {
+        let (first_slot, last_slot): (u64, u64) = params.parse()?;
+        status
+            .get_status(first_slot, last_slot)
+            .map_err(|e| e.into())
+    })?;
+
+    rpc.register_method("prover_getProof", move |params, status| {
+        let (first_slot, last_slot): (u64, u64) = params.parse()?;
+        let proof = status.get_proof(first_slot, last_slot)?;
+        Ok(proof.map(|proof| ProofResponse {
+            first_slot,
+            last_slot,
+            proof,
+        }))
+    })?;
+
+    rpc.register_method("prover_getLastProvenSlot", move |_, status| {
+        status.get_last_proven_slot().map_err(|e| e.into())
+    })?;
+
+    Ok(())
+}
+
+pub fn get_prover_rpc(status: ProofStatusProvider) -> RpcModule<ProofStatusProvider> {
+    let mut rpc = RpcModule::new(status);
+    register_prover_rpc_methods(&mut rpc).expect("Failed to register prover RPC methods");
+    rpc
+}
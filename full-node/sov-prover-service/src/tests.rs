@@ -0,0 +1,282 @@
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use sov_db::ledger_db::{LedgerDB, SlotCommit};
+use sov_db::schema::types::{SlotNumber, StoredProof};
+use sov_rollup_interface::mocks::{MockCodeCommitment, MockProof, MockZkvm, TestBlock};
+use sov_rollup_interface::zk::traits::Zkvm;
+
+use crate::{
+    ProofStatus, ProofStatusProvider, ProverService, ProverServiceConfig, ProvingJob,
+    ScheduledRange, Scheduler, SlotProver,
+};
+
+const MOCK_CODE_COMMITMENT: MockCodeCommitment = MockCodeCommitment([7; 32]);
+
+/// Proves a job by committing to the hashes and witnesses of its slots
+struct MockSlotProver;
+
+impl SlotProver for MockSlotProver {
+    fn prove(&self, job: &ProvingJob) -> Result<Vec<u8>, anyhow::Error> {
+        let mut log = Vec::new();
+        for slot in &job.slots {
+            log.extend_from_slice(&slot.hash);
+            log.extend_from_slice(&slot.witness);
+        }
+        Ok(MockProof {
+            program_id: MOCK_CODE_COMMITMENT,
+            is_valid: true,
+            log: &log,
+        }
+        .encode_to_vec())
+    }
+}
+
+fn commit_slots(ledger_db: &LedgerDB, count: u8, with_witness: bool) {
+    for i in 0..count {
+        let slot_number = SlotNumber(ledger_db.get_next_items_numbers().slot_number);
+        if with_witness {
+            ledger_db
+                .put_slot_witness(slot_number, [i; 32], &vec![i; 4])
+                .unwrap();
+        }
+        let block = TestBlock::new([i + 1; 32], [i; 32]);
+        ledger_db
+            .commit_slot(SlotCommit::<_, (), ()>::new(block))
+            .unwrap();
+    }
+}
+
+fn wait_for_status(
+    status: &ProofStatusProvider,
+    first_slot: u64,
+    last_slot: u64,
+    is_done: impl Fn(&ProofStatus) -> bool,
+) -> ProofStatus {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        if let Some(current) = status.get_status(first_slot, last_slot).unwrap() {
+            if is_done(&current) {
+                return current;
+            }
+        }
+        assert!(
+            Instant::now() < deadline,
+            "Timed out waiting for the proof of slots {first_slot} to {last_slot}"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Creates a scheduler which isn't running, so that it can be driven one poll at a time.
+/// Also returns the sender the workers would use to report failed ranges.
+fn manual_scheduler(ledger_db: &LedgerDB) -> (Scheduler, mpsc::Sender<ScheduledRange>) {
+    let config = test_config();
+    let (failure_sender, failure_receiver) = mpsc::channel();
+    let scheduler = Scheduler {
+        ledger_db: ledger_db.clone(),
+        status: ProofStatusProvider::new(ledger_db.clone(), config.start_slot),
+        slots_per_proof: config.slots_per_proof,
+        next_slot_to_schedule: ledger_db
+            .get_first_unproven_slot(SlotNumber(config.start_slot))
+            .unwrap()
+            .0,
+        poll_interval: Duration::from_millis(config.poll_interval_ms),
+        failures: failure_receiver,
+        retries: Vec::new(),
+    };
+    (scheduler, failure_sender)
+}
+
+fn test_config() -> ProverServiceConfig {
+    ProverServiceConfig {
+        num_workers: 2,
+        slots_per_proof: 2,
+        start_slot: 1,
+        poll_interval_ms: 10,
+    }
+}
+
+#[test]
+fn test_proves_committed_slots() {
+    let ledger_db = LedgerDB::temporary();
+    commit_slots(&ledger_db, 4, true);
+
+    let handle = ProverService::new(ledger_db.clone(), MockSlotProver, test_config())
+        .start()
+        .unwrap();
+    let status = handle.status_provider();
+
+    for (first_slot, last_slot) in [(1, 2), (3, 4)] {
+        let final_status = wait_for_status(&status, first_slot, last_slot, |s| {
+            !matches!(s, ProofStatus::Queued | ProofStatus::Proving)
+        });
+        assert_eq!(final_status, ProofStatus::Completed);
+
+        let proof = status.get_proof(first_slot, last_slot).unwrap().unwrap();
+        let log = MockZkvm::verify(&proof, &MOCK_CODE_COMMITMENT).unwrap();
+        // Each slot contributes a 32 byte hash and its bincode-serialized witness
+        assert_eq!(log.len(), 2 * (32 + 8 + 4));
+    }
+    assert_eq!(status.get_last_proven_slot().unwrap(), Some(4));
+
+    // Slots committed while the service is running are picked up as well
+    commit_slots(&ledger_db, 2, true);
+    let final_status = wait_for_status(&status, 5, 6, |s| *s == ProofStatus::Completed);
+    assert_eq!(final_status, ProofStatus::Completed);
+
+    handle.shutdown();
+}
+
+#[test]
+fn test_incomplete_range_is_not_scheduled() {
+    let ledger_db = LedgerDB::temporary();
+    commit_slots(&ledger_db, 3, true);
+    let (mut scheduler, _failures) = manual_scheduler(&ledger_db);
+    let (sender, receiver) = mpsc::channel();
+
+    scheduler.schedule_committed_slots(&sender).unwrap();
+    assert_eq!(
+        receiver.try_iter().map(|job| job.range).collect::<Vec<_>>(),
+        vec![(1, 2)]
+    );
+    assert_eq!(scheduler.status.get_status(3, 4).unwrap(), None);
+
+    // The range is scheduled once its last slot is committed
+    commit_slots(&ledger_db, 1, true);
+    scheduler.schedule_committed_slots(&sender).unwrap();
+    assert_eq!(
+        receiver.try_iter().map(|job| job.range).collect::<Vec<_>>(),
+        vec![(3, 4)]
+    );
+    assert_eq!(
+        scheduler.status.get_status(3, 4).unwrap(),
+        Some(ProofStatus::Queued)
+    );
+}
+
+#[test]
+fn test_missing_witness_fails_job() {
+    let ledger_db = LedgerDB::temporary();
+    commit_slots(&ledger_db, 2, false);
+
+    let handle = ProverService::new(ledger_db.clone(), MockSlotProver, test_config())
+        .start()
+        .unwrap();
+    let status = handle.status_provider();
+
+    let final_status = wait_for_status(&status, 1, 2, |s| matches!(s, ProofStatus::Failed(_)));
+    assert_eq!(
+        final_status,
+        ProofStatus::Failed("No witness was stored for slot 1".to_string())
+    );
+    assert_eq!(status.get_proof(1, 2).unwrap(), None);
+    assert_eq!(status.get_last_proven_slot().unwrap(), None);
+
+    // The failed range is retried, and succeeds once the witnesses are available
+    for i in 0..2 {
+        ledger_db
+            .put_slot_witness(SlotNumber(i as u64 + 1), [i; 32], &vec![i; 4])
+            .unwrap();
+    }
+    wait_for_status(&status, 1, 2, |s| *s == ProofStatus::Completed);
+    assert_eq!(status.get_last_proven_slot().unwrap(), Some(2));
+
+    handle.shutdown();
+}
+
+#[test]
+fn test_failed_ranges_are_retried_with_backoff() {
+    let ledger_db = LedgerDB::temporary();
+    commit_slots(&ledger_db, 2, true);
+    let (mut scheduler, failures) = manual_scheduler(&ledger_db);
+    let (sender, receiver) = mpsc::channel();
+
+    scheduler.schedule_committed_slots(&sender).unwrap();
+    let job = receiver.try_recv().unwrap();
+    assert_eq!(job.failed_attempts, 0);
+
+    failures
+        .send(ScheduledRange {
+            range: job.range,
+            failed_attempts: 1,
+        })
+        .unwrap();
+    let before_requeue = Instant::now();
+    scheduler.requeue_failed_ranges(&sender).unwrap();
+    // The retry waits for twice the poll interval
+    assert!(receiver.try_recv().is_err());
+    let (due_at, _) = scheduler.retries[0];
+    assert!(due_at >= before_requeue + Duration::from_millis(2 * test_config().poll_interval_ms));
+
+    // Pretend the delay has elapsed
+    scheduler.retries[0].0 = Instant::now();
+    scheduler.requeue_failed_ranges(&sender).unwrap();
+    let retry = receiver.try_recv().unwrap();
+    assert_eq!(retry.range, (1, 2));
+    assert_eq!(retry.failed_attempts, 1);
+    assert_eq!(
+        scheduler.status.get_status(1, 2).unwrap(),
+        Some(ProofStatus::Queued)
+    );
+}
+
+#[test]
+fn test_resumes_from_first_gap() {
+    let ledger_db = LedgerDB::temporary();
+    commit_slots(&ledger_db, 4, true);
+    // The proof of slots 1 and 2 failed before a restart, but slots 3 and 4 were proven
+    let existing_proof = StoredProof {
+        proof: vec![42].into(),
+    };
+    ledger_db
+        .put_proof(SlotNumber(3), SlotNumber(4), &existing_proof)
+        .unwrap();
+
+    let (mut scheduler, _failures) = manual_scheduler(&ledger_db);
+    assert_eq!(scheduler.status.get_last_proven_slot().unwrap(), None);
+    let (sender, receiver) = mpsc::channel();
+    scheduler.schedule_committed_slots(&sender).unwrap();
+    // Only the gap is proven again
+    assert_eq!(
+        receiver.try_iter().map(|job| job.range).collect::<Vec<_>>(),
+        vec![(1, 2)]
+    );
+
+    let handle = ProverService::new(ledger_db, MockSlotProver, test_config())
+        .start()
+        .unwrap();
+    let status = handle.status_provider();
+    wait_for_status(&status, 1, 2, |s| *s == ProofStatus::Completed);
+    assert_eq!(status.get_last_proven_slot().unwrap(), Some(4));
+    assert_eq!(status.get_proof(3, 4).unwrap(), Some(vec![42]));
+
+    handle.shutdown();
+}
+
+#[test]
+fn test_resumes_after_last_proof() {
+    let path = sov_schema_db::temppath::TempPath::new();
+    {
+        let ledger_db = LedgerDB::with_path(&path).unwrap();
+        commit_slots(&ledger_db, 2, true);
+        let handle = ProverService::new(ledger_db, MockSlotProver, test_config())
+            .start()
+            .unwrap();
+        wait_for_status(&handle.status_provider(), 1, 2, |s| {
+            *s == ProofStatus::Completed
+        });
+        handle.shutdown();
+    }
+
+    let ledger_db = LedgerDB::with_path(&path).unwrap();
+    commit_slots(&ledger_db, 2, true);
+    let handle = ProverService::new(ledger_db, MockSlotProver, test_config())
+        .start()
+        .unwrap();
+    let status = handle.status_provider();
+    wait_for_status(&status, 3, 4, |s| *s == ProofStatus::Completed);
+    assert_eq!(status.get_last_proven_slot().unwrap(), Some(4));
+
+    handle.shutdown();
+}
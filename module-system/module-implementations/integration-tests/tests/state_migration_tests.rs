@@ -0,0 +1,269 @@
+use std::path::Path;
+
+use sov_modules_api::default_context::{DefaultContext, ZkDefaultContext};
+use sov_modules_api::hooks::{ApplyBlobHooks, TxHooks};
+use sov_modules_api::migration::{
+    init_state_versions, run_migrations, MigratableModule, MigrationFn, RuntimeMigrations,
+    ScheduledMigration, StateMigrations,
+};
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{Context, Module, ModuleInfo, Spec};
+use sov_modules_macros::{DispatchCall, Genesis, MessageCodec, ModuleInfo};
+use sov_modules_stf_template::{AppTemplate, SequencerOutcome};
+use sov_rollup_interface::mocks::MockZkvm;
+use sov_rollup_interface::stf::StateTransitionFunction;
+use sov_schema_db::temppath::TempPath;
+use sov_state::{DefaultStorageSpec, ProverStorage, StateValue, Storage, WorkingSet, ZkStorage};
+
+const ACTIVATION_SLOT: u64 = 2;
+
+pub mod versioned_module {
+    use super::*;
+
+    #[derive(ModuleInfo)]
+    pub(crate) struct VersionedModule<C: Context> {
+        #[address]
+        pub address: C::Address,
+
+        /// The layout of version 0
+        #[state]
+        pub(crate) value: StateValue<u32>,
+
+        /// The layout of version 1, which adds a label to the value
+        #[state]
+        pub(crate) labeled_value: StateValue<(u32, String)>,
+    }
+
+    impl<C: Context> StateMigrations for VersionedModule<C> {
+        const STATE_VERSION: u32 = 1;
+
+        fn migrations() -> Vec<MigrationFn<Self>> {
+            vec![Self::add_label]
+        }
+    }
+
+    impl<C: Context> Module for VersionedModule<C> {
+        type Context = C;
+        type Config = ();
+
+        fn genesis(
+            &self,
+            _config: &Self::Config,
+            working_set: &mut WorkingSet<C::Storage>,
+        ) -> Result<(), sov_modules_api::Error> {
+            self.value.set(&7, working_set);
+            Ok(())
+        }
+    }
+
+    impl<C: Context> VersionedModule<C> {
+        fn add_label(&self, working_set: &mut WorkingSet<C::Storage>) -> anyhow::Result<()> {
+            let value = self.value.remove_or_err(working_set)?;
+            self.labeled_value
+                .set(&(value, "migrated".to_owned()), working_set);
+            Ok(())
+        }
+    }
+}
+
+#[derive(Genesis, DispatchCall, MessageCodec)]
+#[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+struct TestRuntime<C: Context> {
+    module: versioned_module::VersionedModule<C>,
+}
+
+impl<C: Context> TxHooks for TestRuntime<C> {
+    type Context = C;
+
+    fn pre_dispatch_tx_hook(
+        &self,
+        _tx: Transaction<Self::Context>,
+        _working_set: &mut WorkingSet<<Self::Context as Spec>::Storage>,
+    ) -> anyhow::Result<<Self::Context as Spec>::Address> {
+        anyhow::bail!("The test runtime doesn't accept transactions")
+    }
+
+    fn post_dispatch_tx_hook(
+        &self,
+        _tx: &Transaction<Self::Context>,
+        _working_set: &mut WorkingSet<<Self::Context as Spec>::Storage>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+impl<C: Context> ApplyBlobHooks for TestRuntime<C> {
+    type Context = C;
+    type BlobResult = SequencerOutcome;
+
+    fn begin_blob_hook(
+        &self,
+        _sequencer: &[u8],
+        _raw_blob: &[u8],
+        _working_set: &mut WorkingSet<<Self::Context as Spec>::Storage>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn end_blob_hook(
+        &self,
+        _result: Self::BlobResult,
+        _working_set: &mut WorkingSet<<Self::Context as Spec>::Storage>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+impl<C: Context> RuntimeMigrations for TestRuntime<C> {
+    type Context = C;
+
+    fn scheduled_migrations() -> Vec<ScheduledMigration<Self>> {
+        vec![ScheduledMigration {
+            activation_slot: ACTIVATION_SLOT,
+            version: 1,
+            module: |runtime| &runtime.module,
+        }]
+    }
+}
+
+#[test]
+fn state_migration_test() {
+    let native_storage = ProverStorage::temporary();
+    let working_set = &mut WorkingSet::new(native_storage.clone());
+
+    // Test the `native` execution.
+    {
+        set_initial_state::<DefaultContext>(working_set);
+        execute_migrations::<DefaultContext>(working_set);
+    }
+    let (log, witness) = working_set.freeze();
+    native_storage
+        .validate_and_commit(log, &witness)
+        .expect("State update is valid");
+
+    // Test the `zk` execution.
+    {
+        let zk_storage = ZkStorage::new([0u8; 32]);
+        let working_set = &mut WorkingSet::with_witness(zk_storage, witness);
+        set_initial_state::<ZkDefaultContext>(working_set);
+        execute_migrations::<ZkDefaultContext>(working_set);
+    }
+}
+
+#[test]
+fn migrations_are_skipped_after_genesis_test() {
+    let working_set = &mut WorkingSet::new(ProverStorage::temporary());
+    let runtime = TestRuntime::<DefaultContext> {
+        module: ModuleInfo::new(),
+    };
+
+    // Genesis initializes the state with the latest layout.
+    runtime
+        .module
+        .labeled_value
+        .set(&(7, "genesis".to_owned()), working_set);
+    init_state_versions(&runtime, working_set);
+    assert_eq!(runtime.module.state_version(working_set), 1);
+
+    run_migrations(&runtime, ACTIVATION_SLOT, working_set).unwrap();
+    assert_eq!(
+        runtime.module.labeled_value.get(working_set),
+        Some((7, "genesis".to_owned()))
+    );
+}
+
+#[test]
+fn migrations_activate_at_the_same_slot_after_a_restart_test() {
+    const SLOTS: u64 = ACTIVATION_SLOT + 2;
+
+    let uninterrupted_path = TempPath::new();
+    launch_rollup(&uninterrupted_path);
+    let uninterrupted_slots = execute_slots(&uninterrupted_path, SLOTS);
+
+    // The node is restarted right before the activation slot.
+    let restarted_path = TempPath::new();
+    launch_rollup(&restarted_path);
+    let mut restarted_slots = execute_slots(&restarted_path, ACTIVATION_SLOT);
+    // The restarted node resumes from the root of the last slot it executed.
+    let storage = ProverStorage::<DefaultStorageSpec>::with_path(&restarted_path).unwrap();
+    assert_eq!(
+        storage.get_state_root().unwrap(),
+        restarted_slots.last().unwrap().0
+    );
+    drop(storage);
+    restarted_slots.extend(execute_slots(&restarted_path, SLOTS - ACTIVATION_SLOT));
+
+    assert_eq!(restarted_slots, uninterrupted_slots);
+    for (slot_height, (_, state_version)) in restarted_slots.iter().enumerate() {
+        let expected_version = u32::from(slot_height as u64 >= ACTIVATION_SLOT);
+        assert_eq!(*state_version, expected_version);
+    }
+}
+
+/// Commits the genesis of a rollup which launched with version 0 of the state of the module, before the
+/// migration was scheduled.
+fn launch_rollup(path: &Path) {
+    let storage = ProverStorage::with_path(path).unwrap();
+    let working_set = &mut WorkingSet::new(storage.clone());
+    let runtime = TestRuntime::<DefaultContext> {
+        module: ModuleInfo::new(),
+    };
+    runtime.module.genesis(&(), working_set).unwrap();
+
+    let (log, witness) = working_set.freeze();
+    storage
+        .validate_and_commit(log, &witness)
+        .expect("State update is valid");
+}
+
+/// Starts a node from the state stored at `path` and executes `count` empty slots. Returns the state root
+/// and the state version of the module after each slot.
+fn execute_slots(path: &Path, count: u64) -> Vec<([u8; 32], u32)> {
+    let storage = ProverStorage::with_path(path).unwrap();
+    let runtime = TestRuntime::<DefaultContext> {
+        module: ModuleInfo::new(),
+    };
+    let mut app =
+        AppTemplate::<DefaultContext, TestRuntime<DefaultContext>, MockZkvm>::new(storage, runtime);
+
+    let mut slots: Vec<([u8; 32], u32)> = Vec::new();
+    for _ in 0..count {
+        app.begin_slot(Default::default());
+        let (state_root, _) = app.end_slot();
+        let working_set = &mut WorkingSet::new(app.current_storage.clone());
+        slots.push((state_root.0, app.runtime.module.state_version(working_set)));
+    }
+    slots
+}
+
+fn set_initial_state<C: Context>(working_set: &mut WorkingSet<C::Storage>) {
+    let module = versioned_module::VersionedModule::<C>::new();
+    module.value.set(&7, working_set);
+}
+
+fn execute_migrations<C: Context>(working_set: &mut WorkingSet<C::Storage>) {
+    let runtime = TestRuntime::<C> {
+        module: ModuleInfo::new(),
+    };
+
+    // Nothing happens before the activation slot.
+    run_migrations(&runtime, ACTIVATION_SLOT - 1, working_set).unwrap();
+    assert_eq!(runtime.module.state_version(working_set), 0);
+    assert_eq!(runtime.module.value.get(working_set), Some(7));
+    assert_eq!(runtime.module.labeled_value.get(working_set), None);
+
+    run_migrations(&runtime, ACTIVATION_SLOT, working_set).unwrap();
+    assert_eq!(runtime.module.state_version(working_set), 1);
+    assert_eq!(runtime.module.value.get(working_set), None);
+    assert_eq!(
+        runtime.module.labeled_value.get(working_set),
+        Some((7, "migrated".to_owned()))
+    );
+
+    // A migration runs at most once.
+    run_migrations(&runtime, ACTIVATION_SLOT, working_set).unwrap();
+    assert_eq!(
+        runtime.module.labeled_value.get(working_set),
+        Some((7, "migrated".to_owned()))
+    );
+}
@@ -1,7 +1,7 @@
-use crate::{Amount, Bank, Coins, Token};
+use crate::{Amount, Bank, Coins, Token, TokenMetadata};
 use anyhow::{bail, Result};
 
-use sov_modules_api::CallResponse;
+use sov_modules_api::{CallResponse, ModuleInfo};
 use sov_state::WorkingSet;
 
 /// This enumeration represents the available call messages for interacting with the sov-bank module.
@@ -12,18 +12,27 @@ use sov_state::WorkingSet;
 )]
 #[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
 pub enum CallMessage<C: sov_modules_api::Context> {
-    /// Creates a new token with the specified name and initial balance.
+    /// Creates a new token with the specified name, metadata and initial balance.
+    /// Returns the address of the new token.
     CreateToken {
         /// Random value use to create a unique token address.
         salt: u64,
         /// The name of the new token.
         token_name: String,
+        /// The ticker symbol of the new token.
+        symbol: String,
+        /// The number of decimals used to display amounts of the new token.
+        decimals: u8,
+        /// Optional URI with additional information about the new token.
+        uri: Option<String>,
         /// The initial balance of the new token.
         initial_balance: Amount,
         /// The address of the account that the new tokens are minted to.
         minter_address: C::Address,
         /// Authorized minter list.
         authorized_minters: Vec<C::Address>,
+        /// The maximum total supply of the new token, `None` for an uncapped supply.
+        supply_cap: Option<Amount>,
     },
 
     /// Transfers a specified amount of tokens to the specified address.
@@ -53,25 +62,77 @@ pub enum CallMessage<C: sov_modules_api::Context> {
         /// Address of the token to be frozen
         token_address: C::Address,
     },
+
+    /// Allows `spender` to transfer up to `coins` of the sender with `TransferFrom`. Replaces the
+    /// previous allowance of `spender`.
+    Approve {
+        /// The address allowed to spend the tokens.
+        spender: C::Address,
+        /// The token and the maximum amount `spender` can transfer.
+        coins: Coins<C>,
+    },
+
+    /// Transfers tokens of `from` to `to`, spending the allowance given by `from` to the sender.
+    TransferFrom {
+        /// The address from which the tokens will be transferred.
+        from: C::Address,
+        /// The address to which the tokens will be transferred.
+        to: C::Address,
+        /// The amount of tokens to transfer.
+        coins: Coins<C>,
+    },
+
+    /// Adds an authorized minter to a token. Only the admin of the token, initially its creator, can
+    /// add minters. Frozen tokens can't get new minters.
+    AddMinter {
+        /// Address of the token.
+        token_address: C::Address,
+        /// The address allowed to mint the token.
+        minter: C::Address,
+    },
+
+    /// Removes an authorized minter from a token. Only the admin of the token can remove minters.
+    /// Removing the last minter freezes the token.
+    RemoveMinter {
+        /// Address of the token.
+        token_address: C::Address,
+        /// The address no longer allowed to mint the token.
+        minter: C::Address,
+    },
+
+    /// Transfers the admin role of a token to another account.
+    TransferTokenAdmin {
+        /// Address of the token.
+        token_address: C::Address,
+        /// The new admin of the token.
+        new_admin: C::Address,
+    },
 }
 
 impl<C: sov_modules_api::Context> Bank<C> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn create_token(
         &self,
         token_name: String,
+        metadata: TokenMetadata,
         salt: u64,
         initial_balance: Amount,
         minter_address: C::Address,
         authorized_minters: Vec<C::Address>,
+        supply_cap: Option<Amount>,
         context: &C,
         working_set: &mut WorkingSet<C::Storage>,
     ) -> Result<CallResponse> {
-        let (token_address, token) = Token::<C>::create(
+        let token_address =
+            super::create_token_address::<C>(&token_name, context.sender().as_ref(), salt);
+        let token = Token::<C>::create(
+            &token_address,
             &token_name,
+            metadata,
             &[(minter_address, initial_balance)],
             authorized_minters,
-            context.sender().as_ref(),
-            salt,
+            context.sender().clone(),
+            supply_cap,
             self.tokens.prefix(),
             working_set,
         )?;
@@ -84,8 +145,21 @@ impl<C: sov_modules_api::Context> Bank<C> {
             );
         }
 
-        self.tokens.set(&token_address, &token, working_set);
-        Ok(CallResponse::default())
+        self.insert_token(&token_address, &token, working_set);
+        Ok(CallResponse::with_data(&token_address))
+    }
+
+    /// Stores a new token and appends its address to the list of tokens.
+    pub(crate) fn insert_token(
+        &self,
+        token_address: &C::Address,
+        token: &Token<C>,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) {
+        let index = self.token_count.get(working_set).unwrap_or_default();
+        self.token_addresses.set(&index, token_address, working_set);
+        self.token_count.set(&(index + 1), working_set);
+        self.tokens.set(token_address, token, working_set);
     }
 
     pub fn transfer(
@@ -95,7 +169,21 @@ impl<C: sov_modules_api::Context> Bank<C> {
         context: &C,
         working_set: &mut WorkingSet<C::Storage>,
     ) -> Result<CallResponse> {
-        self.transfer_from(context.sender(), &to, coins, working_set)
+        let token = self.tokens.get_or_err(&coins.token_address, working_set)?;
+        token.transfer(context.sender(), &to, coins.amount, working_set)?;
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn approve(
+        &self,
+        spender: C::Address,
+        coins: Coins<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> Result<CallResponse> {
+        let token = self.tokens.get_or_err(&coins.token_address, working_set)?;
+        token.approve(context.sender(), &spender, coins.amount, working_set);
+        Ok(CallResponse::default())
     }
 
     pub(crate) fn burn(
@@ -106,7 +194,6 @@ impl<C: sov_modules_api::Context> Bank<C> {
     ) -> Result<CallResponse> {
         let mut token = self.tokens.get_or_err(&coins.token_address, working_set)?;
         token.burn(context.sender(), coins.amount, working_set)?;
-        token.total_supply -= coins.amount;
         self.tokens.set(&coins.token_address, &token, working_set);
 
         Ok(CallResponse::default())
@@ -138,18 +225,102 @@ impl<C: sov_modules_api::Context> Bank<C> {
 
         Ok(CallResponse::default())
     }
+
+    pub(crate) fn add_minter(
+        &self,
+        token_address: C::Address,
+        minter: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> Result<CallResponse> {
+        let mut token = self.tokens.get_or_err(&token_address, working_set)?;
+        token.add_minter(context.sender(), minter.clone())?;
+        self.tokens.set(&token_address, &token, working_set);
+
+        working_set.add_event(
+            "minter_added",
+            &format!("token: {token_address}. minter: {minter}"),
+        );
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn remove_minter(
+        &self,
+        token_address: C::Address,
+        minter: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> Result<CallResponse> {
+        let mut token = self.tokens.get_or_err(&token_address, working_set)?;
+        token.remove_minter(context.sender(), &minter)?;
+        self.tokens.set(&token_address, &token, working_set);
+
+        working_set.add_event(
+            "minter_removed",
+            &format!("token: {token_address}. minter: {minter}"),
+        );
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn transfer_token_admin(
+        &self,
+        token_address: C::Address,
+        new_admin: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> Result<CallResponse> {
+        let mut token = self.tokens.get_or_err(&token_address, working_set)?;
+        token.transfer_admin(context.sender(), new_admin.clone())?;
+        self.tokens.set(&token_address, &token, working_set);
+
+        working_set.add_event(
+            "token_admin_transferred",
+            &format!(
+                "token: {token_address}. previous_admin: {}. new_admin: {new_admin}",
+                context.sender()
+            ),
+        );
+        Ok(CallResponse::default())
+    }
 }
 
 impl<C: sov_modules_api::Context> Bank<C> {
+    /// Transfers `coins` from `from` on behalf of the `caller` module. Unless `from` is the address of the
+    /// caller or the caller is one of the `authorized_modules` of the genesis config, the transfer spends the
+    /// allowance given by `from` to the caller. The sender is taken from the caller itself, so that a module
+    /// can't transfer funds on behalf of another account.
     pub fn transfer_from(
         &self,
         from: &C::Address,
         to: &C::Address,
         coins: Coins<C>,
+        caller: &impl ModuleInfo<Context = C>,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> Result<CallResponse> {
+        self.transfer_from_sender(from, to, coins, caller.address(), working_set)
+    }
+
+    /// Transfers `coins` from `from` on behalf of `sender`, the sender of a `CallMessage::TransferFrom`.
+    pub(crate) fn transfer_from_sender(
+        &self,
+        from: &C::Address,
+        to: &C::Address,
+        coins: Coins<C>,
+        sender: &C::Address,
         working_set: &mut WorkingSet<C::Storage>,
     ) -> Result<CallResponse> {
         let token = self.tokens.get_or_err(&coins.token_address, working_set)?;
+        // Both checks run before anything is written, so that a failed transfer keeps the allowance
+        let new_allowance =
+            if sender != from && self.authorized_modules.get(sender, working_set).is_none() {
+                Some(token.check_allowance(from, sender, coins.amount, working_set)?)
+            } else {
+                None
+            };
         token.transfer(from, to, coins.amount, working_set)?;
+        if let Some(new_allowance) = new_allowance {
+            token.approve(from, sender, new_allowance, working_set);
+        }
         Ok(CallResponse::default())
     }
 }
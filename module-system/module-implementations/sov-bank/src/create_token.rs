@@ -1,5 +1,7 @@
 use sov_modules_api::Hasher;
 
+use crate::Bank;
+
 /// Derives token address from `token_name`, `sender` and `salt`.
 pub fn create_token_address<C: sov_modules_api::Context>(
     token_name: &str,
@@ -14,3 +16,22 @@ pub fn create_token_address<C: sov_modules_api::Context>(
     let hash = hasher.finalize();
     C::Address::from(hash)
 }
+
+/// Derives the address of the native token from the address of the sov-bank module, so that every node
+/// of a runtime knows it without reading the state.
+pub fn native_token_address<C: sov_modules_api::Context>(bank_address: &C::Address) -> C::Address {
+    let mut hasher = C::Hasher::new();
+    hasher.update(bank_address.as_ref());
+    hasher.update(b"native_token");
+
+    let hash = hasher.finalize();
+    C::Address::from(hash)
+}
+
+impl<C: sov_modules_api::Context> Bank<C> {
+    /// The address of the native token of the rollup, used for gas and for the bonds of sequencers and
+    /// provers. The token exists if the genesis config of the bank sets `native_token`.
+    pub fn native_token(&self) -> C::Address {
+        native_token_address::<C>(&self.address)
+    }
+}
@@ -0,0 +1,38 @@
+use sov_modules_macros::ModuleError;
+
+use crate::Amount;
+
+/// The errors of the sov-bank module which clients can match on. Their codes are part of the
+/// module's interface and must not change.
+#[derive(Debug, PartialEq, Eq, thiserror::Error, ModuleError)]
+pub enum BankError {
+    /// The balance of the account is lower than the amount being transferred or burned.
+    #[error("Insufficient funds for {0}")]
+    #[code = 1]
+    InsufficientFunds(String),
+
+    /// The token was already frozen.
+    #[error("Token is already frozen")]
+    #[code = 2]
+    TokenAlreadyFrozen,
+
+    /// The caller of `transfer_from` neither owns the funds nor is an authorized module.
+    #[error("{0} is not authorized to transfer funds of other accounts")]
+    #[code = 3]
+    UnauthorizedTransfer(String),
+
+    /// The allowance of the spender is lower than the amount being transferred.
+    #[error("Insufficient allowance for {0}")]
+    #[code = 4]
+    InsufficientAllowance(String),
+
+    /// The sender of a role change isn't the admin of the token.
+    #[error("{0} is not the admin of the token")]
+    #[code = 5]
+    NotTokenAdmin(String),
+
+    /// Creating or minting the tokens would raise the total supply above the supply cap of the token.
+    #[error("The total supply would exceed the supply cap of {0}")]
+    #[code = 6]
+    SupplyCapExceeded(Amount),
+}
@@ -1,4 +1,4 @@
-use crate::{token::Token, Bank};
+use crate::{token::Token, Bank, TokenConfig, TokenMetadata};
 use anyhow::{bail, Result};
 use sov_state::WorkingSet;
 
@@ -11,24 +11,51 @@ impl<C: sov_modules_api::Context> Bank<C> {
         config: &<Self as sov_modules_api::Module>::Config,
         working_set: &mut WorkingSet<C::Storage>,
     ) -> Result<()> {
-        let parent_prefix = self.tokens.prefix();
+        if let Some(native_token_config) = &config.native_token {
+            self.create_genesis_token(self.native_token(), native_token_config, working_set)?;
+        }
+
         for token_config in config.tokens.iter() {
-            let (token_address, token) = Token::<C>::create(
-                &token_config.token_name,
-                &token_config.address_and_balances,
-                vec![C::Address::try_from(&DEPLOYER)?],
-                &DEPLOYER,
-                SALT,
-                parent_prefix,
-                working_set,
-            )?;
+            let token_address =
+                super::create_token_address::<C>(&token_config.token_name, &DEPLOYER, SALT);
+            self.create_genesis_token(token_address, token_config, working_set)?;
+        }
 
-            if self.tokens.get(&token_address, working_set).is_some() {
-                bail!("Token address already exists");
-            }
+        for module_address in config.authorized_modules.iter() {
+            self.authorized_modules
+                .set(module_address, &(), working_set);
+        }
+        Ok(())
+    }
 
-            self.tokens.set(&token_address, &token, working_set);
+    fn create_genesis_token(
+        &self,
+        token_address: C::Address,
+        token_config: &TokenConfig<C>,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> Result<()> {
+        let deployer = C::Address::try_from(&DEPLOYER)?;
+        let token = Token::<C>::create(
+            &token_address,
+            &token_config.token_name,
+            TokenMetadata {
+                symbol: token_config.symbol.clone(),
+                decimals: token_config.decimals,
+                uri: token_config.uri.clone(),
+            },
+            &token_config.address_and_balances,
+            vec![deployer.clone()],
+            deployer,
+            token_config.supply_cap,
+            self.tokens.prefix(),
+            working_set,
+        )?;
+
+        if self.tokens.get(&token_address, working_set).is_some() {
+            bail!("Token address already exists");
         }
+
+        self.insert_token(&token_address, &token, working_set);
         Ok(())
     }
 }
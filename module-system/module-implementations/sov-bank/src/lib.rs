@@ -1,13 +1,15 @@
 pub mod call;
 mod create_token;
+mod error;
 pub mod genesis;
 #[cfg(feature = "native")]
 pub mod query;
 mod token;
 
-pub use create_token::create_token_address;
+pub use create_token::{create_token_address, native_token_address};
+pub use error::BankError;
 use token::Token;
-pub use token::{Amount, Coins};
+pub use token::{format_amount, Amount, Coins, TokenMetadata};
 
 use sov_modules_api::Error;
 use sov_modules_macros::ModuleInfo;
@@ -15,18 +17,30 @@ use sov_state::WorkingSet;
 
 pub struct TokenConfig<C: sov_modules_api::Context> {
     pub token_name: String,
-    pub address_and_balances: Vec<(C::Address, u64)>,
+    pub symbol: String,
+    pub decimals: u8,
+    pub uri: Option<String>,
+    pub supply_cap: Option<Amount>,
+    /// The initial balances. An address listed several times receives the sum of its balances.
+    pub address_and_balances: Vec<(C::Address, Amount)>,
 }
 
 /// Initial configuration for sov-bank module.
 pub struct BankConfig<C: sov_modules_api::Context> {
+    /// The native token of the rollup, created at the address returned by `Bank::native_token`.
+    pub native_token: Option<TokenConfig<C>>,
     pub tokens: Vec<TokenConfig<C>>,
+    /// Addresses of the modules allowed to transfer funds of any account with `Bank::transfer_from`.
+    pub authorized_modules: Vec<C::Address>,
 }
 
 /// The sov-bank module manages user balances. It provides functionality for:
-/// - Token creation.
+/// - Token creation, with a symbol, decimals and an optional URI.
+/// - Token enumeration.
 /// - Token transfers.
+/// - Token allowances, which let another account transfer tokens on behalf of their owner.
 /// - Token burn.
+/// - Minter and admin role management, with an optional supply cap.
 #[derive(ModuleInfo, Clone)]
 pub struct Bank<C: sov_modules_api::Context> {
     /// The address of the sov-bank module.
@@ -36,6 +50,18 @@ pub struct Bank<C: sov_modules_api::Context> {
     /// A mapping of addresses to tokens in the sov-bank.
     #[state]
     pub(crate) tokens: sov_state::StateMap<C::Address, Token<C>>,
+
+    /// The addresses of the tokens, in creation order, used to enumerate them.
+    #[state]
+    pub(crate) token_addresses: sov_state::StateMap<u64, C::Address>,
+
+    /// The number of tokens in the sov-bank.
+    #[state]
+    pub(crate) token_count: sov_state::StateValue<u64>,
+
+    /// The modules allowed to transfer funds of any account.
+    #[state]
+    pub(crate) authorized_modules: sov_state::StateMap<C::Address, ()>,
 }
 
 impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
@@ -59,37 +85,71 @@ impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
         context: &Self::Context,
         working_set: &mut WorkingSet<C::Storage>,
     ) -> Result<sov_modules_api::CallResponse, Error> {
-        match msg {
+        let response = match msg {
             call::CallMessage::CreateToken {
                 salt,
                 token_name,
+                symbol,
+                decimals,
+                uri,
                 initial_balance,
                 minter_address,
                 authorized_minters,
-            } => Ok(self.create_token(
+                supply_cap,
+            } => self.create_token(
                 token_name,
+                TokenMetadata {
+                    symbol,
+                    decimals,
+                    uri,
+                },
                 salt,
                 initial_balance,
                 minter_address,
                 authorized_minters,
+                supply_cap,
                 context,
                 working_set,
-            )?),
+            ),
 
             call::CallMessage::Transfer { to, coins } => {
-                Ok(self.transfer(to, coins, context, working_set)?)
+                self.transfer(to, coins, context, working_set)
             }
 
-            call::CallMessage::Burn { coins } => Ok(self.burn(coins, context, working_set)?),
+            call::CallMessage::Burn { coins } => self.burn(coins, context, working_set),
 
             call::CallMessage::Mint {
                 coins,
                 minter_address,
-            } => Ok(self.mint(coins, minter_address, context, working_set)?),
+            } => self.mint(coins, minter_address, context, working_set),
 
             call::CallMessage::Freeze { token_address } => {
-                Ok(self.freeze(token_address, context, working_set)?)
+                self.freeze(token_address, context, working_set)
             }
-        }
+
+            call::CallMessage::Approve { spender, coins } => {
+                self.approve(spender, coins, context, working_set)
+            }
+
+            call::CallMessage::TransferFrom { from, to, coins } => {
+                self.transfer_from_sender(&from, &to, coins, context.sender(), working_set)
+            }
+
+            call::CallMessage::AddMinter {
+                token_address,
+                minter,
+            } => self.add_minter(token_address, minter, context, working_set),
+
+            call::CallMessage::RemoveMinter {
+                token_address,
+                minter,
+            } => self.remove_minter(token_address, minter, context, working_set),
+
+            call::CallMessage::TransferTokenAdmin {
+                token_address,
+                new_admin,
+            } => self.transfer_token_admin(token_address, new_admin, context, working_set),
+        };
+        response.map_err(Error::from_anyhow::<BankError>)
     }
 }
@@ -1,4 +1,5 @@
-use crate::{Amount, Bank};
+use crate::{Amount, Bank, Token};
+use sov_modules_api::AddressBech32;
 use sov_modules_macros::rpc_gen;
 use sov_state::WorkingSet;
 
@@ -12,6 +13,39 @@ pub struct TotalSupplyResponse {
     pub amount: Option<Amount>,
 }
 
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct AllowanceResponse {
+    pub amount: Option<Amount>,
+}
+
+/// The maximum number of tokens returned by one `listTokens` query.
+pub const LIST_TOKENS_PAGE_SIZE: u64 = 100;
+
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TokenInfo {
+    pub address: AddressBech32,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub uri: Option<String>,
+    pub total_supply: Amount,
+    pub supply_cap: Option<Amount>,
+    pub admin: AddressBech32,
+    pub authorized_minters: Vec<AddressBech32>,
+}
+
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TokenInfoResponse {
+    pub token_info: Option<TokenInfo>,
+}
+
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ListTokensResponse {
+    pub tokens: Vec<TokenInfo>,
+    /// The cursor of the next page, `None` if there are no more tokens.
+    pub next_cursor: Option<u64>,
+}
+
 #[rpc_gen(client, server, namespace = "bank")]
 impl<C: sov_modules_api::Context> Bank<C> {
     #[rpc_method(name = "balanceOf")]
@@ -39,6 +73,91 @@ impl<C: sov_modules_api::Context> Bank<C> {
                 .map(|token| token.total_supply),
         }
     }
+
+    #[rpc_method(name = "allowance")]
+    pub fn allowance(
+        &self,
+        owner: C::Address,
+        spender: C::Address,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> AllowanceResponse {
+        AllowanceResponse {
+            amount: self
+                .tokens
+                .get(&token_address, working_set)
+                .and_then(|token| token.allowances.get(&(owner, spender), working_set)),
+        }
+    }
+
+    #[rpc_method(name = "tokenInfo")]
+    pub fn token_info(
+        &self,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> TokenInfoResponse {
+        TokenInfoResponse {
+            token_info: self
+                .tokens
+                .get(&token_address, working_set)
+                .map(|token| to_token_info(token_address, token)),
+        }
+    }
+
+    #[rpc_method(name = "nativeToken")]
+    pub fn native_token_info(&self, working_set: &mut WorkingSet<C::Storage>) -> TokenInfoResponse {
+        self.token_info(self.native_token(), working_set)
+    }
+
+    /// Lists the tokens in creation order, starting at the index `cursor`. Pass `0` to get the first page,
+    /// then the `next_cursor` of the previous response.
+    #[rpc_method(name = "listTokens")]
+    pub fn list_tokens(
+        &self,
+        cursor: u64,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> ListTokensResponse {
+        let token_count = self.token_count.get(working_set).unwrap_or_default();
+        let end = cursor
+            .saturating_add(LIST_TOKENS_PAGE_SIZE)
+            .min(token_count);
+
+        let mut tokens = Vec::new();
+        for index in cursor..end {
+            let token_address = self
+                .token_addresses
+                .get(&index, working_set)
+                .expect("Every token index below the token count must be set");
+            let token = self
+                .tokens
+                .get(&token_address, working_set)
+                .expect("Every listed token must exist");
+            tokens.push(to_token_info(token_address, token));
+        }
+
+        ListTokensResponse {
+            tokens,
+            next_cursor: (end < token_count).then_some(end),
+        }
+    }
+}
+
+fn to_token_info<C: sov_modules_api::Context>(address: C::Address, token: Token<C>) -> TokenInfo {
+    TokenInfo {
+        address: address.into(),
+        name: token.name,
+        symbol: token.metadata.symbol,
+        decimals: token.metadata.decimals,
+        uri: token.metadata.uri,
+        total_supply: token.total_supply,
+        supply_cap: token.supply_cap,
+        admin: token.admin.into(),
+        authorized_minters: token
+            .authorized_minters
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    }
 }
 
 impl<C: sov_modules_api::Context> Bank<C> {
@@ -47,7 +166,7 @@ impl<C: sov_modules_api::Context> Bank<C> {
         user_address: C::Address,
         token_address: C::Address,
         working_set: &mut WorkingSet<C::Storage>,
-    ) -> Option<u64> {
+    ) -> Option<Amount> {
         self.tokens
             .get(&token_address, working_set)
             .and_then(|token| token.balances.get(&user_address, working_set))
@@ -3,8 +3,10 @@ use sov_state::{Prefix, WorkingSet};
 use std::collections::HashSet;
 
 use crate::call::prefix_from_address_with_parent;
+use crate::BankError;
 
-pub type Amount = u64;
+/// The amount of a token. 128 bits wide to support assets with 18 decimals, e.g. bridged from Ethereum.
+pub type Amount = u128;
 
 #[cfg_attr(
     feature = "native",
@@ -17,15 +19,61 @@ pub struct Coins<C: sov_modules_api::Context> {
     pub token_address: C::Address,
 }
 
+/// The metadata of a token, used by wallets and explorers to display it.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Eq, Clone)]
+pub struct TokenMetadata {
+    /// Ticker symbol of the token, e.g. "SOV".
+    pub symbol: String,
+    /// Number of decimals used to display amounts: an amount of 1234 with 2 decimals is displayed as 12.34.
+    pub decimals: u8,
+    /// Optional URI pointing to additional information about the token, e.g. a logo.
+    pub uri: Option<String>,
+}
+
+/// Formats `amount` with `decimals` decimals, dropping the trailing zeros of the fractional part.
+pub fn format_amount(amount: Amount, decimals: u8) -> String {
+    let digits = amount.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_owned()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
 /// This struct represents a token in the sov-bank module.
+///
+/// The `metadata`, `supply_cap`, `admin` and `allowances` fields changed the borsh layout of `Token`,
+/// so tokens stored by earlier versions of the module can't be read: rollups upgrading to it must
+/// restart from a fresh genesis.
 #[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
 pub(crate) struct Token<C: sov_modules_api::Context> {
     /// Name of the token.
     pub(crate) name: String,
+    /// Symbol, decimals and URI of the token.
+    pub(crate) metadata: TokenMetadata,
     /// Total supply of the coins.
-    pub(crate) total_supply: u64,
+    pub(crate) total_supply: Amount,
     /// Mapping from user address to user balance.
     pub(crate) balances: sov_state::StateMap<C::Address, Amount>,
+    /// Mapping from (owner, spender) to the amount the spender can transfer on behalf of the owner.
+    pub(crate) allowances: sov_state::StateMap<(C::Address, C::Address), Amount>,
+    /// The maximum total supply of the token, enforced when minting. `None` if the supply is uncapped.
+    pub(crate) supply_cap: Option<Amount>,
+    /// The account allowed to add and remove minters and to transfer this role.
+    pub(crate) admin: C::Address,
 
     /// Vector containing the authorized minters
     /// Empty vector indicates that the token supply is frozen
@@ -48,8 +96,14 @@ impl<C: sov_modules_api::Context> Token<C> {
         }
         let from_balance = self.check_balance(from, amount, working_set)?;
 
-        // We can't overflow here because the sum must be smaller or eq to `total_supply` which is u64.
-        let to_balance = self.balances.get(to, working_set).unwrap_or_default() + amount;
+        let to_balance = self
+            .balances
+            .get(to, working_set)
+            .unwrap_or_default()
+            .checked_add(amount)
+            .ok_or(anyhow::Error::msg(
+                "Account Balance overflow in the transfer method of bank module",
+            ))?;
 
         self.balances.set(from, &from_balance, working_set);
         self.balances.set(to, &to_balance, working_set);
@@ -57,6 +111,39 @@ impl<C: sov_modules_api::Context> Token<C> {
         Ok(())
     }
 
+    /// Sets the amount `spender` can transfer on behalf of `owner`, replacing the previous allowance.
+    pub(crate) fn approve(
+        &self,
+        owner: &C::Address,
+        spender: &C::Address,
+        amount: Amount,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) {
+        self.allowances
+            .set(&(owner.clone(), spender.clone()), &amount, working_set);
+    }
+
+    // Check that amount can be deducted from the allowance of `spender` on the funds of `owner`
+    // Returns the new allowance after subtraction, to be set with `approve` once the transfer succeeded.
+    pub(crate) fn check_allowance(
+        &self,
+        owner: &C::Address,
+        spender: &C::Address,
+        amount: Amount,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> Result<Amount> {
+        let key = (owner.clone(), spender.clone());
+        let allowance = match self.allowances.get(&key, working_set) {
+            Some(allowance) => allowance,
+            None => bail!(BankError::UnauthorizedTransfer(spender.to_string())),
+        };
+        let new_allowance = match allowance.checked_sub(amount) {
+            Some(new_allowance) => new_allowance,
+            None => bail!(BankError::InsufficientAllowance(spender.to_string())),
+        };
+        Ok(new_allowance)
+    }
+
     pub(crate) fn burn(
         &mut self,
         from: &C::Address,
@@ -64,6 +151,12 @@ impl<C: sov_modules_api::Context> Token<C> {
         working_set: &mut WorkingSet<C::Storage>,
     ) -> Result<()> {
         let new_balance = self.check_balance(from, amount, working_set)?;
+        self.total_supply = self
+            .total_supply
+            .checked_sub(amount)
+            .ok_or(anyhow::Error::msg(
+                "Total Supply underflow in the burn method of bank module",
+            ))?;
         self.balances.set(from, &new_balance, working_set);
 
         Ok(())
@@ -74,7 +167,7 @@ impl<C: sov_modules_api::Context> Token<C> {
     /// If the vector is empty when the function is called, this means the token is already frozen
     pub(crate) fn freeze(&mut self, sender: &C::Address) -> Result<()> {
         if self.authorized_minters.is_empty() {
-            bail!("Token is already frozen")
+            bail!(BankError::TokenAlreadyFrozen)
         }
         self.is_authorized_minter(sender)?;
         self.authorized_minters = vec![];
@@ -100,14 +193,60 @@ impl<C: sov_modules_api::Context> Token<C> {
             .ok_or(anyhow::Error::msg(
                 "Account Balance overflow in the mint method of bank module",
             ))?;
-
-        self.balances.set(minter_address, &to_balance, working_set);
-        self.total_supply = self
+        let total_supply = self
             .total_supply
             .checked_add(amount)
             .ok_or(anyhow::Error::msg(
                 "Total Supply overflow in the mint method of bank module",
             ))?;
+        check_supply_cap(total_supply, self.supply_cap)?;
+
+        self.total_supply = total_supply;
+        self.balances.set(minter_address, &to_balance, working_set);
+        Ok(())
+    }
+
+    /// Adds `minter` to the authorized minters. Only the admin can add minters, and not to a frozen token.
+    pub(crate) fn add_minter(&mut self, sender: &C::Address, minter: C::Address) -> Result<()> {
+        self.is_admin(sender)?;
+        if self.authorized_minters.is_empty() {
+            bail!(BankError::TokenAlreadyFrozen)
+        }
+        if self.authorized_minters.contains(&minter) {
+            bail!("{} is already an authorized minter", minter)
+        }
+        self.authorized_minters.push(minter);
+        Ok(())
+    }
+
+    /// Removes `minter` from the authorized minters. Only the admin can remove minters. Removing the last
+    /// minter freezes the token.
+    pub(crate) fn remove_minter(&mut self, sender: &C::Address, minter: &C::Address) -> Result<()> {
+        self.is_admin(sender)?;
+        let len_before = self.authorized_minters.len();
+        self.authorized_minters
+            .retain(|authorized| authorized != minter);
+        if self.authorized_minters.len() == len_before {
+            bail!("{} is not an authorized minter", minter)
+        }
+        Ok(())
+    }
+
+    /// Makes `new_admin` the admin of the token. Only the current admin can transfer the role.
+    pub(crate) fn transfer_admin(
+        &mut self,
+        sender: &C::Address,
+        new_admin: C::Address,
+    ) -> Result<()> {
+        self.is_admin(sender)?;
+        self.admin = new_admin;
+        Ok(())
+    }
+
+    fn is_admin(&self, sender: &C::Address) -> Result<()> {
+        if &self.admin != sender {
+            bail!(BankError::NotTokenAdmin(sender.to_string()))
+        }
         Ok(())
     }
 
@@ -129,34 +268,39 @@ impl<C: sov_modules_api::Context> Token<C> {
         let balance = self.balances.get_or_err(from, working_set)?;
         let new_balance = match balance.checked_sub(amount) {
             Some(from_balance) => from_balance,
-            None => bail!("Insufficient funds for {}", from),
+            None => bail!(BankError::InsufficientFunds(from.to_string())),
         };
         Ok(new_balance)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn create(
+        token_address: &C::Address,
         token_name: &str,
-        address_and_balances: &[(C::Address, u64)],
+        metadata: TokenMetadata,
+        address_and_balances: &[(C::Address, Amount)],
         authorized_minters: Vec<C::Address>,
-        sender: &[u8],
-        salt: u64,
+        admin: C::Address,
+        supply_cap: Option<Amount>,
         parent_prefix: &Prefix,
         working_set: &mut WorkingSet<C::Storage>,
-    ) -> Result<(C::Address, Self)> {
-        let token_address = super::create_token_address::<C>(token_name, sender, salt);
-        let token_prefix = prefix_from_address_with_parent::<C>(parent_prefix, &token_address);
+    ) -> Result<Self> {
+        let token_prefix = prefix_from_address_with_parent::<C>(parent_prefix, token_address);
+        let allowances = sov_state::StateMap::new(allowances_prefix(&token_prefix));
         let balances = sov_state::StateMap::new(token_prefix);
 
-        let mut total_supply: Option<u64> = Some(0);
+        let mut total_supply: Amount = 0;
         for (address, balance) in address_and_balances.iter() {
-            balances.set(address, balance, working_set);
-            total_supply = total_supply.and_then(|ts| ts.checked_add(*balance));
+            total_supply = match total_supply.checked_add(*balance) {
+                Some(total_supply) => total_supply,
+                None => bail!("Total supply overflow"),
+            };
+            // An address listed several times receives the sum of its balances. This can't overflow,
+            // since the sum is part of the total supply.
+            let address_balance = balances.get(address, working_set).unwrap_or_default() + balance;
+            balances.set(address, &address_balance, working_set);
         }
-
-        let total_supply = match total_supply {
-            Some(total_supply) => total_supply,
-            None => bail!("Total supply overflow"),
-        };
+        check_supply_cap(total_supply, supply_cap)?;
 
         let mut indices = HashSet::new();
         let mut auth_minter_list = Vec::new();
@@ -169,11 +313,32 @@ impl<C: sov_modules_api::Context> Token<C> {
 
         let token = Token::<C> {
             name: token_name.to_owned(),
+            metadata,
             total_supply,
             balances,
+            allowances,
+            supply_cap,
+            admin,
             authorized_minters: auth_minter_list,
         };
 
-        Ok((token_address, token))
+        Ok(token)
+    }
+}
+
+fn check_supply_cap(total_supply: Amount, supply_cap: Option<Amount>) -> Result<()> {
+    match supply_cap {
+        Some(supply_cap) if total_supply > supply_cap => {
+            bail!(BankError::SupplyCapExceeded(supply_cap))
+        }
+        _ => Ok(()),
     }
 }
+
+// The allowances are stored under the prefix of the balances followed by a suffix. Balance keys are
+// shorter than the allowance keys, so the two maps never collide.
+fn allowances_prefix(token_prefix: &Prefix) -> Prefix {
+    let mut prefix = token_prefix.as_aligned_vec().clone().into_inner();
+    prefix.extend_from_slice(b"/allowances");
+    Prefix::new(prefix)
+}
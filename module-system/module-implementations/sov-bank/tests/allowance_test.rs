@@ -0,0 +1,210 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::call::CallMessage;
+use sov_bank::genesis::{DEPLOYER, SALT};
+use sov_bank::query::AllowanceResponse;
+use sov_bank::{create_token_address, Amount, Bank, Coins};
+use sov_modules_api::{Address, Context, Module, ModuleInfo};
+use sov_state::{DefaultStorageSpec, ProverStorage, WorkingSet};
+
+pub type Storage = ProverStorage<DefaultStorageSpec>;
+
+#[test]
+fn transfer_from_with_allowance() {
+    let initial_balance = 100;
+    let approved_amount = 30;
+    let transfer_amount = 10;
+    let bank_config = create_bank_config_with_token(3, initial_balance);
+    let mut working_set = WorkingSet::new(ProverStorage::temporary());
+    let bank = Bank::new();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let token_address =
+        create_token_address::<C>(&bank_config.tokens[0].token_name, &DEPLOYER, SALT);
+    let owner_address = bank_config.tokens[0].address_and_balances[0].0.clone();
+    let spender_address = bank_config.tokens[0].address_and_balances[1].0.clone();
+    let receiver_address = bank_config.tokens[0].address_and_balances[2].0.clone();
+
+    // Preparation
+    let query_user_balance =
+        |user_address: Address, working_set: &mut WorkingSet<Storage>| -> Option<Amount> {
+            bank.get_balance_of(user_address, token_address.clone(), working_set)
+        };
+
+    let query_allowance = |working_set: &mut WorkingSet<Storage>| -> Option<Amount> {
+        let allowance: AllowanceResponse = bank.allowance(
+            owner_address.clone(),
+            spender_address.clone(),
+            token_address.clone(),
+            working_set,
+        );
+        allowance.amount
+    };
+
+    let owner_context = C::new(owner_address.clone());
+    let spender_context = C::new(spender_address.clone());
+    let transfer_from_message = |amount| CallMessage::TransferFrom {
+        from: owner_address.clone(),
+        to: receiver_address.clone(),
+        coins: Coins {
+            amount,
+            token_address: token_address.clone(),
+        },
+    };
+
+    assert_eq!(query_allowance(&mut working_set), None);
+
+    // Transfer without allowance
+    {
+        let err = bank
+            .call(
+                transfer_from_message(transfer_amount),
+                &spender_context,
+                &mut working_set,
+            )
+            .unwrap_err();
+        assert_eq!(err.code(), 3);
+    }
+
+    // Approve
+    {
+        let approve_message = CallMessage::Approve {
+            spender: spender_address.clone(),
+            coins: Coins {
+                amount: approved_amount,
+                token_address: token_address.clone(),
+            },
+        };
+        bank.call(approve_message, &owner_context, &mut working_set)
+            .expect("Approve call failed");
+        assert_eq!(query_allowance(&mut working_set), Some(approved_amount));
+    }
+
+    // Transfer happy test
+    {
+        bank.call(
+            transfer_from_message(transfer_amount),
+            &spender_context,
+            &mut working_set,
+        )
+        .expect("TransferFrom call failed");
+
+        assert_eq!(
+            query_user_balance(owner_address.clone(), &mut working_set),
+            Some(initial_balance - transfer_amount)
+        );
+        assert_eq!(
+            query_user_balance(spender_address.clone(), &mut working_set),
+            Some(initial_balance)
+        );
+        assert_eq!(
+            query_user_balance(receiver_address.clone(), &mut working_set),
+            Some(initial_balance + transfer_amount)
+        );
+        assert_eq!(
+            query_allowance(&mut working_set),
+            Some(approved_amount - transfer_amount)
+        );
+    }
+
+    // Transfer more than the remaining allowance
+    {
+        let err = bank
+            .call(
+                transfer_from_message(approved_amount),
+                &spender_context,
+                &mut working_set,
+            )
+            .unwrap_err();
+        assert_eq!(err.code(), 4);
+        assert_eq!(
+            query_user_balance(owner_address.clone(), &mut working_set),
+            Some(initial_balance - transfer_amount)
+        );
+        assert_eq!(
+            query_allowance(&mut working_set),
+            Some(approved_amount - transfer_amount)
+        );
+    }
+
+    // Transfer more than the balance of the owner, which keeps the allowance
+    {
+        let approve_message = CallMessage::Approve {
+            spender: spender_address.clone(),
+            coins: Coins {
+                amount: initial_balance,
+                token_address: token_address.clone(),
+            },
+        };
+        bank.call(approve_message, &owner_context, &mut working_set)
+            .expect("Approve call failed");
+
+        let err = bank
+            .call(
+                transfer_from_message(initial_balance),
+                &spender_context,
+                &mut working_set,
+            )
+            .unwrap_err();
+        assert_eq!(err.code(), 1);
+        assert_eq!(
+            query_user_balance(owner_address.clone(), &mut working_set),
+            Some(initial_balance - transfer_amount)
+        );
+        assert_eq!(query_allowance(&mut working_set), Some(initial_balance));
+    }
+
+    // Approving again replaces the allowance
+    {
+        let approve_message = CallMessage::Approve {
+            spender: spender_address.clone(),
+            coins: Coins {
+                amount: 0,
+                token_address: token_address.clone(),
+            },
+        };
+        bank.call(approve_message, &owner_context, &mut working_set)
+            .expect("Approve call failed");
+        assert_eq!(query_allowance(&mut working_set), Some(0));
+
+        let err = bank
+            .call(transfer_from_message(1), &spender_context, &mut working_set)
+            .unwrap_err();
+        assert_eq!(err.code(), 4);
+    }
+}
+
+#[test]
+fn approve_unknown_token() {
+    let bank_config = create_bank_config_with_token(2, 100);
+    let mut working_set = WorkingSet::new(ProverStorage::temporary());
+    let bank = Bank::<C>::new();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let owner_address = bank_config.tokens[0].address_and_balances[0].0.clone();
+    let spender_address = bank_config.tokens[0].address_and_balances[1].0.clone();
+    let unknown_token_address = generate_address("unknown_token");
+
+    let approve_message = CallMessage::Approve {
+        spender: spender_address.clone(),
+        coins: Coins {
+            amount: 10,
+            token_address: unknown_token_address.clone(),
+        },
+    };
+    bank.call(
+        approve_message,
+        &C::new(owner_address.clone()),
+        &mut working_set,
+    )
+    .expect_err("Approving an unknown token must fail");
+
+    let allowance = bank.allowance(
+        owner_address,
+        spender_address,
+        unknown_token_address,
+        &mut working_set,
+    );
+    assert_eq!(allowance.amount, None);
+}
@@ -2,7 +2,7 @@ use helpers::{generate_address, C};
 use sov_bank::call::CallMessage;
 use sov_bank::genesis::{DEPLOYER, SALT};
 use sov_bank::query::TotalSupplyResponse;
-use sov_bank::{create_token_address, Bank, BankConfig, Coins};
+use sov_bank::{create_token_address, Amount, Bank, BankConfig, Coins};
 use sov_modules_api::{Address, Context, Module, ModuleInfo};
 use sov_state::{DefaultStorageSpec, ProverStorage, WorkingSet};
 
@@ -16,7 +16,11 @@ pub type Storage = ProverStorage<DefaultStorageSpec>;
 fn burn_deployed_tokens() {
     let bank = Bank::<C>::new();
     let mut working_set = WorkingSet::new(ProverStorage::temporary());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        native_token: None,
+        tokens: vec![],
+        authorized_modules: vec![],
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let sender_address = generate_address("just_sender");
@@ -34,22 +38,26 @@ fn burn_deployed_tokens() {
     let mint_message = CallMessage::CreateToken {
         salt,
         token_name,
+        symbol: "TKN".to_owned(),
+        decimals: 0,
+        uri: None,
         initial_balance,
         minter_address: minter_address.clone(),
         authorized_minters: vec![minter_address.clone()],
+        supply_cap: None,
     };
     bank.call(mint_message, &minter_context, &mut working_set)
         .expect("Failed to mint token");
     // No events at the moment. If there are, needs to be checked
     assert!(working_set.events().is_empty());
 
-    let query_total_supply = |working_set: &mut WorkingSet<Storage>| -> Option<u64> {
+    let query_total_supply = |working_set: &mut WorkingSet<Storage>| -> Option<Amount> {
         let total_supply: TotalSupplyResponse = bank.supply_of(token_address.clone(), working_set);
         total_supply.amount
     };
 
     let query_user_balance =
-        |user_address: Address, working_set: &mut WorkingSet<Storage>| -> Option<u64> {
+        |user_address: Address, working_set: &mut WorkingSet<Storage>| -> Option<Amount> {
             bank.get_balance_of(user_address, token_address.clone(), working_set)
         };
 
@@ -154,7 +162,7 @@ fn burn_initial_tokens() {
     let sender_address = bank_config.tokens[0].address_and_balances[0].0.clone();
 
     let query_user_balance =
-        |user_address: Address, working_set: &mut WorkingSet<Storage>| -> Option<u64> {
+        |user_address: Address, working_set: &mut WorkingSet<Storage>| -> Option<Amount> {
             bank.get_balance_of(user_address, token_address.clone(), working_set)
         };
 
@@ -1,6 +1,6 @@
 use sov_bank::call::CallMessage;
-use sov_bank::{create_token_address, Bank};
-use sov_modules_api::{Context, Module, ModuleInfo};
+use sov_bank::{create_token_address, Amount, Bank};
+use sov_modules_api::{Address, Context, Module, ModuleInfo};
 use sov_state::{ProverStorage, WorkingSet};
 
 mod helpers;
@@ -24,13 +24,19 @@ fn initial_and_deployed_token() {
     let create_token_message = CallMessage::CreateToken::<C> {
         salt,
         token_name,
+        symbol: "TKN".to_owned(),
+        decimals: 0,
+        uri: None,
         initial_balance,
         minter_address: minter_address.clone(),
         authorized_minters: vec![minter_address.clone()],
+        supply_cap: None,
     };
 
-    bank.call(create_token_message, &sender_context, &mut working_set)
+    let response = bank
+        .call(create_token_message, &sender_context, &mut working_set)
         .expect("Failed to create token");
+    assert_eq!(response.data::<Address>().unwrap(), token_address);
 
     assert!(working_set.events().is_empty());
 
@@ -49,7 +55,7 @@ fn overflow_max_supply() {
     let bank = Bank::<C>::new();
     let mut working_set = WorkingSet::new(ProverStorage::temporary());
 
-    let bank_config = create_bank_config_with_token(2, u64::MAX - 2);
+    let bank_config = create_bank_config_with_token(2, Amount::MAX - 2);
 
     let genesis_result = bank.genesis(&bank_config, &mut working_set);
     assert!(genesis_result.is_err());
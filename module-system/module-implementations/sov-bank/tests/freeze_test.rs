@@ -1,7 +1,7 @@
 use helpers::{generate_address, C};
 use sov_bank::call::CallMessage;
 use sov_bank::query::TotalSupplyResponse;
-use sov_bank::{create_token_address, Bank, BankConfig, Coins};
+use sov_bank::{create_token_address, Amount, Bank, BankConfig, Coins};
 use sov_modules_api::{Address, Context, Module, ModuleInfo};
 use sov_state::{DefaultStorageSpec, ProverStorage, WorkingSet};
 
@@ -13,7 +13,11 @@ pub type Storage = ProverStorage<DefaultStorageSpec>;
 fn freeze_token() {
     let bank = Bank::<C>::new();
     let mut working_set = WorkingSet::new(ProverStorage::temporary());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        native_token: None,
+        tokens: vec![],
+        authorized_modules: vec![],
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let minter_address = generate_address("minter");
@@ -29,9 +33,13 @@ fn freeze_token() {
     let mint_message = CallMessage::CreateToken {
         salt,
         token_name,
+        symbol: "TKN".to_owned(),
+        decimals: 0,
+        uri: None,
         initial_balance,
         minter_address: minter_address.clone(),
         authorized_minters: vec![minter_address.clone()],
+        supply_cap: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
@@ -59,10 +67,9 @@ fn freeze_token() {
     let freeze = bank.call(freeze_message.clone(), &minter_context, &mut working_set);
     assert!(freeze.is_err());
 
-    assert_eq!(
-        "Token is already frozen".to_string(),
-        freeze.err().unwrap().to_string()
-    );
+    let error = freeze.err().unwrap();
+    assert_eq!("Token is already frozen".to_string(), error.to_string());
+    assert_eq!(error.code(), 2);
 
     // create a second token
     let token_name = "Token2".to_owned();
@@ -74,9 +81,13 @@ fn freeze_token() {
     let mint_message = CallMessage::CreateToken {
         salt,
         token_name,
+        symbol: "TKN".to_owned(),
+        decimals: 0,
+        uri: None,
         initial_balance,
         minter_address: minter_address.clone(),
         authorized_minters: vec![minter_address.clone()],
+        supply_cap: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
@@ -117,7 +128,7 @@ fn freeze_token() {
 
     let query_total_supply = |token_address: Address,
                               working_set: &mut WorkingSet<Storage>|
-     -> Option<u64> {
+     -> Option<Amount> {
         let total_supply: TotalSupplyResponse = bank.supply_of(token_address.clone(), working_set);
         total_supply.amount
     };
@@ -152,7 +163,7 @@ fn freeze_token() {
     let query_user_balance = |token_address: Address,
                               user_address: Address,
                               working_set: &mut WorkingSet<Storage>|
-     -> Option<u64> {
+     -> Option<Amount> {
         bank.get_balance_of(user_address, token_address.clone(), working_set)
     };
     let bal = query_user_balance(token_address_2.clone(), minter_address, &mut working_set);
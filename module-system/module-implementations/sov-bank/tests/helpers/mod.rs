@@ -1,4 +1,4 @@
-use sov_bank::{BankConfig, TokenConfig};
+use sov_bank::{Amount, BankConfig, TokenConfig};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::Hasher;
 use sov_modules_api::{Address, Spec};
@@ -12,7 +12,7 @@ pub fn generate_address(key: &str) -> <C as Spec>::Address {
 
 pub fn create_bank_config_with_token(
     addresses_count: usize,
-    initial_balance: u64,
+    initial_balance: Amount,
 ) -> BankConfig<C> {
     let address_and_balances = (0..addresses_count)
         .map(|i| {
@@ -24,10 +24,16 @@ pub fn create_bank_config_with_token(
 
     let token_config = TokenConfig {
         token_name: "InitialToken".to_owned(),
+        symbol: "INIT".to_owned(),
+        decimals: 0,
+        uri: None,
+        supply_cap: None,
         address_and_balances,
     };
 
     BankConfig {
+        native_token: None,
         tokens: vec![token_config],
+        authorized_modules: vec![],
     }
 }
@@ -1,7 +1,7 @@
 use helpers::{generate_address, C};
 use sov_bank::call::CallMessage;
 use sov_bank::query::TotalSupplyResponse;
-use sov_bank::{create_token_address, Bank, BankConfig, Coins};
+use sov_bank::{create_token_address, Amount, Bank, BankConfig, Coins};
 use sov_modules_api::{Address, Context, Module, ModuleInfo};
 use sov_state::{DefaultStorageSpec, ProverStorage, WorkingSet};
 
@@ -13,7 +13,11 @@ pub type Storage = ProverStorage<DefaultStorageSpec>;
 fn mint_token() {
     let bank = Bank::<C>::new();
     let mut working_set = WorkingSet::new(ProverStorage::temporary());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        native_token: None,
+        tokens: vec![],
+        authorized_modules: vec![],
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let minter_address = generate_address("minter");
@@ -29,9 +33,13 @@ fn mint_token() {
     let mint_message = CallMessage::CreateToken {
         salt,
         token_name,
+        symbol: "TKN".to_owned(),
+        decimals: 0,
+        uri: None,
         initial_balance,
         minter_address: minter_address.clone(),
         authorized_minters: vec![minter_address.clone()],
+        supply_cap: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
@@ -41,13 +49,13 @@ fn mint_token() {
 
     let query_total_supply = |token_address: Address,
                               working_set: &mut WorkingSet<Storage>|
-     -> Option<u64> {
+     -> Option<Amount> {
         let total_supply: TotalSupplyResponse = bank.supply_of(token_address.clone(), working_set);
         total_supply.amount
     };
 
     let query_user_balance =
-        |user_address: Address, working_set: &mut WorkingSet<Storage>| -> Option<u64> {
+        |user_address: Address, working_set: &mut WorkingSet<Storage>| -> Option<Amount> {
             bank.get_balance_of(user_address, token_address.clone(), working_set)
         };
 
@@ -111,12 +119,16 @@ fn mint_token() {
     let mint_message = CallMessage::CreateToken {
         salt,
         token_name,
+        symbol: "TKN".to_owned(),
+        decimals: 0,
+        uri: None,
         initial_balance,
         minter_address: minter_address.clone(),
         authorized_minters: vec![
             authorized_minter_address_1.clone(),
             authorized_minter_address_2.clone(),
         ],
+        supply_cap: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
@@ -188,7 +200,7 @@ fn mint_token() {
     // Overflow test - account balance
     let overflow_mint_message = CallMessage::Mint {
         coins: Coins {
-            amount: u64::MAX,
+            amount: Amount::MAX,
             token_address: token_address.clone(),
         },
         minter_address: new_holder.clone(),
@@ -212,7 +224,7 @@ fn mint_token() {
     let new_holder = generate_address("new_holder_3");
     let overflow_mint_message = CallMessage::Mint {
         coins: Coins {
-            amount: u64::MAX - 1,
+            amount: Amount::MAX - 1,
             token_address: token_address.clone(),
         },
         minter_address: new_holder.clone(),
@@ -0,0 +1,230 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::call::CallMessage;
+use sov_bank::{Amount, Bank, BankConfig, Coins};
+use sov_modules_api::{Address, Context, Module, ModuleInfo};
+use sov_rollup_interface::stf::Event;
+use sov_state::{DefaultStorageSpec, ProverStorage, WorkingSet};
+
+pub type Storage = ProverStorage<DefaultStorageSpec>;
+
+const INITIAL_BALANCE: Amount = 100;
+const SUPPLY_CAP: Amount = 300;
+
+#[test]
+fn rotate_minters_and_admin() {
+    let bank = Bank::<C>::new();
+    let mut working_set = WorkingSet::new(ProverStorage::temporary());
+    let empty_bank_config = BankConfig::<C> {
+        native_token: None,
+        tokens: vec![],
+        authorized_modules: vec![],
+    };
+    bank.genesis(&empty_bank_config, &mut working_set).unwrap();
+
+    let admin = generate_address("admin");
+    let new_admin = generate_address("new_admin");
+    let minter_1 = generate_address("minter_1");
+    let minter_2 = generate_address("minter_2");
+    let admin_context = C::new(admin.clone());
+    let new_admin_context = C::new(new_admin.clone());
+
+    let token_address = bank
+        .call(
+            CallMessage::CreateToken {
+                salt: 0,
+                token_name: "Token1".to_owned(),
+                symbol: "TKN".to_owned(),
+                decimals: 0,
+                uri: None,
+                initial_balance: INITIAL_BALANCE,
+                minter_address: admin.clone(),
+                authorized_minters: vec![minter_1.clone()],
+                supply_cap: Some(SUPPLY_CAP),
+            },
+            &admin_context,
+            &mut working_set,
+        )
+        .expect("Failed to create token")
+        .data::<Address>()
+        .unwrap();
+
+    let mint = |minter: &Address, amount: Amount, working_set: &mut WorkingSet<Storage>| {
+        bank.call(
+            CallMessage::Mint {
+                coins: Coins {
+                    amount,
+                    token_address: token_address.clone(),
+                },
+                minter_address: minter.clone(),
+            },
+            &C::new(minter.clone()),
+            working_set,
+        )
+    };
+    let query_total_supply = |working_set: &mut WorkingSet<Storage>| -> Option<Amount> {
+        bank.supply_of(token_address.clone(), working_set).amount
+    };
+
+    // Only the admin can add minters
+    {
+        let add_minter_message = CallMessage::AddMinter {
+            token_address: token_address.clone(),
+            minter: minter_2.clone(),
+        };
+        let err = bank
+            .call(
+                add_minter_message.clone(),
+                &C::new(minter_1.clone()),
+                &mut working_set,
+            )
+            .unwrap_err();
+        assert_eq!(err.code(), 5);
+        mint(&minter_2, 10, &mut working_set).expect_err("Minter 2 isn't authorized yet");
+
+        bank.call(add_minter_message.clone(), &admin_context, &mut working_set)
+            .expect("Adding a minter failed");
+        assert_eq!(
+            working_set.events().last(),
+            Some(&Event::new(
+                "minter_added",
+                &format!("token: {}. minter: {}", token_address, minter_2)
+            ))
+        );
+        mint(&minter_2, 10, &mut working_set).expect("Minter 2 is authorized");
+        assert_eq!(
+            query_total_supply(&mut working_set),
+            Some(INITIAL_BALANCE + 10)
+        );
+
+        bank.call(add_minter_message, &admin_context, &mut working_set)
+            .expect_err("Minter 2 is already authorized");
+    }
+
+    // Remove the old minter
+    {
+        bank.call(
+            CallMessage::RemoveMinter {
+                token_address: token_address.clone(),
+                minter: minter_1.clone(),
+            },
+            &admin_context,
+            &mut working_set,
+        )
+        .expect("Removing a minter failed");
+        assert_eq!(
+            working_set.events().last(),
+            Some(&Event::new(
+                "minter_removed",
+                &format!("token: {}. minter: {}", token_address, minter_1)
+            ))
+        );
+        mint(&minter_1, 10, &mut working_set).expect_err("Minter 1 was removed");
+    }
+
+    // Transfer the admin role
+    {
+        bank.call(
+            CallMessage::TransferTokenAdmin {
+                token_address: token_address.clone(),
+                new_admin: new_admin.clone(),
+            },
+            &admin_context,
+            &mut working_set,
+        )
+        .expect("Transferring the admin role failed");
+        assert_eq!(
+            working_set.events().last(),
+            Some(&Event::new(
+                "token_admin_transferred",
+                &format!(
+                    "token: {}. previous_admin: {}. new_admin: {}",
+                    token_address, admin, new_admin
+                )
+            ))
+        );
+
+        let add_minter_message = CallMessage::AddMinter {
+            token_address: token_address.clone(),
+            minter: minter_1.clone(),
+        };
+        let err = bank
+            .call(add_minter_message.clone(), &admin_context, &mut working_set)
+            .unwrap_err();
+        assert_eq!(err.code(), 5);
+        bank.call(add_minter_message, &new_admin_context, &mut working_set)
+            .expect("The new admin can add minters");
+    }
+
+    // Minting can't exceed the supply cap
+    {
+        let remaining = SUPPLY_CAP - query_total_supply(&mut working_set).unwrap();
+        let err = mint(&minter_2, remaining + 1, &mut working_set).unwrap_err();
+        assert_eq!(err.code(), 6);
+        assert_eq!(
+            query_total_supply(&mut working_set),
+            Some(SUPPLY_CAP - remaining)
+        );
+
+        mint(&minter_2, remaining, &mut working_set).expect("Minting up to the cap failed");
+        assert_eq!(query_total_supply(&mut working_set), Some(SUPPLY_CAP));
+    }
+
+    // Removing the last minters freezes the token
+    {
+        for minter in [&minter_1, &minter_2] {
+            bank.call(
+                CallMessage::RemoveMinter {
+                    token_address: token_address.clone(),
+                    minter: minter.clone(),
+                },
+                &new_admin_context,
+                &mut working_set,
+            )
+            .expect("Removing a minter failed");
+        }
+
+        let err = bank
+            .call(
+                CallMessage::AddMinter {
+                    token_address: token_address.clone(),
+                    minter: minter_1.clone(),
+                },
+                &new_admin_context,
+                &mut working_set,
+            )
+            .unwrap_err();
+        assert_eq!(err.code(), 2);
+    }
+}
+
+#[test]
+fn create_token_above_supply_cap() {
+    let bank = Bank::<C>::new();
+    let mut working_set = WorkingSet::new(ProverStorage::temporary());
+
+    let mut bank_config = create_bank_config_with_token(3, INITIAL_BALANCE);
+    bank_config.tokens[0].supply_cap = Some(3 * INITIAL_BALANCE - 1);
+    assert!(bank.genesis(&bank_config, &mut working_set).is_err());
+
+    let sender = generate_address("sender");
+    let err = bank
+        .call(
+            CallMessage::CreateToken {
+                salt: 0,
+                token_name: "Token1".to_owned(),
+                symbol: "TKN".to_owned(),
+                decimals: 0,
+                uri: None,
+                initial_balance: SUPPLY_CAP + 1,
+                minter_address: sender.clone(),
+                authorized_minters: vec![sender.clone()],
+                supply_cap: Some(SUPPLY_CAP),
+            },
+            &C::new(sender),
+            &mut working_set,
+        )
+        .unwrap_err();
+    assert_eq!(err.code(), 6);
+}
@@ -0,0 +1,64 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::genesis::{DEPLOYER, SALT};
+use sov_bank::{create_token_address, native_token_address, Bank, TokenConfig};
+use sov_modules_api::{Module, ModuleInfo};
+use sov_state::{ProverStorage, WorkingSet};
+
+#[test]
+fn native_token_at_genesis() {
+    let initial_balance = 100;
+    let mut bank_config = create_bank_config_with_token(2, initial_balance);
+    let holder = generate_address("holder");
+    bank_config.native_token = Some(TokenConfig {
+        token_name: "NativeToken".to_owned(),
+        symbol: "NAT".to_owned(),
+        decimals: 9,
+        uri: None,
+        supply_cap: None,
+        address_and_balances: vec![(holder.clone(), initial_balance)],
+    });
+
+    let mut working_set = WorkingSet::new(ProverStorage::temporary());
+    let bank = Bank::<C>::new();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    // The address of the native token only depends on the address of the bank.
+    let native_token = bank.native_token();
+    assert_eq!(native_token, native_token_address::<C>(bank.address()));
+    assert_eq!(native_token, Bank::<C>::new().native_token());
+    assert_ne!(
+        native_token,
+        create_token_address::<C>("NativeToken", &DEPLOYER, SALT)
+    );
+
+    assert_eq!(
+        bank.get_balance_of(holder, native_token.clone(), &mut working_set),
+        Some(initial_balance)
+    );
+
+    let token_info = bank
+        .native_token_info(&mut working_set)
+        .token_info
+        .expect("The native token must exist");
+    assert_eq!(token_info.address, native_token.into());
+    assert_eq!(token_info.symbol, "NAT");
+    assert_eq!(token_info.decimals, 9);
+    assert_eq!(token_info.total_supply, initial_balance);
+
+    // The native token is listed first, before the other genesis tokens.
+    let tokens = bank.list_tokens(0, &mut working_set).tokens;
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0], token_info);
+}
+
+#[test]
+fn no_native_token() {
+    let bank_config = create_bank_config_with_token(2, 100);
+    let mut working_set = WorkingSet::new(ProverStorage::temporary());
+    let bank = Bank::<C>::new();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    assert_eq!(bank.native_token_info(&mut working_set).token_info, None);
+}
@@ -0,0 +1,189 @@
+mod helpers;
+
+use helpers::*;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use sov_bank::call::CallMessage;
+use sov_bank::genesis::{DEPLOYER, SALT};
+use sov_bank::{create_token_address, Amount, Bank, BankConfig, Coins, TokenConfig};
+use sov_modules_api::{Address, Context, Module, ModuleInfo};
+use sov_state::{ProverStorage, WorkingSet};
+
+const USERS: usize = 4;
+
+#[derive(Debug, Clone)]
+enum Operation {
+    Transfer {
+        from: usize,
+        to: usize,
+        amount: Amount,
+    },
+    Burn {
+        from: usize,
+        amount: Amount,
+    },
+    Mint {
+        to: usize,
+        amount: Amount,
+    },
+}
+
+// Mostly small amounts, so that operations succeed, mixed with amounts close to the overflow bounds.
+fn amount() -> impl Strategy<Value = Amount> {
+    prop_oneof![
+        3 => 0..=1_000 as Amount,
+        1 => any::<Amount>(),
+        1 => (Amount::MAX - 1_000)..=Amount::MAX,
+    ]
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        (0..USERS, 0..USERS, amount()).prop_map(|(from, to, amount)| Operation::Transfer {
+            from,
+            to,
+            amount
+        }),
+        (0..USERS, amount()).prop_map(|(from, amount)| Operation::Burn { from, amount }),
+        (0..USERS, amount()).prop_map(|(to, amount)| Operation::Mint { to, amount }),
+    ]
+}
+
+fn initial_balance() -> impl Strategy<Value = Amount> {
+    prop_oneof![0..=1_000 as Amount, 0..=Amount::MAX / USERS as Amount]
+}
+
+proptest! {
+    #[test]
+    fn total_supply_equals_sum_of_balances(
+        // The same user can be listed several times.
+        initial_balances in vec((0..USERS, initial_balance()), 0..=USERS),
+        operations in vec(operation(), 0..30),
+    ) {
+        let users: Vec<Address> = (0..USERS)
+            .map(|i| generate_address(&format!("user_{}", i)))
+            .collect();
+        let bank_config = BankConfig {
+            native_token: None,
+            tokens: vec![TokenConfig {
+                token_name: "InitialToken".to_owned(),
+                symbol: "INIT".to_owned(),
+                decimals: 18,
+                uri: None,
+                supply_cap: None,
+                address_and_balances: initial_balances
+                    .iter()
+                    .map(|(user, balance)| (users[*user].clone(), *balance))
+                    .collect(),
+            }],
+            authorized_modules: vec![],
+        };
+
+        let mut working_set = WorkingSet::new(ProverStorage::temporary());
+        let bank = Bank::<C>::new();
+        bank.genesis(&bank_config, &mut working_set).unwrap();
+
+        let token_address =
+            create_token_address::<C>(&bank_config.tokens[0].token_name, &DEPLOYER, SALT);
+        for (i, user) in users.iter().enumerate() {
+            let expected_balance: Amount = initial_balances
+                .iter()
+                .filter(|(owner, _)| *owner == i)
+                .map(|(_, balance)| balance)
+                .sum();
+            prop_assert_eq!(
+                bank.get_balance_of(user.clone(), token_address.clone(), &mut working_set)
+                    .unwrap_or_default(),
+                expected_balance
+            );
+        }
+        let coins = |amount| Coins {
+            amount,
+            token_address: token_address.clone(),
+        };
+        let minter_context = C::new(Address::from(DEPLOYER));
+
+        for operation in operations {
+            // Failed operations must leave the state untouched, so their result is ignored.
+            let _ = match operation {
+                Operation::Transfer { from, to, amount } => bank.call(
+                    CallMessage::Transfer {
+                        to: users[to].clone(),
+                        coins: coins(amount),
+                    },
+                    &C::new(users[from].clone()),
+                    &mut working_set,
+                ),
+                Operation::Burn { from, amount } => bank.call(
+                    CallMessage::Burn {
+                        coins: coins(amount),
+                    },
+                    &C::new(users[from].clone()),
+                    &mut working_set,
+                ),
+                Operation::Mint { to, amount } => bank.call(
+                    CallMessage::Mint {
+                        coins: coins(amount),
+                        minter_address: users[to].clone(),
+                    },
+                    &minter_context,
+                    &mut working_set,
+                ),
+            };
+
+            let total_supply = bank
+                .supply_of(token_address.clone(), &mut working_set)
+                .amount
+                .unwrap();
+            let sum_of_balances = users
+                .iter()
+                .map(|user| {
+                    bank.get_balance_of(user.clone(), token_address.clone(), &mut working_set)
+                        .unwrap_or_default()
+                })
+                .try_fold(0 as Amount, |sum, balance| sum.checked_add(balance));
+            prop_assert_eq!(sum_of_balances, Some(total_supply));
+        }
+    }
+
+    #[test]
+    fn genesis_rejects_total_supply_overflow(
+        initial_balances in vec((0..USERS, any::<Amount>()), 1..USERS),
+    ) {
+        let bank_config = BankConfig {
+            native_token: None,
+            tokens: vec![TokenConfig {
+                token_name: "InitialToken".to_owned(),
+                symbol: "INIT".to_owned(),
+                decimals: 18,
+                uri: None,
+                supply_cap: None,
+                address_and_balances: initial_balances
+                    .iter()
+                    .map(|(user, balance)| (generate_address(&format!("user_{}", user)), *balance))
+                    .collect(),
+            }],
+            authorized_modules: vec![],
+        };
+
+        let mut working_set = WorkingSet::new(ProverStorage::temporary());
+        let bank = Bank::<C>::new();
+        let genesis_result = bank.genesis(&bank_config, &mut working_set);
+
+        let expected_total_supply = initial_balances
+            .iter()
+            .try_fold(0 as Amount, |sum, (_, balance)| sum.checked_add(*balance));
+        match expected_total_supply {
+            Some(total_supply) => {
+                prop_assert!(genesis_result.is_ok());
+                let token_address =
+                    create_token_address::<C>(&bank_config.tokens[0].token_name, &DEPLOYER, SALT);
+                prop_assert_eq!(
+                    bank.supply_of(token_address, &mut working_set).amount,
+                    Some(total_supply)
+                );
+            }
+            None => prop_assert!(genesis_result.is_err()),
+        }
+    }
+}
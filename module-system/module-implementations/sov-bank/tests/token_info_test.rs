@@ -0,0 +1,110 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::call::CallMessage;
+use sov_bank::genesis::{DEPLOYER, SALT};
+use sov_bank::query::{TokenInfo, LIST_TOKENS_PAGE_SIZE};
+use sov_bank::{create_token_address, format_amount, Amount, Bank};
+use sov_modules_api::{Address, Context, Module, ModuleInfo};
+use sov_state::{ProverStorage, WorkingSet};
+
+#[test]
+fn token_info_and_list_tokens() {
+    let bank_config = create_bank_config_with_token(2, 100);
+    let mut working_set = WorkingSet::new(ProverStorage::temporary());
+    let bank = Bank::new();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let genesis_token_address =
+        create_token_address::<C>(&bank_config.tokens[0].token_name, &DEPLOYER, SALT);
+    let sender_address = generate_address("sender");
+    let sender_context = C::new(sender_address.clone());
+
+    let create_token_message = |salt| CallMessage::CreateToken::<C> {
+        salt,
+        token_name: "Token1".to_owned(),
+        symbol: "TKN1".to_owned(),
+        decimals: 6,
+        uri: Some("https://example.com/token1.json".to_owned()),
+        initial_balance: 1_500_000,
+        minter_address: sender_address.clone(),
+        authorized_minters: vec![],
+        supply_cap: Some(2_000_000),
+    };
+
+    let token_address = bank
+        .call(create_token_message(1), &sender_context, &mut working_set)
+        .expect("Failed to create token")
+        .data::<Address>()
+        .unwrap();
+
+    let expected_token_info = TokenInfo {
+        address: token_address.clone().into(),
+        name: "Token1".to_owned(),
+        symbol: "TKN1".to_owned(),
+        decimals: 6,
+        uri: Some("https://example.com/token1.json".to_owned()),
+        total_supply: 1_500_000,
+        supply_cap: Some(2_000_000),
+        admin: sender_address.clone().into(),
+        authorized_minters: vec![],
+    };
+
+    let token_info = bank
+        .token_info(token_address.clone(), &mut working_set)
+        .token_info;
+    assert_eq!(token_info, Some(expected_token_info));
+
+    let unknown_token_info = bank
+        .token_info(generate_address("unknown_token"), &mut working_set)
+        .token_info;
+    assert_eq!(unknown_token_info, None);
+
+    let tokens = bank.list_tokens(0, &mut working_set);
+    assert_eq!(tokens.next_cursor, None);
+    assert_eq!(
+        tokens
+            .tokens
+            .iter()
+            .map(|token| token.address.clone())
+            .collect::<Vec<_>>(),
+        vec![genesis_token_address.into(), token_address.into()]
+    );
+    assert_eq!(tokens.tokens[0].symbol, "INIT");
+
+    // Fill more than one page
+    for salt in 2..=LIST_TOKENS_PAGE_SIZE {
+        bank.call(
+            create_token_message(salt),
+            &sender_context,
+            &mut working_set,
+        )
+        .expect("Failed to create token");
+    }
+
+    let first_page = bank.list_tokens(0, &mut working_set);
+    assert_eq!(first_page.tokens.len() as u64, LIST_TOKENS_PAGE_SIZE);
+    assert_eq!(first_page.next_cursor, Some(LIST_TOKENS_PAGE_SIZE));
+
+    let second_page = bank.list_tokens(LIST_TOKENS_PAGE_SIZE, &mut working_set);
+    assert_eq!(second_page.tokens.len(), 1);
+    assert_eq!(second_page.next_cursor, None);
+
+    let past_the_end = bank.list_tokens(LIST_TOKENS_PAGE_SIZE + 1, &mut working_set);
+    assert!(past_the_end.tokens.is_empty());
+    assert_eq!(past_the_end.next_cursor, None);
+}
+
+#[test]
+fn format_amount_with_decimals() {
+    assert_eq!(format_amount(123456, 0), "123456");
+    assert_eq!(format_amount(123456, 2), "1234.56");
+    assert_eq!(format_amount(123400, 2), "1234");
+    assert_eq!(format_amount(120, 2), "1.2");
+    assert_eq!(format_amount(5, 3), "0.005");
+    assert_eq!(format_amount(0, 6), "0");
+    assert_eq!(
+        format_amount(Amount::MAX, 18),
+        "340282366920938463463.374607431768211455"
+    );
+}
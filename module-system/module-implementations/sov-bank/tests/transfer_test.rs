@@ -4,7 +4,7 @@ use helpers::*;
 use sov_bank::call::CallMessage;
 use sov_bank::genesis::{DEPLOYER, SALT};
 use sov_bank::query::TotalSupplyResponse;
-use sov_bank::{create_token_address, Bank, BankConfig, Coins};
+use sov_bank::{create_token_address, Amount, Bank, BankConfig, BankError, Coins};
 use sov_modules_api::{Address, Context, Module, ModuleInfo};
 use sov_state::{DefaultStorageSpec, ProverStorage, WorkingSet};
 
@@ -27,11 +27,11 @@ fn transfer_initial_token() {
 
     // Preparation
     let query_user_balance =
-        |user_address: Address, working_set: &mut WorkingSet<Storage>| -> Option<u64> {
+        |user_address: Address, working_set: &mut WorkingSet<Storage>| -> Option<Amount> {
             bank.get_balance_of(user_address, token_address.clone(), working_set)
         };
 
-    let query_total_supply = |working_set: &mut WorkingSet<Storage>| -> Option<u64> {
+    let query_total_supply = |working_set: &mut WorkingSet<Storage>| -> Option<Amount> {
         let total_supply: TotalSupplyResponse = bank.supply_of(token_address.clone(), working_set);
         total_supply.amount
     };
@@ -91,6 +91,7 @@ fn transfer_initial_token() {
             "Insufficient funds for sov1h5567we4l0ne5vyrkvqd6jq5qp2cs7sa780vut0vrwr8pytwrzess8mu2s",
             error.to_string()
         );
+        assert_eq!(error.code(), 1);
     }
 
     // Non existent token
@@ -201,7 +202,11 @@ fn transfer_initial_token() {
 fn transfer_deployed_token() {
     let bank = Bank::<C>::new();
     let mut working_set = WorkingSet::new(ProverStorage::temporary());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        native_token: None,
+        tokens: vec![],
+        authorized_modules: vec![],
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let sender_address = generate_address("just_sender");
@@ -216,11 +221,11 @@ fn transfer_deployed_token() {
 
     // Preparation
     let query_user_balance =
-        |user_address: Address, working_set: &mut WorkingSet<Storage>| -> Option<u64> {
+        |user_address: Address, working_set: &mut WorkingSet<Storage>| -> Option<Amount> {
             bank.get_balance_of(user_address, token_address.clone(), working_set)
         };
 
-    let query_total_supply = |working_set: &mut WorkingSet<Storage>| -> Option<u64> {
+    let query_total_supply = |working_set: &mut WorkingSet<Storage>| -> Option<Amount> {
         let total_supply: TotalSupplyResponse = bank.supply_of(token_address.clone(), working_set);
         total_supply.amount
     };
@@ -237,9 +242,13 @@ fn transfer_deployed_token() {
     let mint_message = CallMessage::CreateToken {
         salt,
         token_name,
+        symbol: "TKN".to_owned(),
+        decimals: 0,
+        uri: None,
         initial_balance,
         minter_address: sender_address.clone(),
         authorized_minters: vec![sender_address.clone()],
+        supply_cap: None,
     };
     bank.call(mint_message, &sender_context, &mut working_set)
         .expect("Failed to mint token");
@@ -278,3 +287,121 @@ fn transfer_deployed_token() {
     let total_supply_after = query_total_supply(&mut working_set);
     assert_eq!(total_supply_before, total_supply_after);
 }
+
+mod caller_module {
+    use sov_modules_macros::ModuleInfo;
+
+    #[derive(ModuleInfo)]
+    pub struct CallerModule<C: sov_modules_api::Context> {
+        #[address]
+        pub address: C::Address,
+
+        #[module]
+        pub bank: sov_bank::Bank<C>,
+    }
+
+    /// A module which isn't one of the `authorized_modules` of the bank
+    #[derive(ModuleInfo)]
+    pub struct UnauthorizedModule<C: sov_modules_api::Context> {
+        #[address]
+        pub address: C::Address,
+    }
+}
+
+#[test]
+fn transfer_from_requires_authorization() {
+    let initial_balance = 100;
+    let caller = caller_module::CallerModule::<C>::new();
+    let mut bank_config = create_bank_config_with_token(3, initial_balance);
+    bank_config.authorized_modules = vec![caller.address().clone()];
+    let mut working_set = WorkingSet::new(ProverStorage::temporary());
+    caller.bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let token_address =
+        create_token_address::<C>(&bank_config.tokens[0].token_name, &DEPLOYER, SALT);
+    let owner_address = bank_config.tokens[0].address_and_balances[0].0.clone();
+    let other_address = bank_config.tokens[0].address_and_balances[1].0.clone();
+    let coins = Coins {
+        amount: 10,
+        token_address: token_address.clone(),
+    };
+
+    // Users can't move the funds of other users
+    let err = caller
+        .bank
+        .call(
+            CallMessage::TransferFrom {
+                from: owner_address.clone(),
+                to: other_address.clone(),
+                coins: coins.clone(),
+            },
+            &C::new(other_address.clone()),
+            &mut working_set,
+        )
+        .unwrap_err();
+    assert_eq!(err.code(), 3);
+
+    // Neither can modules which aren't authorized and have no allowance
+    let unauthorized = caller_module::UnauthorizedModule::<C>::new();
+    let err = caller
+        .bank
+        .transfer_from(
+            &owner_address,
+            unauthorized.address(),
+            coins.clone(),
+            &unauthorized,
+            &mut working_set,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<BankError>(),
+        Some(&BankError::UnauthorizedTransfer(
+            unauthorized.address().to_string()
+        ))
+    );
+    assert_eq!(
+        caller.bank.get_balance_of(
+            owner_address.clone(),
+            token_address.clone(),
+            &mut working_set
+        ),
+        Some(initial_balance)
+    );
+
+    // Authorized modules can
+    caller
+        .bank
+        .transfer_from(
+            &owner_address,
+            &other_address,
+            coins.clone(),
+            &caller,
+            &mut working_set,
+        )
+        .expect("Transfer from an authorized module failed");
+    assert_eq!(
+        caller.bank.get_balance_of(
+            other_address.clone(),
+            token_address.clone(),
+            &mut working_set
+        ),
+        Some(initial_balance + 10)
+    );
+
+    // Calls made by a module are sent from its own address
+    let err = caller
+        .call_module(
+            &caller.bank,
+            CallMessage::Transfer {
+                to: other_address,
+                coins,
+            },
+            &mut working_set,
+        )
+        .unwrap_err();
+    assert_eq!(err.code(), 1);
+    assert_eq!(
+        err.to_string(),
+        BankError::InsufficientFunds(caller.address().to_string()).to_string()
+    );
+}
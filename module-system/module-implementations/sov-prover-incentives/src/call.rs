@@ -1,6 +1,6 @@
 use anyhow::Result;
 use borsh::{BorshDeserialize, BorshSerialize};
-use sov_bank::Coins;
+use sov_bank::{Amount, Coins};
 use sov_modules_api::CallResponse;
 use sov_rollup_interface::zk::traits::Zkvm;
 use sov_state::WorkingSet;
@@ -13,7 +13,7 @@ use crate::ProverIncentives;
 // TODO: allow call messages to borrow data
 // https://github.com/Sovereign-Labs/sovereign/issues/274
 pub enum CallMessage {
-    BondProver(u64),
+    BondProver(Amount),
     UnbondProver,
     VerifyProof(Vec<u8>),
 }
@@ -23,7 +23,7 @@ impl<C: sov_modules_api::Context, Vm: Zkvm> ProverIncentives<C, Vm> {
     /// during genesis when no context is available.
     pub(super) fn bond_prover_helper(
         &self,
-        bond_amount: u64,
+        bond_amount: Amount,
         prover: &C::Address,
         working_set: &mut WorkingSet<C::Storage>,
     ) -> Result<CallResponse> {
@@ -37,7 +37,7 @@ impl<C: sov_modules_api::Context, Vm: Zkvm> ProverIncentives<C, Vm> {
             amount: bond_amount,
         };
         self.bank
-            .transfer_from(prover, &self.address, coins, working_set)?;
+            .transfer_from(prover, &self.address, coins, self, working_set)?;
 
         // Update our record of the total bonded amount for the sender.
         // This update is infallible, so no value can be destroyed.
@@ -60,7 +60,7 @@ impl<C: sov_modules_api::Context, Vm: Zkvm> ProverIncentives<C, Vm> {
     /// Try to bond the requested amount of coins from context.sender()
     pub(crate) fn bond_prover(
         &self,
-        bond_amount: u64,
+        bond_amount: Amount,
         context: &C,
         working_set: &mut WorkingSet<C::Storage>,
     ) -> Result<sov_modules_api::CallResponse> {
@@ -87,7 +87,7 @@ impl<C: sov_modules_api::Context, Vm: Zkvm> ProverIncentives<C, Vm> {
             // Try to unbond the entire balance
             // If the unbonding fails, no state is changed
             self.bank
-                .transfer_from(&self.address, context.sender(), coins, working_set)?;
+                .transfer_from(&self.address, context.sender(), coins, self, working_set)?;
 
             // Update our internal tracking of the total bonded amount for the sender.
             self.bonded_provers.set(context.sender(), &0, working_set);
@@ -8,6 +8,7 @@ mod tests;
 pub mod query;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use sov_bank::Amount;
 use sov_modules_api::{Context, Error};
 use sov_modules_macros::ModuleInfo;
 use sov_rollup_interface::zk::traits::Zkvm;
@@ -17,11 +18,11 @@ pub struct ProverIncentivesConfig<C: Context, Vm: Zkvm> {
     /// The address of the token to be used for bonding.
     bonding_token_address: C::Address,
     /// The minimum bond for a prover.
-    minimum_bond: u64,
+    minimum_bond: Amount,
     /// A code commitment to be used for verifying proofs
     commitment_of_allowed_verifier_method: Vm::CodeCommitment,
     /// A list of initial provers and their bonded amount.
-    initial_provers: Vec<(C::Address, u64)>,
+    initial_provers: Vec<(C::Address, Amount)>,
 }
 
 /// A wrapper around a code commitment which implements borsh
@@ -65,11 +66,11 @@ pub struct ProverIncentives<C: sov_modules_api::Context, Vm: Zkvm> {
 
     /// The set of registered provers and their bonded amount.
     #[state]
-    pub bonded_provers: sov_state::StateMap<C::Address, u64>,
+    pub bonded_provers: sov_state::StateMap<C::Address, Amount>,
 
     /// The minimum bond for a prover to be eligble for onchain verification
     #[state]
-    pub minimum_bond: sov_state::StateValue<u64>,
+    pub minimum_bond: sov_state::StateValue<Amount>,
 
     /// Reference to the Bank module.
     #[module]
@@ -1,11 +1,12 @@
 use super::ProverIncentives;
 use serde::{Deserialize, Serialize};
+use sov_bank::Amount;
 use sov_rollup_interface::zk::traits::Zkvm;
 use sov_state::WorkingSet;
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
 pub struct Response {
-    pub value: u64,
+    pub value: Amount,
 }
 
 impl<C: sov_modules_api::Context, Vm: Zkvm> ProverIncentives<C, Vm> {
@@ -1,3 +1,4 @@
+use sov_bank::Amount;
 use sov_modules_api::{default_context::DefaultContext, Address, Hasher, Module, ModuleInfo, Spec};
 use sov_rollup_interface::mocks::{MockCodeCommitment, MockProof, MockZkvm};
 use sov_state::{ProverStorage, WorkingSet};
@@ -6,7 +7,7 @@ use crate::ProverIncentives;
 
 type C = DefaultContext;
 
-const BOND_AMOUNT: u64 = 1000;
+const BOND_AMOUNT: Amount = 1000;
 const MOCK_CODE_COMMITMENT: MockCodeCommitment = MockCodeCommitment([0u8; 32]);
 
 pub fn generate_address(key: &str) -> <C as Spec>::Address {
@@ -19,12 +20,18 @@ fn create_bank_config() -> (sov_bank::BankConfig<C>, <C as Spec>::Address) {
 
     let token_config = sov_bank::TokenConfig {
         token_name: "InitialToken".to_owned(),
+        symbol: "INIT".to_owned(),
+        decimals: 0,
+        uri: None,
+        supply_cap: None,
         address_and_balances: vec![(prover_address.clone(), BOND_AMOUNT * 5)],
     };
 
     (
         sov_bank::BankConfig {
+            native_token: None,
             tokens: vec![token_config],
+            authorized_modules: vec![ProverIncentives::<C, MockZkvm>::new().address().clone()],
         },
         prover_address,
     )
@@ -28,7 +28,7 @@ impl<C: Context> ApplyBlobHooks for Sequencer<C> {
         let coins = self.coins_to_lock.get_or_err(working_set)?;
 
         self.bank
-            .transfer_from(sequencer, locker, coins, working_set)?;
+            .transfer_from(sequencer, locker, coins, self, working_set)?;
 
         Ok(())
     }
@@ -43,7 +43,7 @@ impl<C: Context> ApplyBlobHooks for Sequencer<C> {
         let coins = self.coins_to_lock.get_or_err(working_set)?;
 
         self.bank
-            .transfer_from(locker, sequencer, coins, working_set)?;
+            .transfer_from(locker, sequencer, coins, self, working_set)?;
 
         Ok(())
     }
@@ -1,5 +1,6 @@
 #[cfg(feature = "native")]
 use crate::Sequencer;
+use sov_bank::Amount;
 use sov_modules_api::AddressBech32;
 #[cfg(feature = "native")]
 use sov_modules_api::Context;
@@ -11,7 +12,7 @@ use sov_state::WorkingSet;
 #[derive(Debug, Eq, PartialEq)]
 pub struct Data {
     pub address: AddressBech32,
-    pub balance: u64,
+    pub balance: Amount,
 }
 
 #[cfg_attr(feature = "native", derive(serde::Deserialize, serde::Serialize))]
@@ -1,3 +1,4 @@
+use sov_bank::Amount;
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::hooks::ApplyBlobHooks;
 use sov_modules_api::Hasher;
@@ -10,8 +11,8 @@ use crate::{Sequencer, SequencerConfig};
 type C = DefaultContext;
 
 const SEQUENCER_DA_ADDRESS: [u8; 32] = [0; 32];
-const INITIAL_BALANCE: u64 = 201;
-const LOCKED_AMOUNT: u64 = 200;
+const INITIAL_BALANCE: Amount = 201;
+const LOCKED_AMOUNT: Amount = 200;
 
 struct TestSequencer {
     bank: sov_bank::Bank<C>,
@@ -53,12 +54,18 @@ fn create_bank_config() -> (sov_bank::BankConfig<C>, <C as Spec>::Address) {
 
     let token_config = sov_bank::TokenConfig {
         token_name: "InitialToken".to_owned(),
+        symbol: "INIT".to_owned(),
+        decimals: 0,
+        uri: None,
+        supply_cap: None,
         address_and_balances: vec![(seq_address.clone(), INITIAL_BALANCE)],
     };
 
     (
         sov_bank::BankConfig {
+            native_token: None,
             tokens: vec![token_config],
+            authorized_modules: vec![Sequencer::<C>::new().address().clone()],
         },
         seq_address,
     )
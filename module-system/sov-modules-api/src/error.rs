@@ -7,4 +7,40 @@ pub enum Error {
     /// Custom error thrown by a module.
     #[error(transparent)]
     ModuleError(#[from] anyhow::Error),
+    /// Typed error thrown by a module, with a stable code.
+    #[error("{0}")]
+    CodedError(Box<dyn ModuleError>),
+}
+
+/// An error type of a module, whose variants have stable numeric codes. Clients identify errors by
+/// the address of the module which returned them and their code, so codes must never be reused
+/// for a different error. Usually derived with `#[derive(ModuleError)]` from `sov-modules-macros`.
+pub trait ModuleError: std::error::Error + Send + Sync + 'static {
+    /// The code of the error. Code 0 is reserved for errors without a code.
+    fn code(&self) -> u32;
+}
+
+impl<E: ModuleError> From<E> for Error {
+    fn from(error: E) -> Self {
+        Error::CodedError(Box::new(error))
+    }
+}
+
+impl Error {
+    /// Converts an error returned by a module, keeping the code of the `E` it wraps if there is one.
+    /// This lets modules return `E` through `anyhow::Result`.
+    pub fn from_anyhow<E: ModuleError>(error: anyhow::Error) -> Self {
+        match error.downcast::<E>() {
+            Ok(error) => error.into(),
+            Err(error) => Error::ModuleError(error),
+        }
+    }
+
+    /// The code of the error, or 0 if it doesn't have one.
+    pub fn code(&self) -> u32 {
+        match self {
+            Error::ModuleError(_) => 0,
+            Error::CodedError(error) => error.code(),
+        }
+    }
 }
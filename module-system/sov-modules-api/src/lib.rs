@@ -7,6 +7,7 @@ mod dispatch;
 mod encode;
 mod error;
 pub mod hooks;
+pub mod migration;
 mod prefix;
 mod response;
 mod serde_address;
@@ -17,9 +18,11 @@ pub use crate::bech32::AddressBech32;
 use borsh::{BorshDeserialize, BorshSerialize};
 use core::fmt::{self, Debug, Display};
 pub use dispatch::{DispatchCall, Genesis};
-pub use error::Error;
+pub use error::{Error, ModuleError};
 pub use jmt::SimpleHasher as Hasher;
-pub use prefix::Prefix;
+pub use prefix::{
+    assert_unique_prefixes, Prefix, RESERVED_PREFIXES, SLOT_HEIGHT_PREFIX, STATE_VERSIONS_PREFIX,
+};
 pub use response::CallResponse;
 pub use sov_rollup_interface::traits::AddressTrait;
 use sov_state::{Storage, Witness, WorkingSet};
@@ -216,11 +219,30 @@ pub trait Module {
 pub trait ModuleInfo {
     type Context: Context;
 
+    /// Prefixes of the state variables of the module, excluding the ones of its nested modules.
+    const STATE_PREFIXES: &'static [Prefix];
+
     /// Module constructor.
     fn new() -> Self;
 
     /// Returns address of the module.
     fn address(&self) -> &<Self::Context as Spec>::Address;
+
+    /// The context of the calls made by this module to other modules: its sender is the address of this
+    /// module, so that the callee can authorize the call as it authorizes calls from users.
+    fn caller_context(&self) -> Self::Context {
+        Self::Context::new(self.address().clone())
+    }
+
+    /// Calls `module` on behalf of this module, see [`ModuleInfo::caller_context`].
+    fn call_module<M: Module<Context = Self::Context>>(
+        &self,
+        module: &M,
+        message: M::CallMessage,
+        working_set: &mut WorkingSet<<Self::Context as Spec>::Storage>,
+    ) -> Result<CallResponse, Error> {
+        module.call(message, &self.caller_context(), working_set)
+    }
 }
 
 /// A StateTransitionRunner needs to implement this if
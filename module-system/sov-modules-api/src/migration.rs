@@ -0,0 +1,139 @@
+//! State migrations, which change the layout of the state of a module once a rollup is live.
+//!
+//! A module declares the version of its state layout with [`StateMigrations`], and the runtime schedules the
+//! migrations of its modules at given slot heights with [`RuntimeMigrations`]. The migrations run at the
+//! beginning of their activation slot, against the working set of the slot, so they are executed (and
+//! proven) the same way in native and zk mode.
+use anyhow::{bail, ensure};
+use sov_state::{StateMap, WorkingSet};
+
+use crate::{Context, ModuleInfo, Spec, STATE_VERSIONS_PREFIX};
+
+/// Migrates the state of a module from one version to the next one.
+pub type MigrationFn<M> =
+    fn(&M, &mut WorkingSet<<<M as ModuleInfo>::Context as Spec>::Storage>) -> anyhow::Result<()>;
+
+/// Implemented by modules whose state layout changed after launch.
+pub trait StateMigrations: ModuleInfo + Sized {
+    /// The version of the state layout expected by the module. Modules start at version 0, and each
+    /// migration increases the version by one.
+    const STATE_VERSION: u32;
+
+    /// The migrations of the module, in order: `migrations()[v]` migrates the state from version `v` to
+    /// version `v + 1`. Must contain `STATE_VERSION` migrations.
+    fn migrations() -> Vec<MigrationFn<Self>>;
+}
+
+/// An object safe view of [`StateMigrations`], so that the runtime can schedule the migrations of
+/// modules of different types.
+pub trait MigratableModule<C: Context> {
+    /// The current version of the state of the module.
+    fn state_version(&self, working_set: &mut WorkingSet<C::Storage>) -> u32;
+
+    /// Migrates the state of the module to `version`. Does nothing if the state is already at `version`
+    /// or later, and fails if the state isn't at `version - 1`.
+    fn migrate(&self, version: u32, working_set: &mut WorkingSet<C::Storage>)
+        -> anyhow::Result<()>;
+
+    /// Records that the state of the module is at the latest version. Called at genesis, since modules
+    /// initialize their state with the latest layout.
+    fn init_state_version(&self, working_set: &mut WorkingSet<C::Storage>);
+}
+
+impl<M: StateMigrations> MigratableModule<M::Context> for M {
+    fn state_version(&self, working_set: &mut WorkingSet<<M::Context as Spec>::Storage>) -> u32 {
+        state_versions::<M::Context>()
+            .get(self.address(), working_set)
+            .unwrap_or_default()
+    }
+
+    fn migrate(
+        &self,
+        version: u32,
+        working_set: &mut WorkingSet<<M::Context as Spec>::Storage>,
+    ) -> anyhow::Result<()> {
+        let current_version = self.state_version(working_set);
+        if current_version >= version {
+            return Ok(());
+        }
+        ensure!(
+            version <= M::STATE_VERSION,
+            "Module {} only supports state versions up to {}, can't migrate to version {}",
+            self.address(),
+            M::STATE_VERSION,
+            version
+        );
+        ensure!(
+            current_version + 1 == version,
+            "The state of module {} is at version {}, can't migrate to version {}",
+            self.address(),
+            current_version,
+            version
+        );
+
+        let migration = match M::migrations().get(current_version as usize) {
+            Some(migration) => *migration,
+            None => bail!(
+                "Module {} has no migration to version {}",
+                self.address(),
+                version
+            ),
+        };
+        migration(self, working_set)?;
+        state_versions::<M::Context>().set(self.address(), &version, working_set);
+        Ok(())
+    }
+
+    fn init_state_version(&self, working_set: &mut WorkingSet<<M::Context as Spec>::Storage>) {
+        state_versions::<M::Context>().set(self.address(), &M::STATE_VERSION, working_set);
+    }
+}
+
+/// A migration of a module of the runtime `RT`, which runs at the beginning of the slot `activation_slot`.
+pub struct ScheduledMigration<RT: RuntimeMigrations> {
+    /// The height of the slot at which the migration runs. The first slot after genesis has height 0.
+    pub activation_slot: u64,
+    /// The version the state of the module is migrated to.
+    pub version: u32,
+    /// Returns the migrated module, e.g. `|runtime| &runtime.bank`.
+    pub module: fn(&RT) -> &dyn MigratableModule<RT::Context>,
+}
+
+/// Implemented by the runtime to schedule the migrations of its modules.
+pub trait RuntimeMigrations: Sized {
+    type Context: Context;
+
+    /// The migrations of the runtime. Migrations must stay in the schedule once the rollup is live, so that
+    /// new nodes replay them when syncing from genesis.
+    fn scheduled_migrations() -> Vec<ScheduledMigration<Self>>;
+}
+
+/// Records that the state of the migrated modules is at the latest version, see
+/// [`MigratableModule::init_state_version`].
+pub fn init_state_versions<RT: RuntimeMigrations>(
+    runtime: &RT,
+    working_set: &mut WorkingSet<<RT::Context as Spec>::Storage>,
+) {
+    for migration in RT::scheduled_migrations() {
+        (migration.module)(runtime).init_state_version(working_set);
+    }
+}
+
+/// Runs the migrations activated at `slot_height`, in the order of the schedule.
+pub fn run_migrations<RT: RuntimeMigrations>(
+    runtime: &RT,
+    slot_height: u64,
+    working_set: &mut WorkingSet<<RT::Context as Spec>::Storage>,
+) -> anyhow::Result<()> {
+    for migration in RT::scheduled_migrations()
+        .into_iter()
+        .filter(|migration| migration.activation_slot == slot_height)
+    {
+        (migration.module)(runtime).migrate(migration.version, working_set)?;
+    }
+    Ok(())
+}
+
+fn state_versions<C: Context>() -> StateMap<C::Address, u32> {
+    StateMap::new(STATE_VERSIONS_PREFIX.into())
+}
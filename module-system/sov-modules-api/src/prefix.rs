@@ -6,47 +6,131 @@ const DOMAIN_SEPARATOR: [u8; 1] = [47];
 /// A unique identifier for each state variable in a module.
 #[derive(Debug, PartialEq, Eq)]
 pub struct Prefix {
-    module_path: &'static str,
+    // `None` if the module overrides its prefix with `#[module_info(prefix = "...")]`, in which case
+    // `module_name` holds the override.
+    module_path: Option<&'static str>,
     module_name: &'static str,
     storage_name: Option<&'static str>,
 }
 
 impl Prefix {
-    pub fn new_storage(
+    pub const fn new_storage(
         module_path: &'static str,
         module_name: &'static str,
         storage_name: &'static str,
     ) -> Self {
         Self {
-            module_path,
+            module_path: Some(module_path),
             module_name,
             storage_name: Some(storage_name),
         }
     }
 
-    pub fn new_module(module_path: &'static str, module_name: &'static str) -> Self {
+    pub const fn new_module(module_path: &'static str, module_name: &'static str) -> Self {
         Self {
-            module_path,
+            module_path: Some(module_path),
             module_name,
             storage_name: None,
         }
     }
 
+    /// Creates the prefix of a state variable of a module with a custom prefix. Unlike the default
+    /// prefixes it doesn't depend on the module path, so the module can be moved or renamed
+    /// without orphaning its state.
+    pub const fn new_custom_storage(
+        module_prefix: &'static str,
+        storage_name: &'static str,
+    ) -> Self {
+        Self {
+            module_path: None,
+            module_name: module_prefix,
+            storage_name: Some(storage_name),
+        }
+    }
+
+    /// Creates the prefix of a module with a custom prefix, see [`Prefix::new_custom_storage`].
+    pub const fn new_custom_module(module_prefix: &'static str) -> Self {
+        Self {
+            module_path: None,
+            module_name: module_prefix,
+            storage_name: None,
+        }
+    }
+
+    /// Returns `true` if the bytes of one prefix start with the bytes of the other, in which case the
+    /// keys of one state variable can overlap with the other's. Usable in const contexts.
+    pub const fn collides_with(&self, other: &Prefix) -> bool {
+        let self_len = self.encoded_len();
+        let other_len = other.encoded_len();
+        let len = if self_len < other_len {
+            self_len
+        } else {
+            other_len
+        };
+
+        let mut i = 0;
+        while i < len {
+            if self.encoded_byte(i) != other.encoded_byte(i) {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// The length of the bytes returned by [`Prefix::combine_prefix`].
+    const fn encoded_len(&self) -> usize {
+        let mut len = self.module_name.len() + DOMAIN_SEPARATOR.len();
+        if let Some(module_path) = self.module_path {
+            len += module_path.len() + DOMAIN_SEPARATOR.len();
+        }
+        if let Some(storage_name) = self.storage_name {
+            len += storage_name.len() + DOMAIN_SEPARATOR.len();
+        }
+        len
+    }
+
+    /// The byte at `index` of the bytes returned by [`Prefix::combine_prefix`], without allocating them.
+    const fn encoded_byte(&self, index: usize) -> u8 {
+        let index = match self.module_path {
+            Some(module_path) => match part_byte(module_path, index) {
+                Ok(byte) => return byte,
+                Err(index) => index,
+            },
+            None => index,
+        };
+        let index = match part_byte(self.module_name, index) {
+            Ok(byte) => return byte,
+            Err(index) => index,
+        };
+        match self.storage_name {
+            Some(storage_name) => match part_byte(storage_name, index) {
+                Ok(byte) => byte,
+                Err(_) => panic!("Index out of the bounds of the prefix"),
+            },
+            None => panic!("Index out of the bounds of the prefix"),
+        }
+    }
+
     fn combine_prefix(&self) -> Vec<u8> {
         let storage_name_len = self
             .storage_name
             .map(|name| name.len() + DOMAIN_SEPARATOR.len())
             .unwrap_or_default();
 
+        let module_path_len = self
+            .module_path
+            .map(|path| path.len() + DOMAIN_SEPARATOR.len())
+            .unwrap_or_default();
+
         let mut combined_prefix = Vec::with_capacity(
-            self.module_path.len()
-                + self.module_name.len()
-                + 2 * DOMAIN_SEPARATOR.len()
-                + storage_name_len,
+            module_path_len + self.module_name.len() + DOMAIN_SEPARATOR.len() + storage_name_len,
         );
 
-        combined_prefix.extend(self.module_path.as_bytes());
-        combined_prefix.extend(DOMAIN_SEPARATOR);
+        if let Some(module_path) = self.module_path {
+            combined_prefix.extend(module_path.as_bytes());
+            combined_prefix.extend(DOMAIN_SEPARATOR);
+        }
         combined_prefix.extend(self.module_name.as_bytes());
         combined_prefix.extend(DOMAIN_SEPARATOR);
         if let Some(storage_name) = self.storage_name {
@@ -62,6 +146,56 @@ impl Prefix {
     }
 }
 
+/// Panics if two of the provided prefixes collide. This is evaluated at compile time by the
+/// `Genesis` derive, to ensure that no two state variables of a runtime share the same storage.
+pub const fn assert_unique_prefixes(modules: &[&[Prefix]]) {
+    let mut i = 0;
+    while i < modules.len() {
+        let mut j = 0;
+        while j < modules[i].len() {
+            let prefix = &modules[i][j];
+            // Compare with the following prefixes of the same module, then with all the other modules.
+            let mut k = i;
+            let mut l = j + 1;
+            while k < modules.len() {
+                while l < modules[k].len() {
+                    if prefix.collides_with(&modules[k][l]) {
+                        panic!("Two state variables of the runtime resolve to the same prefix");
+                    }
+                    l += 1;
+                }
+                k += 1;
+                l = 0;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+/// The prefix of the state versions stored by [`crate::migration`].
+pub const STATE_VERSIONS_PREFIX: Prefix =
+    Prefix::new_custom_storage("migrations", "state_versions");
+
+/// The prefix of the slot height stored by the `AppTemplate` of `sov-modules-stf-template`.
+pub const SLOT_HEIGHT_PREFIX: Prefix = Prefix::new_custom_storage("app_template", "slot_height");
+
+/// The prefixes of the state stored outside of the modules. The `Genesis` derive checks that no state
+/// variable of the runtime collides with them.
+pub const RESERVED_PREFIXES: &[Prefix] = &[STATE_VERSIONS_PREFIX, SLOT_HEIGHT_PREFIX];
+
+/// Returns the byte at `index` of `part` followed by the separator, or the remaining index if it's past them.
+const fn part_byte(part: &str, index: usize) -> Result<u8, usize> {
+    let part = part.as_bytes();
+    if index < part.len() {
+        Ok(part[index])
+    } else if index < part.len() + DOMAIN_SEPARATOR.len() {
+        Ok(DOMAIN_SEPARATOR[index - part.len()])
+    } else {
+        Err(index - part.len() - DOMAIN_SEPARATOR.len())
+    }
+}
+
 impl From<Prefix> for sov_state::Prefix {
     fn from(prefix: Prefix) -> Self {
         let combined_prefix = prefix.combine_prefix();
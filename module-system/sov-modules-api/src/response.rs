@@ -1,3 +1,35 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use sov_rollup_interface::stf::Event;
+
 /// Response type for the `Module::call` method.
-#[derive(Default)]
-pub struct CallResponse {}
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CallResponse {
+    /// The data returned by the call, serialized with borsh. Empty if the call doesn't return anything.
+    /// It's persisted in the receipt of the transaction.
+    pub data: Vec<u8>,
+    /// Events emitted by the call, in addition to the ones it added to the working set.
+    pub events: Vec<Event>,
+}
+
+impl CallResponse {
+    /// Creates a response which returns `data`.
+    pub fn with_data<T: BorshSerialize>(data: &T) -> Self {
+        Self {
+            data: data
+                .try_to_vec()
+                .expect("Serialization to vec is infallible"),
+            events: Vec::new(),
+        }
+    }
+
+    /// Attaches an event to the response.
+    pub fn with_event(mut self, key: &str, value: &str) -> Self {
+        self.events.push(Event::new(key, value));
+        self
+    }
+
+    /// Deserializes the data returned by the call.
+    pub fn data<T: BorshDeserialize>(&self) -> Result<T, std::io::Error> {
+        T::try_from_slice(&self.data)
+    }
+}
@@ -44,3 +44,99 @@ fn test_hex_conversion() {
     let deserialized_pub_key = DefaultPrivateKey::from_hex(&hex).unwrap().pub_key();
     assert_eq!(priv_key.pub_key(), deserialized_pub_key)
 }
+
+#[derive(Debug, thiserror::Error)]
+enum TestError {
+    #[error("Value {0} is too large")]
+    TooLarge(u64),
+}
+
+impl crate::ModuleError for TestError {
+    fn code(&self) -> u32 {
+        match self {
+            TestError::TooLarge(_) => 1,
+        }
+    }
+}
+
+#[test]
+fn test_error_codes() {
+    let error: crate::Error = TestError::TooLarge(5).into();
+    assert_eq!(error.code(), 1);
+    assert_eq!(error.to_string(), "Value 5 is too large");
+
+    let wrapped = anyhow::Error::new(TestError::TooLarge(5)).context("Setting the value");
+    let error = crate::Error::from_anyhow::<TestError>(wrapped);
+    assert_eq!(error.code(), 1);
+
+    let error = crate::Error::from_anyhow::<TestError>(anyhow::anyhow!("Other error"));
+    assert_eq!(error.code(), 0);
+    assert_eq!(error.to_string(), "Other error");
+}
+
+#[test]
+fn test_call_response_data() {
+    let response =
+        crate::CallResponse::with_data(&(7u64, "pool".to_string())).with_event("created", "pool");
+    assert_eq!(
+        response.data::<(u64, String)>().unwrap(),
+        (7, "pool".to_string())
+    );
+    assert_eq!(
+        response.events,
+        vec![sov_rollup_interface::stf::Event::new("created", "pool")]
+    );
+    assert!(crate::CallResponse::default().data::<u64>().is_err());
+}
+
+#[test]
+fn test_unique_prefixes() {
+    use crate::Prefix;
+
+    let bank = [
+        Prefix::new_custom_storage("bank", "tokens"),
+        Prefix::new_custom_storage("bank", "balances"),
+    ];
+    let other = [
+        Prefix::new_storage("other", "Bank", "tokens"),
+        Prefix::new_custom_storage("other", "tokens"),
+    ];
+    crate::assert_unique_prefixes(&[&bank, &other]);
+
+    assert!(bank[0].collides_with(&Prefix::new_custom_storage("bank", "tokens")));
+    assert!(!bank[0].collides_with(&Prefix::new_storage("bank", "token", "tokens")));
+    assert!(!bank[0].collides_with(&Prefix::new_custom_module("bank2")));
+    // "bank/tokens/" starts the keys of "bank/tokens/tokens/", in both directions
+    let nested = Prefix::new_storage("bank", "tokens", "tokens");
+    assert!(bank[0].collides_with(&nested));
+    assert!(nested.collides_with(&bank[0]));
+}
+
+#[test]
+#[should_panic(expected = "Two state variables of the runtime resolve to the same prefix")]
+fn test_colliding_prefixes() {
+    use crate::Prefix;
+
+    let bank = [Prefix::new_custom_storage("bank", "tokens")];
+    let other = [Prefix::new_custom_storage("bank", "tokens")];
+    crate::assert_unique_prefixes(&[&bank, &other]);
+}
+
+#[test]
+#[should_panic(expected = "Two state variables of the runtime resolve to the same prefix")]
+fn test_nested_prefixes_collide() {
+    use crate::Prefix;
+
+    let bank = [Prefix::new_custom_storage("a", "b")];
+    let other = [Prefix::new_storage("a", "b", "c")];
+    crate::assert_unique_prefixes(&[&bank, &other]);
+}
+
+#[test]
+#[should_panic(expected = "Two state variables of the runtime resolve to the same prefix")]
+fn test_prefix_colliding_with_reserved_prefix() {
+    use crate::Prefix;
+
+    let migrations = [Prefix::new_custom_module("migrations")];
+    crate::assert_unique_prefixes(&[crate::RESERVED_PREFIXES, &migrations]);
+}
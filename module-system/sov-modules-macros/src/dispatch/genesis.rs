@@ -31,16 +31,27 @@ impl GenesisMacro {
         let generic_param = parse_generic_params(&generics)?;
         let genesis_config = Self::make_genesis_config(&fields, &type_generics, &generic_param);
         let genesis_fn_body = Self::make_genesis_fn_body(&fields);
+        let module_types = fields.iter().map(|field| &field.ty);
 
         // Implements the Genesis trait
         Ok(quote::quote! {
             #genesis_config
 
+            impl #impl_generics #ident #type_generics #where_clause {
+                // Evaluating this constant fails if two state variables of the modules share a prefix,
+                // or if one of them shares a prefix with the state stored outside of the modules.
+                const _UNIQUE_STATE_PREFIXES: () = sov_modules_api::assert_unique_prefixes(&[
+                    sov_modules_api::RESERVED_PREFIXES,
+                    #(<#module_types as sov_modules_api::ModuleInfo>::STATE_PREFIXES),*
+                ]);
+            }
+
             impl #impl_generics sov_modules_api::Genesis for #ident #type_generics #where_clause {
                 type Context = #generic_param;
                 type Config = GenesisConfig #type_generics;
 
                 fn genesis(&self, config: &Self::Config, working_set: &mut sov_state::WorkingSet<<<Self as sov_modules_api::Genesis>::Context as sov_modules_api::Spec>::Storage>) -> core::result::Result<(), sov_modules_api::Error> {
+                    let () = Self::_UNIQUE_STATE_PREFIXES;
                     #(#genesis_fn_body)*
                     Ok(())
                 }
@@ -1,5 +1,6 @@
 #![feature(log_syntax)]
 mod dispatch;
+mod module_error;
 mod module_info;
 use dispatch::{
     dispatch_call::DispatchCallMacro, genesis::GenesisMacro, message_codec::MessageCodec,
@@ -29,10 +30,12 @@ use syn::parse_macro_input;
 /// ````
 /// ## Attributes
 ///
-///  * `state` - attribute for state members
+///  * `state` - attribute for state members, `#[state(key = "...")]` overrides the field name in the prefix
 ///  * `module` - attribute for module members
 ///  * `address` - attribute for module address
-#[proc_macro_derive(ModuleInfo, attributes(state, module, address))]
+///  * `module_info` - `#[module_info(prefix = "...")]` replaces the module path and name in the prefixes
+///    of the module, so that its state doesn't move when the module is moved or renamed
+#[proc_macro_derive(ModuleInfo, attributes(state, module, address, module_info))]
 pub fn module_info(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input);
 
@@ -40,6 +43,10 @@ pub fn module_info(input: TokenStream) -> TokenStream {
 }
 
 /// Derives the `sov-modules-api::Genesis` implementation for the underlying type.
+///
+/// The derived implementation fails to build if the prefix of a state variable of the modules starts
+/// with the prefix of another one, since their keys could overlap. The same holds for the
+/// `sov_modules_api::RESERVED_PREFIXES` of the state stored outside of the modules.
 #[proc_macro_derive(Genesis)]
 pub fn genesis(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input);
@@ -57,6 +64,29 @@ pub fn dispatch_call(input: TokenStream) -> TokenStream {
     handle_macro_error(call_macro.derive_dispatch_call(input))
 }
 
+/// Derives the `sov-modules-api::ModuleError` implementation for an error enum. Each variant must have a
+/// `#[code = N]` attribute with a unique, non-zero code.
+/// ## Example
+///
+/// ``` ignore
+///  #[derive(Debug, thiserror::Error, ModuleError)]
+///  pub enum TestError {
+///     #[error("Value {0} is too large")]
+///     #[code = 1]
+///     TooLarge(u64),
+///
+///     #[error("The value is frozen")]
+///     #[code = 2]
+///     Frozen,
+///  }
+/// ```
+#[proc_macro_derive(ModuleError, attributes(code))]
+pub fn module_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input);
+
+    handle_macro_error(module_error::derive_module_error(input))
+}
+
 /// Adds encoding functionality to the underlying type.
 #[proc_macro_derive(MessageCodec)]
 pub fn codec(input: TokenStream) -> TokenStream {
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use syn::{Data, DeriveInput, Lit, Meta};
+
+const CODE: &str = "code";
+
+pub(crate) fn derive_module_error(
+    input: DeriveInput,
+) -> Result<proc_macro::TokenStream, syn::Error> {
+    let DeriveInput {
+        data,
+        ident,
+        generics,
+        ..
+    } = input;
+
+    let variants = match data {
+        Data::Enum(data_enum) => data_enum.variants,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "The `ModuleError` macro supports enums only.",
+            ))
+        }
+    };
+
+    let mut codes = HashMap::new();
+    let mut match_legs = Vec::with_capacity(variants.len());
+    for variant in variants.iter() {
+        let variant_ident = &variant.ident;
+        let code = parse_code(variant)?;
+        if let Some(other) = codes.insert(code, variant_ident) {
+            return Err(syn::Error::new_spanned(
+                variant_ident,
+                format!("Code {} is already used by the variant `{}`.", code, other),
+            ));
+        }
+        match_legs.push(quote::quote! {
+            Self::#variant_ident { .. } => #code,
+        });
+    }
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote::quote! {
+        impl #impl_generics sov_modules_api::ModuleError for #ident #type_generics #where_clause {
+            fn code(&self) -> u32 {
+                match self {
+                    #(#match_legs)*
+                }
+            }
+        }
+    }
+    .into())
+}
+
+// Parses the `#[code = N]` attribute of a variant.
+fn parse_code(variant: &syn::Variant) -> Result<u32, syn::Error> {
+    let attr = variant
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident(CODE))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &variant.ident,
+                format!(
+                    "The variant `{}` is missing a `#[code = ...]` attribute.",
+                    variant.ident
+                ),
+            )
+        })?;
+
+    let code = match attr.parse_meta()? {
+        Meta::NameValue(name_value) => match name_value.lit {
+            Lit::Int(code) => code.base10_parse::<u32>()?,
+            lit => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "The code must be an integer literal.",
+                ))
+            }
+        },
+        meta => {
+            return Err(syn::Error::new_spanned(
+                meta,
+                "Expected an attribute of the form `#[code = ...]`.",
+            ))
+        }
+    };
+
+    if code == 0 {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "Code 0 is reserved for errors without a code.",
+        ));
+    }
+    Ok(code)
+}
@@ -1,7 +1,15 @@
 use super::dispatch::common::parse_generic_params;
 use proc_macro2::{self};
 use proc_macro2::{Ident, Span};
-use syn::{DataStruct, DeriveInput, ImplGenerics, PathArguments, TypeGenerics, WhereClause};
+use std::collections::HashSet;
+use syn::{
+    Attribute, DataStruct, DeriveInput, ImplGenerics, Lit, LitStr, Meta, NestedMeta, PathArguments,
+    TypeGenerics, WhereClause,
+};
+
+const MODULE_INFO: &str = "module_info";
+const PREFIX: &str = "prefix";
+const KEY: &str = "key";
 
 #[derive(Clone)]
 struct StructNamedField {
@@ -14,7 +22,8 @@ struct StructNamedField {
 #[derive(Clone)]
 enum FieldKind {
     Address(StructNamedField),
-    State(StructNamedField),
+    // The optional storage key set with `#[state(key = "...")]`.
+    State(StructNamedField, Option<String>),
     Module(StructNamedField),
 }
 
@@ -23,6 +32,8 @@ struct StructDef<'a> {
     impl_generics: ImplGenerics<'a>,
     type_generics: TypeGenerics<'a>,
     generic_param: &'a Ident,
+    // The custom prefix set with `#[module_info(prefix = "...")]`.
+    module_prefix: Option<String>,
 
     fields: Result<Vec<FieldKind>, syn::Error>,
     where_clause: Option<&'a WhereClause>,
@@ -32,6 +43,7 @@ pub(crate) fn derive_module_info(
     input: DeriveInput,
 ) -> Result<proc_macro::TokenStream, syn::Error> {
     let DeriveInput {
+        attrs,
         data,
         ident,
         generics,
@@ -39,6 +51,7 @@ pub(crate) fn derive_module_info(
     } = input;
 
     let generic_param = parse_generic_params(&generics)?;
+    let module_prefix = parse_module_prefix(&attrs)?;
 
     let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
     let fields = get_fields_from_struct(&data);
@@ -49,6 +62,7 @@ pub(crate) fn derive_module_info(
         impl_generics,
         type_generics,
         generic_param: &generic_param,
+        module_prefix,
         where_clause,
     };
 
@@ -69,7 +83,12 @@ impl<'a> StructDef<'a> {
         let fields = self.fields.clone()?;
 
         let impl_prefix_functions = fields.iter().filter_map(|field| match field {
-            FieldKind::State(field) => Some(make_prefix_func(field, &self.ident)),
+            FieldKind::State(field, key) => Some(make_prefix_func(
+                field,
+                key.as_deref(),
+                &self.ident,
+                self.module_prefix.as_deref(),
+            )),
             // Don't generate prefix functions for modules
             FieldKind::Module(_) => None,
             // Don't generate prefix functions for address
@@ -96,19 +115,27 @@ impl<'a> StructDef<'a> {
         let mut impl_self_init = Vec::default();
         let mut impl_self_body = Vec::default();
 
+        let mut state_prefixes = Vec::default();
+
         let mut module_address = None;
         for field in fields.iter() {
             match field {
-                FieldKind::State(field) => {
+                FieldKind::State(field, _) => {
                     impl_self_init.push(make_init_state(field)?);
                     impl_self_body.push(&field.ident);
+                    state_prefixes.push(prefix_func_ident(&field.ident));
                 }
                 FieldKind::Module(field) => {
                     impl_self_init.push(make_init_module(field)?);
                     impl_self_body.push(&field.ident);
                 }
                 FieldKind::Address(field) => {
-                    impl_self_init.push(make_init_address(field, &self.ident, module_address)?);
+                    impl_self_init.push(make_init_address(
+                        field,
+                        &self.ident,
+                        self.module_prefix.as_deref(),
+                        module_address,
+                    )?);
                     impl_self_body.push(&field.ident);
                     module_address = Some(&field.ident);
                 }
@@ -129,6 +156,8 @@ impl<'a> StructDef<'a> {
             impl #impl_generics sov_modules_api::ModuleInfo for #ident #type_generics #where_clause{
                 type Context = #generic_param;
 
+                const STATE_PREFIXES: &'static [sov_modules_api::Prefix] = &[#(Self::#state_prefixes()),*];
+
                 fn new() -> Self {
 
                     #(#impl_self_init)*
@@ -159,8 +188,74 @@ fn get_fields_from_struct(data: &syn::Data) -> Result<Vec<FieldKind>, syn::Error
     }
 }
 
+// Parses the optional `#[module_info(prefix = "...")]` attribute of the struct.
+fn parse_module_prefix(attrs: &[Attribute]) -> Result<Option<String>, syn::Error> {
+    let attr = match attrs.iter().find(|attr| attr.path.is_ident(MODULE_INFO)) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    match parse_name_value_arg(attr, PREFIX)? {
+        Some(prefix) => Ok(Some(validate_prefix_part(&prefix)?)),
+        None => Err(syn::Error::new_spanned(
+            attr,
+            "Expected an attribute of the form `#[module_info(prefix = \"...\")]`.",
+        )),
+    }
+}
+
+// Parses the optional `#[state(key = "...")]` argument of a state field.
+fn parse_state_key(attr: &Attribute) -> Result<Option<String>, syn::Error> {
+    parse_name_value_arg(attr, KEY)?
+        .map(|key| validate_prefix_part(&key))
+        .transpose()
+}
+
+// Parses an attribute of the form `#[attr]` or `#[attr(name = "...")]`.
+fn parse_name_value_arg(attr: &Attribute, name: &str) -> Result<Option<LitStr>, syn::Error> {
+    let list = match attr.parse_meta()? {
+        Meta::Path(_) => return Ok(None),
+        Meta::List(list) if list.nested.len() == 1 => list,
+        meta => {
+            return Err(syn::Error::new_spanned(
+                meta,
+                format!("Expected a single `{} = \"...\"` argument.", name),
+            ))
+        }
+    };
+
+    match list.nested.first() {
+        Some(NestedMeta::Meta(Meta::NameValue(name_value))) if name_value.path.is_ident(name) => {
+            match &name_value.lit {
+                Lit::Str(value) => Ok(Some(value.clone())),
+                lit => Err(syn::Error::new_spanned(
+                    lit,
+                    format!("The `{}` must be a string literal.", name),
+                )),
+            }
+        }
+        _ => Err(syn::Error::new_spanned(
+            list,
+            format!("Expected a single `{} = \"...\"` argument.", name),
+        )),
+    }
+}
+
+// Custom prefixes and keys are joined with the "/" separator, so they can't contain it.
+fn validate_prefix_part(value: &LitStr) -> Result<String, syn::Error> {
+    let value_str = value.value();
+    if value_str.is_empty() || value_str.contains('/') {
+        return Err(syn::Error::new_spanned(
+            value,
+            "Prefixes and keys must be non-empty and can't contain `/`.",
+        ));
+    }
+    Ok(value_str)
+}
+
 fn get_fields_from_data_struct(data_struct: &DataStruct) -> Result<Vec<FieldKind>, syn::Error> {
     let mut output_fields = Vec::default();
+    let mut storage_names = HashSet::new();
 
     for original_field in data_struct.fields.iter() {
         let field_ident = original_field
@@ -185,7 +280,15 @@ fn get_fields_from_data_struct(data_struct: &DataStruct) -> Result<Vec<FieldKind
             };
 
             if attribute.path.segments[0].ident == "state" {
-                output_fields.push(FieldKind::State(field));
+                let key = parse_state_key(attribute)?;
+                let storage_name = key.clone().unwrap_or_else(|| field_ident.to_string());
+                if !storage_names.insert(storage_name.clone()) {
+                    return Err(syn::Error::new_spanned(
+                        field_ident,
+                        format!("The state key `{}` is used more than once.", storage_name),
+                    ));
+                }
+                output_fields.push(FieldKind::State(field, key));
             } else if attribute.path.segments[0].ident == "module" {
                 output_fields.push(FieldKind::Module(field))
             } else if attribute.path.segments[0].ident == "address" {
@@ -209,21 +312,38 @@ fn prefix_func_ident(ident: &proc_macro2::Ident) -> proc_macro2::Ident {
 
 fn make_prefix_func(
     field: &StructNamedField,
+    key: Option<&str>,
     module_ident: &proc_macro2::Ident,
+    module_prefix: Option<&str>,
 ) -> proc_macro2::TokenStream {
     let field_ident = &field.ident;
     let prefix_func_ident = prefix_func_ident(field_ident);
+    let storage_name = match key {
+        Some(key) => quote::quote!(#key),
+        None => quote::quote!(stringify!(#field_ident)),
+    };
 
     // generates prefix functions:
-    //   fn _prefix_field_ident() -> sov_modules_api::Prefix {
+    //   const fn _prefix_field_ident() -> sov_modules_api::Prefix {
     //      let module_path = "some_module";
     //      sov_modules_api::Prefix::new_storage(module_path, module_name, field_ident)
     //   }
-    quote::quote! {
-        fn #prefix_func_ident() -> sov_modules_api::Prefix {
-            let module_path = module_path!();
-            sov_modules_api::Prefix::new_storage(module_path, stringify!(#module_ident), stringify!(#field_ident))
-        }
+    // or, if the module has a custom prefix:
+    //   const fn _prefix_field_ident() -> sov_modules_api::Prefix {
+    //      sov_modules_api::Prefix::new_custom_storage(module_prefix, field_ident)
+    //   }
+    match module_prefix {
+        Some(module_prefix) => quote::quote! {
+            const fn #prefix_func_ident() -> sov_modules_api::Prefix {
+                sov_modules_api::Prefix::new_custom_storage(#module_prefix, #storage_name)
+            }
+        },
+        None => quote::quote! {
+            const fn #prefix_func_ident() -> sov_modules_api::Prefix {
+                let module_path = module_path!();
+                sov_modules_api::Prefix::new_storage(module_path, stringify!(#module_ident), #storage_name)
+            }
+        },
     }
 }
 
@@ -290,9 +410,18 @@ fn make_init_module(field: &StructNamedField) -> Result<proc_macro2::TokenStream
 fn make_init_address(
     field: &StructNamedField,
     struct_ident: &Ident,
+    module_prefix: Option<&str>,
     address: Option<&Ident>,
 ) -> Result<proc_macro2::TokenStream, syn::Error> {
     let field_ident = &field.ident;
+    let prefix = match module_prefix {
+        Some(module_prefix) => quote::quote! {
+            sov_modules_api::Prefix::new_custom_module(#module_prefix)
+        },
+        None => quote::quote! {
+            sov_modules_api::Prefix::new_module(module_path!(), stringify!(#struct_ident))
+        },
+    };
 
     match address {
         Some(addr) => Err(syn::Error::new_spanned(
@@ -304,8 +433,7 @@ fn make_init_address(
         )),
         None => Ok(quote::quote! {
             use sov_modules_api::Hasher;
-            let module_path = module_path!();
-            let prefix = sov_modules_api::Prefix::new_module(module_path, stringify!(#struct_ident));
+            let prefix = #prefix;
             let #field_ident =
                 <Self::Context as sov_modules_api::Spec>::Address::try_from(&prefix.hash:: <Self::Context> ())
                     .unwrap_or_else(|e| panic!("ModuleInfo macro error, unable to create an Address for module: {}", e));
@@ -3,12 +3,15 @@ fn module_info_tests() {
     let t = trybuild::TestCases::new();
     t.pass("tests/module_info/parse.rs");
     t.pass("tests/module_info/mod_and_state.rs");
+    t.pass("tests/module_info/custom_prefix.rs");
     t.compile_fail("tests/module_info/field_missing_attribute.rs");
     t.compile_fail("tests/module_info/not_supported_attribute.rs");
     t.compile_fail("tests/module_info/derive_on_enum_not_supported.rs");
     t.compile_fail("tests/module_info/not_supported_type.rs");
     t.compile_fail("tests/module_info/second_addr_not_supported.rs");
     t.compile_fail("tests/module_info/missing_address.rs");
+    t.compile_fail("tests/module_info/duplicate_state_key.rs");
+    t.compile_fail("tests/module_info/invalid_prefix.rs");
 }
 
 #[test]
@@ -20,8 +23,26 @@ fn module_dispatch_tests() {
     t.compile_fail("tests/dispatch/missing_serialization.rs");
 }
 
+#[test]
+fn colliding_prefixes_tests() {
+    let t = trybuild::TestCases::new();
+    // The first test is compiled as the crate `trybuild000`, which its module prefixes depend on. The
+    // collision is only detected once the runtime is built, and the passing test makes trybuild build
+    // the tests instead of only checking them.
+    t.compile_fail("tests/dispatch/colliding_prefixes.rs");
+    t.pass("tests/dispatch/derive_genesis.rs");
+}
+
 #[test]
 fn rpc_tests() {
     let t = trybuild::TestCases::new();
     t.pass("tests/dispatch/derive_rpc.rs");
 }
+
+#[test]
+fn module_error_tests() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/module_error/derive_module_error.rs");
+    t.compile_fail("tests/module_error/duplicate_code.rs");
+    t.compile_fail("tests/module_error/missing_code.rs");
+}
@@ -0,0 +1,91 @@
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::{CallResponse, Context, Error, Module, ModuleInfo};
+use sov_modules_macros::{Genesis, ModuleInfo};
+use sov_state::{ProverStorage, StateMap, WorkingSet};
+
+pub mod colliding {
+    use super::*;
+
+    // The balances are stored under "trybuild000::colliding/Bank/balances/".
+    #[derive(ModuleInfo)]
+    pub struct Bank<C: Context> {
+        #[address]
+        pub address: C::Address,
+
+        #[state]
+        pub balances: StateMap<u32, u32>,
+    }
+}
+
+// The tokens are stored under "trybuild000::colliding/Bank/", which starts the keys of the balances.
+#[derive(ModuleInfo)]
+#[module_info(prefix = "trybuild000::colliding")]
+pub struct Impostor<C: Context> {
+    #[address]
+    pub address: C::Address,
+
+    #[state(key = "Bank")]
+    pub tokens: StateMap<u32, u32>,
+}
+
+impl<C: Context> Module for colliding::Bank<C> {
+    type Context = C;
+    type Config = ();
+    type CallMessage = ();
+
+    fn genesis(&self, _config: &(), working_set: &mut WorkingSet<C::Storage>) -> Result<(), Error> {
+        self.balances.set(&0, &1, working_set);
+        Ok(())
+    }
+
+    fn call(
+        &self,
+        _msg: (),
+        _context: &C,
+        _working_set: &mut WorkingSet<C::Storage>,
+    ) -> Result<CallResponse, Error> {
+        Ok(CallResponse::default())
+    }
+}
+
+impl<C: Context> Module for Impostor<C> {
+    type Context = C;
+    type Config = ();
+    type CallMessage = ();
+
+    fn genesis(&self, _config: &(), working_set: &mut WorkingSet<C::Storage>) -> Result<(), Error> {
+        self.tokens.set(&0, &1, working_set);
+        Ok(())
+    }
+
+    fn call(
+        &self,
+        _msg: (),
+        _context: &C,
+        _working_set: &mut WorkingSet<C::Storage>,
+    ) -> Result<CallResponse, Error> {
+        Ok(CallResponse::default())
+    }
+}
+
+#[derive(Genesis)]
+struct Runtime<C>
+where
+    C: Context,
+{
+    pub bank: colliding::Bank<C>,
+    pub impostor: Impostor<C>,
+}
+
+fn main() {
+    use sov_modules_api::Genesis;
+
+    type C = DefaultContext;
+    let runtime = Runtime::<C> {
+        bank: colliding::Bank::new(),
+        impostor: Impostor::new(),
+    };
+    let config = GenesisConfig::new((), ());
+    let mut working_set = WorkingSet::new(ProverStorage::temporary());
+    runtime.genesis(&config, &mut working_set).unwrap();
+}
@@ -0,0 +1,28 @@
+use sov_modules_api::ModuleError;
+use sov_modules_macros::ModuleError;
+
+#[derive(Debug, thiserror::Error, ModuleError)]
+enum TestError {
+    #[error("Value {0} is too large")]
+    #[code = 1]
+    TooLarge(u64),
+
+    #[error("The value is frozen")]
+    #[code = 7]
+    Frozen,
+
+    #[error("{name} is not allowed")]
+    #[code = 2]
+    NotAllowed { name: String },
+}
+
+fn main() {
+    assert_eq!(TestError::TooLarge(5).code(), 1);
+    assert_eq!(TestError::Frozen.code(), 7);
+    let error: sov_modules_api::Error = TestError::NotAllowed {
+        name: "Alice".to_owned(),
+    }
+    .into();
+    assert_eq!(error.code(), 2);
+    assert_eq!(error.to_string(), "Alice is not allowed");
+}
@@ -0,0 +1,13 @@
+use sov_modules_macros::ModuleError;
+
+#[derive(Debug, thiserror::Error, ModuleError)]
+enum TestError {
+    #[error("Value {0} is too large")]
+    #[code = 1]
+    TooLarge(u64),
+
+    #[error("The value is frozen")]
+    Frozen,
+}
+
+fn main() {}
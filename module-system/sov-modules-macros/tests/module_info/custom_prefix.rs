@@ -0,0 +1,71 @@
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::{Context, ModuleInfo, Prefix};
+use sov_modules_macros::ModuleInfo;
+use sov_state::{StateMap, StateValue};
+
+mod test_module {
+    use super::*;
+
+    #[derive(ModuleInfo)]
+    #[module_info(prefix = "test")]
+    pub(crate) struct CustomPrefix<C: Context> {
+        #[address]
+        pub address: C::Address,
+
+        #[state(key = "values")]
+        pub test_state1: StateMap<u32, u32>,
+
+        #[state]
+        pub test_state2: StateValue<String>,
+    }
+
+    #[derive(ModuleInfo)]
+    pub(crate) struct CustomKey<C: Context> {
+        #[address]
+        pub address: C::Address,
+
+        #[state(key = "values")]
+        pub test_state: StateMap<u32, u32>,
+    }
+}
+
+fn main() {
+    type C = DefaultContext;
+    let custom_prefix = <test_module::CustomPrefix<C> as ModuleInfo>::new();
+
+    assert_eq!(
+        *custom_prefix.test_state1.prefix(),
+        Prefix::new_custom_storage("test", "values").into()
+    );
+    assert_eq!(
+        *custom_prefix.test_state2.prefix(),
+        Prefix::new_custom_storage("test", "test_state2").into()
+    );
+    assert_eq!(
+        <test_module::CustomPrefix<C> as ModuleInfo>::STATE_PREFIXES,
+        &[
+            Prefix::new_custom_storage("test", "values"),
+            Prefix::new_custom_storage("test", "test_state2")
+        ]
+    );
+
+    use sov_modules_api::Hasher;
+    let mut hasher = <C as sov_modules_api::Spec>::Hasher::new();
+    hasher.update("test/".as_bytes());
+    assert_eq!(
+        &sov_modules_api::Address::try_from(hasher.finalize().as_ref()).unwrap(),
+        custom_prefix.address()
+    );
+
+    let custom_key = <test_module::CustomKey<C> as ModuleInfo>::new();
+    assert_eq!(
+        *custom_key.test_state.prefix(),
+        Prefix::new_storage(
+            // trybuild names the crate of each test after its position in the test cases.
+            concat!(module_path!(), "::test_module"),
+            "CustomKey",
+            "values"
+        )
+        .into()
+    );
+}
@@ -0,0 +1,17 @@
+use sov_modules_api::Context;
+use sov_modules_macros::ModuleInfo;
+use sov_state::StateMap;
+
+#[derive(ModuleInfo)]
+struct TestStruct<C: Context> {
+    #[address]
+    address: C::Address,
+
+    #[state(key = "values")]
+    test_state1: StateMap<u32, String>,
+
+    #[state(key = "values")]
+    test_state2: StateMap<u32, String>,
+}
+
+fn main() {}
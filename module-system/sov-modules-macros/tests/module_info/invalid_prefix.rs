@@ -0,0 +1,15 @@
+use sov_modules_api::Context;
+use sov_modules_macros::ModuleInfo;
+use sov_state::StateMap;
+
+#[derive(ModuleInfo)]
+#[module_info(prefix = "bank/v1")]
+struct TestStruct<C: Context> {
+    #[address]
+    address: C::Address,
+
+    #[state]
+    test_state: StateMap<u32, String>,
+}
+
+fn main() {}
@@ -1,6 +1,7 @@
 mod batch;
 
 mod tx_verifier;
+pub mod zk_simulator;
 
 use std::marker::PhantomData;
 
@@ -8,6 +9,8 @@ pub use batch::Batch;
 use borsh::BorshDeserialize;
 use sov_modules_api::hooks::ApplyBlobHooks;
 use sov_modules_api::hooks::TxHooks;
+use sov_modules_api::migration::{init_state_versions, run_migrations, RuntimeMigrations};
+use sov_rollup_interface::rpc::TxReceiptStatus;
 use sov_rollup_interface::stf::BatchReceipt;
 use sov_rollup_interface::stf::TransactionReceipt;
 use sov_rollup_interface::zk::traits::Zkvm;
@@ -17,15 +20,19 @@ use tracing::error;
 use tx_verifier::verify_txs_stateless;
 pub use tx_verifier::RawTx;
 
-use sov_modules_api::{Context, DispatchCall, Genesis, Hasher, Spec};
+use sov_modules_api::{Context, DispatchCall, Genesis, Hasher, Spec, SLOT_HEIGHT_PREFIX};
 use sov_rollup_interface::{stf::StateTransitionFunction, traits::BatchTrait};
-use sov_state::{Storage, WorkingSet};
+use sov_state::{StateValue, Storage, WorkingSet};
 use std::io::Read;
 
 pub struct AppTemplate<C: Context, RT, Vm> {
     pub current_storage: C::Storage,
     pub runtime: RT,
     working_set: Option<WorkingSet<C::Storage>>,
+    /// The number of slots begun since genesis, which schedules the state migrations of the runtime
+    slot_height: StateValue<u64>,
+    /// Whether the serialized transactions are included in their receipts, so that the full node persists them
+    save_tx_bodies: bool,
     phantom_vm: PhantomData<Vm>,
 }
 
@@ -34,17 +41,27 @@ where
     RT: DispatchCall<Context = C>
         + Genesis<Context = C>
         + TxHooks<Context = C>
-        + ApplyBlobHooks<Context = C, BlobResult = SequencerOutcome>,
+        + ApplyBlobHooks<Context = C, BlobResult = SequencerOutcome>
+        + RuntimeMigrations<Context = C>,
 {
     pub fn new(storage: C::Storage, runtime: RT) -> Self {
         Self {
             runtime,
             current_storage: storage,
             working_set: None,
+            slot_height: StateValue::new(SLOT_HEIGHT_PREFIX.into()),
+            save_tx_bodies: false,
             phantom_vm: PhantomData,
         }
     }
 
+    /// Sets whether the serialized transactions are included in their receipts. Disabled by default,
+    /// since the transactions are already stored on the DA layer.
+    pub fn with_saved_tx_bodies(mut self, save_tx_bodies: bool) -> Self {
+        self.save_tx_bodies = save_tx_bodies;
+        self
+    }
+
     // TODO: implement a state machine instead of manually deciding when to commit and when to revert
     pub fn apply_batch(
         &mut self,
@@ -123,8 +140,9 @@ where
         let mut tx_receipts = Vec::with_capacity(txs.len());
 
         // Process transactions in a loop, commit changes after every step of the loop.
-        for (tx, raw_tx_hash) in txs {
+        for (tx, raw_tx_hash, raw_tx) in txs {
             batch_workspace = batch_workspace.to_revertable();
+            let body_to_save = self.save_tx_bodies.then_some(raw_tx.data);
 
             // Run the stateful verification, possibly modifies the state.
             let sender_address = match self
@@ -138,9 +156,13 @@ where
                     batch_workspace = batch_workspace.revert();
                     let receipt = TransactionReceipt {
                         tx_hash: raw_tx_hash,
-                        body_to_save: None,
+                        body_to_save,
                         events: batch_workspace.take_events(),
-                        receipt: TxEffect::Reverted,
+                        receipt: TxEffect::Reverted(RevertReason {
+                            module_address: None,
+                            code: 0,
+                            message: format!("{:#}", e),
+                        }),
                     };
 
                     tx_receipts.push(receipt);
@@ -151,26 +173,40 @@ where
             match RT::decode_call(tx.runtime_msg()) {
                 Ok(msg) => {
                     let ctx = C::new(sender_address.clone());
+                    let module_address = self.runtime.module_address(&msg).to_string();
                     let tx_result = self.runtime.dispatch_call(msg, &mut batch_workspace, &ctx);
 
                     self.runtime
                         .post_dispatch_tx_hook(&tx, &mut batch_workspace)
                         .expect("Impossible happened: error in post_dispatch_tx_hook");
 
-                    let tx_effect = match tx_result {
-                        Ok(_) => TxEffect::Successful,
-                        Err(_e) => {
+                    let (tx_effect, call_events) = match tx_result {
+                        Ok(response) => (
+                            TxEffect::Successful {
+                                return_data: response.data,
+                            },
+                            response.events,
+                        ),
+                        Err(e) => {
                             // The transaction causing invalid state transition is reverted but we don't slash and we continue
                             // processing remaining transactions.
                             batch_workspace = batch_workspace.revert();
-                            TxEffect::Reverted
+                            debug!("Tx 0x{} reverted: {}", hex::encode(raw_tx_hash), e);
+                            let reason = RevertReason {
+                                module_address: Some(module_address),
+                                code: e.code(),
+                                message: format!("{:#}", e),
+                            };
+                            (TxEffect::Reverted(reason), Vec::new())
                         }
                     };
 
+                    let mut events = batch_workspace.take_events();
+                    events.extend(call_events);
                     let receipt = TransactionReceipt {
                         tx_hash: raw_tx_hash,
-                        body_to_save: None,
-                        events: batch_workspace.take_events(),
+                        body_to_save,
+                        events,
                         receipt: tx_effect,
                     };
 
@@ -231,10 +267,32 @@ impl BatchDataAndHash {
         }
     }
 }
-#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum TxEffect {
-    Reverted,
-    Successful,
+    Reverted(RevertReason),
+    Successful {
+        /// The borsh-serialized data returned by the call, see `sov_modules_api::CallResponse`
+        return_data: Vec<u8>,
+    },
+}
+
+impl TxReceiptStatus for TxEffect {
+    fn is_reverted(&self) -> bool {
+        matches!(self, TxEffect::Reverted(_))
+    }
+}
+
+/// Why a transaction was reverted
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RevertReason {
+    /// The address of the module whose call failed, or `None` if the transaction was rejected
+    /// before being dispatched (for example because of an invalid nonce).
+    pub module_address: Option<String>,
+    /// The code of the error, specific to the module (see `sov_modules_api::ModuleError`), or 0 if
+    /// the error doesn't have one
+    pub code: u32,
+    /// The message of the error, followed by the messages of its causes
+    pub message: String,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -256,7 +314,8 @@ where
     RT: DispatchCall<Context = C>
         + Genesis<Context = C>
         + TxHooks<Context = C>
-        + ApplyBlobHooks<Context = C, BlobResult = SequencerOutcome>,
+        + ApplyBlobHooks<Context = C, BlobResult = SequencerOutcome>
+        + RuntimeMigrations<Context = C>,
 {
     type StateRoot = jmt::RootHash;
 
@@ -276,6 +335,7 @@ where
         self.runtime
             .genesis(&params, working_set)
             .expect("module initialization must succeed");
+        init_state_versions(&self.runtime, working_set);
 
         let (log, witness) = working_set.freeze();
         self.current_storage
@@ -284,10 +344,14 @@ where
     }
 
     fn begin_slot(&mut self, witness: Self::Witness) {
-        self.working_set = Some(WorkingSet::with_witness(
-            self.current_storage.clone(),
-            witness,
-        ));
+        let mut working_set = WorkingSet::with_witness(self.current_storage.clone(), witness);
+
+        let slot_height = self.slot_height.get(&mut working_set).unwrap_or_default();
+        run_migrations(&self.runtime, slot_height, &mut working_set)
+            .expect("state migrations must succeed");
+        self.slot_height.set(&(slot_height + 1), &mut working_set);
+
+        self.working_set = Some(working_set);
     }
 
     fn apply_blob(
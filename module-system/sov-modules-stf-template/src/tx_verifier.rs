@@ -17,9 +17,11 @@ impl RawTx {
 
 type RawTxHash = [u8; 32];
 
+/// Deserializes and verifies the signatures of the transactions, returning each of them along with its
+/// hash and serialized form.
 pub fn verify_txs_stateless<C: Context>(
     raw_txs: Vec<RawTx>,
-) -> anyhow::Result<Vec<(Transaction<C>, RawTxHash)>> {
+) -> anyhow::Result<Vec<(Transaction<C>, RawTxHash, RawTx)>> {
     let mut txs = Vec::with_capacity(raw_txs.len());
     debug!("Verifying {} transactions", raw_txs.len());
     for raw_tx in raw_txs {
@@ -27,7 +29,7 @@ pub fn verify_txs_stateless<C: Context>(
         let mut data = Cursor::new(&raw_tx.data);
         let tx = Transaction::<C>::deserialize_reader(&mut data)?;
         tx.verify()?;
-        txs.push((tx, raw_tx_hash));
+        txs.push((tx, raw_tx_hash, raw_tx));
     }
     Ok(txs)
 }
@@ -0,0 +1,116 @@
+//! Native replay of slots in zk mode. Running a witness through [`ZkStorage`](sov_state::ZkStorage)
+//! outside of a zkVM makes it possible to debug witnesses which fail to replay, without waiting for
+//! a proof to be generated.
+
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use sov_modules_api::default_context::ZkDefaultContext;
+use sov_modules_api::hooks::{ApplyBlobHooks, TxHooks};
+use sov_modules_api::migration::RuntimeMigrations;
+use sov_modules_api::{DispatchCall, Genesis};
+use sov_rollup_interface::da::BlobTransactionTrait;
+use sov_rollup_interface::stf::{BatchReceipt, StateTransitionFunction};
+use sov_rollup_interface::zk::traits::Zkvm;
+use sov_state::{ArrayWitness, HintMismatch, Storage, Witness, ZkStorage};
+
+use crate::{AppTemplate, SequencerOutcome, TxEffect};
+
+/// The result of successfully replaying a slot in zk mode.
+#[derive(Debug)]
+pub struct SimulatedSlot {
+    /// The state root after applying the slot
+    pub state_root: [u8; 32],
+    pub batch_receipts: Vec<BatchReceipt<SequencerOutcome, TxEffect>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SimulationError {
+    /// A storage access didn't match the next hint in the witness
+    #[error(transparent)]
+    HintMismatch(#[from] HintMismatch),
+    /// The slot was replayed successfully, but the witness contains hints which were never used.
+    /// This usually means that the witness was produced by a different set of blobs.
+    #[error("The replay stopped at hint {first_unused_index} but the witness contains {num_hints} hints")]
+    UnusedHints {
+        first_unused_index: usize,
+        num_hints: usize,
+    },
+    /// Execution failed for a reason unrelated to the witness hints
+    #[error("Zk execution failed: {0}")]
+    ExecutionFailed(String),
+}
+
+/// Replays a slot with [`ZkDefaultContext`], starting from `prev_state_root` and reading all state from
+/// `witness`. The blobs must be the same ones which were applied natively when the witness was generated.
+pub fn simulate_slot<RT, Vm: Zkvm>(
+    runtime: RT,
+    prev_state_root: [u8; 32],
+    witness: ArrayWitness,
+    blobs: impl IntoIterator<Item = impl BlobTransactionTrait>,
+) -> Result<SimulatedSlot, SimulationError>
+where
+    RT: DispatchCall<Context = ZkDefaultContext>
+        + Genesis<Context = ZkDefaultContext>
+        + TxHooks<Context = ZkDefaultContext>
+        + ApplyBlobHooks<Context = ZkDefaultContext, BlobResult = SequencerOutcome>
+        + RuntimeMigrations<Context = ZkDefaultContext>,
+{
+    let num_hints = witness.num_hints();
+    let mut app: AppTemplate<ZkDefaultContext, RT, Vm> =
+        AppTemplate::new(ZkStorage::new(prev_state_root), runtime);
+
+    // `ZkStorage` reports mismatched reads by panicking with a `HintMismatch` payload. `begin_slot`
+    // reads the state too, to run the scheduled state migrations.
+    let batch_receipts = catch_unwind(AssertUnwindSafe(|| {
+        app.begin_slot(witness);
+        blobs
+            .into_iter()
+            .map(|blob| app.apply_blob(blob, None))
+            .collect::<Vec<_>>()
+    }))
+    .map_err(error_from_panic)?;
+
+    // Equivalent to `end_slot`, except that storage errors are returned instead of panicking
+    let (cache_log, witness) = app
+        .working_set
+        .take()
+        .expect("Working_set was initialized in begin_slot")
+        .freeze();
+    let state_root = app
+        .current_storage
+        .validate_and_commit(cache_log, &witness)
+        .map_err(
+            |e| match e.chain().find_map(|e| e.downcast_ref::<HintMismatch>()) {
+                Some(mismatch) => SimulationError::HintMismatch(mismatch.clone()),
+                None => SimulationError::ExecutionFailed(e.to_string()),
+            },
+        )?;
+
+    let first_unused_index = witness.next_hint_index();
+    if first_unused_index != num_hints {
+        return Err(SimulationError::UnusedHints {
+            first_unused_index,
+            num_hints,
+        });
+    }
+
+    Ok(SimulatedSlot {
+        state_root,
+        batch_receipts,
+    })
+}
+
+fn error_from_panic(payload: Box<dyn Any + Send>) -> SimulationError {
+    if let Some(mismatch) = payload.downcast_ref::<HintMismatch>() {
+        return SimulationError::HintMismatch(mismatch.clone());
+    }
+    let message = match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    };
+    SimulationError::ExecutionFailed(message)
+}
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::witness::{ArrayWitness, HintError, Witness};
+
+/// The current version of the [`CompactWitness`] encoding.
+pub const COMPACT_WITNESS_VERSION: u8 = 1;
+
+/// A witness which stores each distinct hint only once.
+///
+/// The same value is often read several times in a slot, for example again after a reverted transaction
+/// discarded the cached read, so an [`ArrayWitness`] can contain many duplicate hints. This witness keeps
+/// a table of distinct hints plus the sequence in which they are consumed.
+///
+/// The encoding (version 1) is:
+/// ```text
+/// version: u8
+/// num_distinct_hints: varint, followed by each hint as (length: varint, bytes)
+/// num_hints: varint, followed by the position of each hint in the table as a varint
+/// ```
+/// where varints are unsigned LEB128. The encoding is wrapped in a byte vector when serialized with serde.
+///
+/// The [`crate::DefaultStorageSpec`] and the demo guest still use an [`ArrayWitness`]. The witness
+/// benchmarks convert one into this witness to compare the size and decode cost of both encodings.
+#[derive(Default, Debug)]
+pub struct CompactWitness {
+    next_idx: AtomicUsize,
+    table: Mutex<HintTable>,
+}
+
+#[derive(Default, Debug)]
+struct HintTable {
+    /// The distinct hints, in the order in which they were first added
+    distinct: Vec<Vec<u8>>,
+    /// The position of each hint in `distinct`. Only populated for hints added with `add_hint`, so
+    /// hints added after decoding a witness may not be deduplicated against the decoded ones.
+    positions: HashMap<Vec<u8>, u32>,
+    /// The position in `distinct` of each hint, in the order in which they are consumed
+    sequence: Vec<u32>,
+}
+
+impl HintTable {
+    fn push(&mut self, hint: Vec<u8>) {
+        let position = match self.positions.get(&hint) {
+            Some(position) => *position,
+            None => {
+                let position = self.distinct.len() as u32;
+                self.distinct.push(hint.clone());
+                self.positions.insert(hint, position);
+                position
+            }
+        };
+        self.sequence.push(position);
+    }
+}
+
+impl CompactWitness {
+    /// The total number of hints in the witness, including the ones which have already been consumed.
+    pub fn num_hints(&self) -> usize {
+        self.table.lock().unwrap().sequence.len()
+    }
+
+    /// The number of distinct hints stored in the witness.
+    pub fn num_distinct_hints(&self) -> usize {
+        self.table.lock().unwrap().distinct.len()
+    }
+
+    /// Encodes every hint in the witness, regardless of how many have been consumed.
+    pub fn encode(&self) -> Vec<u8> {
+        let table = self.table.lock().unwrap();
+        let mut out = vec![COMPACT_WITNESS_VERSION];
+        write_varint(&mut out, table.distinct.len() as u64);
+        for hint in &table.distinct {
+            write_varint(&mut out, hint.len() as u64);
+            out.extend_from_slice(hint);
+        }
+        write_varint(&mut out, table.sequence.len() as u64);
+        for position in &table.sequence {
+            write_varint(&mut out, *position as u64);
+        }
+        out
+    }
+
+    /// Decodes a witness produced by [`CompactWitness::encode`].
+    pub fn decode(mut bytes: &[u8]) -> Result<Self, anyhow::Error> {
+        let buf = &mut bytes;
+        let version = read_bytes(buf, 1)?[0];
+        anyhow::ensure!(
+            version == COMPACT_WITNESS_VERSION,
+            "Unsupported compact witness version {}",
+            version
+        );
+
+        let num_distinct = read_varint(buf)? as usize;
+        // Each hint takes at least one byte, so this bounds the allocation by the input size
+        let mut distinct = Vec::with_capacity(num_distinct.min(buf.len()));
+        for _ in 0..num_distinct {
+            let len = read_varint(buf)? as usize;
+            distinct.push(read_bytes(buf, len)?.to_vec());
+        }
+
+        let num_hints = read_varint(buf)? as usize;
+        let mut sequence = Vec::with_capacity(num_hints.min(buf.len()));
+        for _ in 0..num_hints {
+            let position = read_varint(buf)?;
+            anyhow::ensure!(
+                position < distinct.len() as u64,
+                "Hint refers to position {} but the witness only has {} distinct hints",
+                position,
+                distinct.len()
+            );
+            sequence.push(position as u32);
+        }
+        anyhow::ensure!(
+            buf.is_empty(),
+            "Compact witness has {} trailing bytes",
+            buf.len()
+        );
+
+        Ok(Self {
+            next_idx: AtomicUsize::new(0),
+            table: Mutex::new(HintTable {
+                distinct,
+                positions: HashMap::new(),
+                sequence,
+            }),
+        })
+    }
+}
+
+impl Witness for CompactWitness {
+    fn add_hint<T: BorshSerialize>(&self, hint: T) {
+        self.table.lock().unwrap().push(hint.try_to_vec().unwrap())
+    }
+
+    fn try_get_hint<T: BorshDeserialize>(&self) -> Result<T, HintError> {
+        let index = self.next_idx.fetch_add(1, Ordering::SeqCst);
+        let table = self.table.lock().unwrap();
+        let position = *table
+            .sequence
+            .get(index)
+            .ok_or(HintError::Missing { index })?;
+        T::try_from_slice(&table.distinct[position as usize]).map_err(|e| HintError::Malformed {
+            index,
+            reason: e.to_string(),
+        })
+    }
+
+    fn next_hint_index(&self) -> usize {
+        self.next_idx.load(Ordering::SeqCst)
+    }
+
+    fn merge(&self, rhs: &Self) {
+        let rhs_next_idx = rhs.next_idx.load(Ordering::SeqCst);
+        let mut lhs_table = self.table.lock().unwrap();
+        let mut rhs_table = rhs.table.lock().unwrap();
+        let rhs_table = &mut *rhs_table;
+        for position in rhs_table.sequence.drain(rhs_next_idx..) {
+            lhs_table.push(rhs_table.distinct[position as usize].clone());
+        }
+    }
+}
+
+impl From<ArrayWitness> for CompactWitness {
+    fn from(witness: ArrayWitness) -> Self {
+        let compact = CompactWitness::default();
+        {
+            let mut table = compact.table.lock().unwrap();
+            for hint in witness.into_hints() {
+                table.push(hint);
+            }
+        }
+        compact
+    }
+}
+
+impl Serialize for CompactWitness {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.encode().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CompactWitness {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        CompactWitness::decode(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(buf: &mut &[u8]) -> Result<u64, anyhow::Error> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_bytes(buf, 1)?[0];
+        // The 10th byte holds the 64th bit only, so anything larger would be silently truncated
+        anyhow::ensure!(shift < 63 || byte <= 1, "Varint is longer than 64 bits");
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    anyhow::bail!("Varint is longer than 64 bits")
+}
+
+fn read_bytes<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8], anyhow::Error> {
+    anyhow::ensure!(
+        buf.len() >= len,
+        "Compact witness ended unexpectedly: needed {} bytes but only {} remain",
+        len,
+        buf.len()
+    );
+    let (head, tail) = buf.split_at(len);
+    *buf = tail;
+    Ok(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_witness() -> CompactWitness {
+        let witness = CompactWitness::default();
+        witness.add_hint(7u64);
+        witness.add_hint(Some(vec![1u8, 2, 3]));
+        witness.add_hint(7u64);
+        witness.add_hint(Option::<Vec<u8>>::None);
+        witness.add_hint(Some(vec![1u8, 2, 3]));
+        witness
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let witness = sample_witness();
+        assert_eq!(witness.num_hints(), 5);
+        assert_eq!(witness.num_distinct_hints(), 3);
+
+        let decoded = CompactWitness::decode(&witness.encode()).unwrap();
+        assert_eq!(decoded.get_hint::<u64>(), 7);
+        assert_eq!(decoded.get_hint::<Option<Vec<u8>>>(), Some(vec![1, 2, 3]));
+        assert_eq!(decoded.get_hint::<u64>(), 7);
+        assert_eq!(decoded.get_hint::<Option<Vec<u8>>>(), None);
+        assert_eq!(decoded.get_hint::<Option<Vec<u8>>>(), Some(vec![1, 2, 3]));
+        assert_eq!(
+            decoded.try_get_hint::<u64>(),
+            Err(HintError::Missing { index: 5 })
+        );
+    }
+
+    #[test]
+    fn test_matches_array_witness() {
+        let array_witness = ArrayWitness::default();
+        for i in 0..100u64 {
+            array_witness.add_hint(i % 10);
+        }
+        let compact = CompactWitness::from(array_witness);
+        assert_eq!(compact.num_hints(), 100);
+        assert_eq!(compact.num_distinct_hints(), 10);
+        for i in 0..100u64 {
+            assert_eq!(compact.get_hint::<u64>(), i % 10);
+        }
+    }
+
+    #[test]
+    fn test_merge_skips_consumed_hints() {
+        let lhs = CompactWitness::default();
+        lhs.add_hint(1u64);
+        let rhs = sample_witness();
+        assert_eq!(rhs.get_hint::<u64>(), 7);
+
+        lhs.merge(&rhs);
+        assert_eq!(lhs.num_hints(), 5);
+        assert_eq!(lhs.get_hint::<u64>(), 1);
+        assert_eq!(lhs.get_hint::<Option<Vec<u8>>>(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_rejects_invalid_encodings() {
+        let mut encoded = sample_witness().encode();
+
+        let mut wrong_version = encoded.clone();
+        wrong_version[0] = COMPACT_WITNESS_VERSION + 1;
+        assert!(CompactWitness::decode(&wrong_version).is_err());
+
+        encoded.push(0);
+        assert!(CompactWitness::decode(&encoded).is_err());
+        encoded.truncate(encoded.len() - 2);
+        assert!(CompactWitness::decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_varint_bounds() {
+        for value in [0, 1, 0x7f, 0x80, u32::MAX as u64, u64::MAX - 1, u64::MAX] {
+            let mut encoded = Vec::new();
+            write_varint(&mut encoded, value);
+            assert_eq!(read_varint(&mut &encoded[..]).unwrap(), value);
+        }
+
+        let mut max = Vec::new();
+        write_varint(&mut max, u64::MAX);
+        assert_eq!(max.len(), 10);
+        assert_eq!(max[9], 1);
+
+        // A 10th byte above 1 encodes bits beyond the 64th
+        let mut overflowing = max.clone();
+        overflowing[9] = 2;
+        assert!(read_varint(&mut &overflowing[..]).is_err());
+        overflowing[9] = 0x7f;
+        assert!(read_varint(&mut &overflowing[..]).is_err());
+        // An 11th byte is never valid
+        let mut too_long = max;
+        too_long[9] = 0x81;
+        too_long.push(0);
+        assert!(read_varint(&mut &too_long[..]).is_err());
+    }
+}
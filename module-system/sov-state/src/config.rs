@@ -1,7 +1,12 @@
 use std::path::PathBuf;
 
+use sov_db::rocks_db_config::RocksdbConfigs;
+
 #[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Config {
     /// Path to folder where storage files will be stored
     pub path: PathBuf,
+    /// RocksDB options of the ledger and state databases stored in `path`
+    #[serde(default)]
+    pub rocksdb: RocksdbConfigs,
 }
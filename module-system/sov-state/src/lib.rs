@@ -1,22 +1,27 @@
+mod compact_witness;
 mod internal_cache;
 mod map;
 #[cfg(feature = "native")]
+mod metrics;
+mod multiproof;
+#[cfg(feature = "native")]
 mod prover_storage;
 mod scratchpad;
 pub mod storage;
-#[cfg(feature = "native")]
-mod tree_db;
 mod utils;
 mod value;
 mod witness;
 mod zk_storage;
 
+#[cfg(feature = "native")]
 pub mod config;
 #[cfg(test)]
 mod state_tests;
 
-pub use crate::witness::{ArrayWitness, TreeWitnessReader, Witness};
+pub use crate::witness::{ArrayWitness, HintError, TreeWitnessReader, Witness};
+pub use compact_witness::{CompactWitness, COMPACT_WITNESS_VERSION};
 pub use map::StateMap;
+pub use multiproof::{MultiProofError, SparseMerkleMultiProof};
 #[cfg(feature = "native")]
 pub use prover_storage::{delete_storage, ProverStorage};
 pub use scratchpad::*;
@@ -25,7 +30,7 @@ use std::{fmt::Display, str};
 pub use storage::Storage;
 use utils::AlignedVec;
 pub use value::StateValue;
-pub use zk_storage::ZkStorage;
+pub use zk_storage::{HintMismatch, ZkStorage};
 
 // A prefix prepended to each key before insertion and retrieval from the storage.
 // All the collection types in this crate are backed by the same storage instance, this means that insertions of the same key
@@ -0,0 +1,13 @@
+use once_cell::sync::Lazy;
+use prometheus::{exponential_buckets, register_histogram, Histogram};
+
+pub static JMT_COMMIT_LATENCY_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "jmt_commit_latency_seconds",
+        // metric description
+        "Latency of proving and committing a jmt update in ProverStorage::validate_and_commit in seconds",
+        exponential_buckets(/*start=*/ 1e-4, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
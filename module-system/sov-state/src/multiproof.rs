@@ -0,0 +1,689 @@
+//! Proofs of several keys against a single JMT root, which share the nodes common to their paths.
+//!
+//! A separate proof per key repeats every node between the root and the point where the paths of the
+//! keys diverge, and verifying them hashes those nodes once per key. A multiproof instead stores only the
+//! siblings which can't be computed from another path, and verification hashes each node of the union of
+//! the paths exactly once. Both the size and the verification cost therefore grow with the number of
+//! touched subtrees rather than with the number of keys.
+//!
+//! The same proof is enough to compute the root after writing to the proven keys, since the nodes
+//! which change are exactly the ones on their paths. This lets zk mode update the tree without trusting
+//! any node which wasn't authenticated against the previous root.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use jmt::proof::SparseMerkleProof;
+use jmt::storage::TreeReader;
+use jmt::{JellyfishMerkleTree, KeyHash, RootHash, SimpleHasher, Version};
+
+// These must match the values `jmt` uses to hash the nodes of its sparse merkle proofs.
+const LEAF_DOMAIN_SEPARATOR: &[u8] = b"JMT::LeafNode";
+const INTERNAL_DOMAIN_SEPARATOR: &[u8] = b"JMT::IntrnalNode";
+const PLACEHOLDER_HASH: [u8; 32] = *b"SPARSE_MERKLE_PLACEHOLDER_HASH__";
+
+/// The key and value hashes of a leaf of the sparse merkle tree.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ProofLeaf {
+    pub key_hash: [u8; 32],
+    pub value_hash: [u8; 32],
+}
+
+/// Where the path of a single key ends: either at a leaf, or at an empty subtree.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub(crate) struct PathEnd {
+    pub(crate) leaf: Option<ProofLeaf>,
+    pub(crate) depth: u16,
+}
+
+/// Mirrors the borsh encoding of [`SparseMerkleProof`], whose fields are private.
+#[derive(BorshDeserialize)]
+struct RawProof {
+    leaf: Option<ProofLeaf>,
+    /// Ordered from the leaf up to the root
+    siblings: Vec<[u8; 32]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MultiProofError {
+    #[error("the proof of key {index} is invalid: {reason}")]
+    InvalidKey { index: usize, reason: String },
+    #[error("invalid multiproof: {0}")]
+    InvalidProof(String),
+    /// The writes empty a subtree whose sibling isn't covered by any path, so it is unknown whether the
+    /// sibling is a single leaf which moves up the tree. Proving `probe` resolves this.
+    #[error("the writes empty a subtree next to the unproven subtree containing 0x{}", hex::encode(.probe))]
+    UnknownSibling { probe: [u8; 32] },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct SparseMerkleMultiProof {
+    /// The end of the path of each key, in the order in which the keys were proven
+    pub(crate) paths: Vec<PathEnd>,
+    /// Keys which are neither read nor written, and are only proven to reveal the contents of
+    /// subtrees next to the ones emptied by the writes. Their paths come after all other paths.
+    pub(crate) probes: Vec<[u8; 32]>,
+    /// The hashes of the subtrees next to the paths which aren't covered by any other path,
+    /// in the order in which they are used during verification
+    pub(crate) siblings: Vec<[u8; 32]>,
+}
+
+/// The contents of a subtree, as far as they are known from the proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Subtree {
+    Empty,
+    Leaf(ProofLeaf),
+    Internal([u8; 32]),
+    /// A subtree which no path goes through, which may be either a leaf or an internal node
+    Unknown([u8; 32]),
+}
+
+impl Subtree {
+    fn from_sibling(hash: [u8; 32]) -> Self {
+        if hash == PLACEHOLDER_HASH {
+            Subtree::Empty
+        } else {
+            Subtree::Unknown(hash)
+        }
+    }
+
+    fn hash<H: SimpleHasher>(&self) -> [u8; 32] {
+        match self {
+            Subtree::Empty => PLACEHOLDER_HASH,
+            Subtree::Leaf(leaf) => leaf_hash::<H>(leaf),
+            Subtree::Internal(hash) | Subtree::Unknown(hash) => *hash,
+        }
+    }
+}
+
+/// The keys covered by a proof, and the values written to them.
+struct ProvenKeys<'a> {
+    hashes: Vec<KeyHash>,
+    first_write: usize,
+    writes: &'a [(KeyHash, Option<&'a [u8]>)],
+}
+
+impl<'a> ProvenKeys<'a> {
+    /// The value written to the key at `index`, or `None` if the key isn't written.
+    fn written_value(&self, index: usize) -> Option<Option<&'a [u8]>> {
+        index
+            .checked_sub(self.first_write)
+            .and_then(|i| self.writes.get(i))
+            .map(|(_, value)| *value)
+    }
+}
+
+impl SparseMerkleMultiProof {
+    /// Combines the proofs of several keys against the same root.
+    pub fn from_proofs<H: SimpleHasher>(
+        proofs: &[(KeyHash, SparseMerkleProof<H>)],
+    ) -> Result<Self, anyhow::Error> {
+        let keys: Vec<KeyHash> = proofs.iter().map(|(key, _)| *key).collect();
+        let raw_proofs = proofs
+            .iter()
+            .map(|(_, proof)| RawProof::try_from_slice(&proof.try_to_vec()?))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut siblings = Vec::new();
+        if !keys.is_empty() {
+            collect_siblings(&keys, &raw_proofs, &sorted_indices(&keys), 0, &mut siblings)?;
+        }
+
+        let paths = raw_proofs
+            .into_iter()
+            .map(|proof| PathEnd {
+                depth: proof.siblings.len() as u16,
+                leaf: proof.leaf,
+            })
+            .collect();
+        Ok(Self {
+            paths,
+            probes: Vec::new(),
+            siblings,
+        })
+    }
+
+    /// Proves the reads and the writes of a batch against `version` of the tree, returning the proof
+    /// together with the root of the tree after the writes.
+    pub fn prove_update<R: TreeReader, H: SimpleHasher>(
+        tree: &JellyfishMerkleTree<R, H>,
+        version: Version,
+        reads: &[(KeyHash, Option<&[u8]>)],
+        writes: &[(KeyHash, Option<&[u8]>)],
+    ) -> Result<(Self, [u8; 32]), anyhow::Error> {
+        let root = tree.get_root_hash(version)?;
+        let mut probes: Vec<[u8; 32]> = Vec::new();
+        loop {
+            let proofs = reads
+                .iter()
+                .chain(writes)
+                .map(|(key, _)| *key)
+                .chain(probes.iter().map(|probe| KeyHash(*probe)))
+                .map(|key| Ok((key, tree.get_with_proof(key, version)?.1)))
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+            let mut proof = Self::from_proofs(&proofs)?;
+            proof.probes = probes.clone();
+
+            match proof.verify_update::<H>(root, reads, writes) {
+                Ok(new_root) => return Ok((proof, new_root)),
+                // Each probe reveals one more subtree, so this terminates
+                Err(MultiProofError::UnknownSibling { probe }) if !probes.contains(&probe) => {
+                    probes.push(probe)
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// The number of sibling hashes included in the proof.
+    pub fn num_siblings(&self) -> usize {
+        self.siblings.len()
+    }
+
+    /// Verifies the value (or absence) of every key against `root`. The reads must be provided
+    /// in the same order as the proofs passed to [`SparseMerkleMultiProof::from_proofs`].
+    pub fn verify<H: SimpleHasher>(
+        &self,
+        root: RootHash,
+        reads: &[(KeyHash, Option<&[u8]>)],
+    ) -> Result<(), MultiProofError> {
+        self.verify_update::<H>(root, reads, &[]).map(|_| ())
+    }
+
+    /// Verifies the reads against `root`, and computes the root of the tree after applying the writes.
+    ///
+    /// Every node which the writes touch is authenticated against `root` before it is used, so the
+    /// returned root can't be influenced by the prover. A write of `None` deletes the key.
+    pub fn verify_update<H: SimpleHasher>(
+        &self,
+        root: RootHash,
+        reads: &[(KeyHash, Option<&[u8]>)],
+        writes: &[(KeyHash, Option<&[u8]>)],
+    ) -> Result<[u8; 32], MultiProofError> {
+        let num_keys = reads.len() + writes.len() + self.probes.len();
+        if self.paths.len() != num_keys {
+            return Err(MultiProofError::InvalidProof(format!(
+                "the proof covers {} keys but {} were accessed",
+                self.paths.len(),
+                num_keys
+            )));
+        }
+        if num_keys == 0 {
+            return Ok(root.0);
+        }
+
+        let keys = ProvenKeys {
+            hashes: reads
+                .iter()
+                .chain(writes)
+                .map(|(key, _)| *key)
+                .chain(self.probes.iter().map(|probe| KeyHash(*probe)))
+                .collect(),
+            first_write: reads.len(),
+            writes,
+        };
+        for (index, (key, path)) in keys.hashes.iter().zip(&self.paths).enumerate() {
+            // Only the values of the reads are known. Writes and probes just need a valid path.
+            let value = reads.get(index).map(|(_, value)| *value);
+            verify_path_end::<H>(key, value, path)
+                .map_err(|reason| MultiProofError::InvalidKey { index, reason })?;
+        }
+
+        let mut siblings = self.siblings.iter();
+        let (old_root, new_root) =
+            self.subtree::<H>(&keys, &sorted_indices(&keys.hashes), 0, &mut siblings)?;
+
+        if siblings.next().is_some() {
+            return Err(MultiProofError::InvalidProof(
+                "the proof contains unused siblings".to_string(),
+            ));
+        }
+        let old_root = old_root.hash::<H>();
+        if old_root != root.0 {
+            return Err(MultiProofError::InvalidProof(format!(
+                "expected root 0x{} but the proof hashes to 0x{}",
+                hex::encode(root.0),
+                hex::encode(old_root)
+            )));
+        }
+        Ok(new_root.hash::<H>())
+    }
+
+    /// Computes the subtree at `depth` which contains the paths of the keys in `indices`, both before
+    /// and after applying the writes.
+    fn subtree<'a, H: SimpleHasher>(
+        &self,
+        keys: &ProvenKeys,
+        indices: &[usize],
+        depth: usize,
+        siblings: &mut impl Iterator<Item = &'a [u8; 32]>,
+    ) -> Result<(Subtree, Subtree), MultiProofError> {
+        let first_path = &self.paths[indices[0]];
+        if indices
+            .iter()
+            .any(|&i| self.paths[i].depth as usize == depth)
+        {
+            // A subtree is either a leaf or an internal node, so every path through it must end here
+            if indices.iter().any(|&i| self.paths[i] != *first_path) {
+                return Err(MultiProofError::InvalidProof(format!(
+                    "inconsistent paths end at depth {}",
+                    depth
+                )));
+            }
+            let old = match &first_path.leaf {
+                Some(leaf) => Subtree::Leaf(leaf.clone()),
+                None => Subtree::Empty,
+            };
+
+            let mut leaves: Vec<ProofLeaf> = first_path.leaf.iter().cloned().collect();
+            for &i in indices {
+                if let Some(value) = keys.written_value(i) {
+                    let key_hash = keys.hashes[i].0;
+                    leaves.retain(|leaf| leaf.key_hash != key_hash);
+                    if let Some(value) = value {
+                        leaves.push(ProofLeaf {
+                            key_hash,
+                            value_hash: H::hash(value),
+                        });
+                    }
+                }
+            }
+            return Ok((old, build_subtree::<H>(leaves, depth)));
+        }
+        if depth >= 256 {
+            return Err(MultiProofError::InvalidProof(
+                "a path is longer than the key".to_string(),
+            ));
+        }
+
+        let (left, right) =
+            indices.split_at(indices.partition_point(|&i| !bit(&keys.hashes[i].0, depth)));
+        let (old_left, new_left) = self.child::<H>(keys, left, depth + 1, siblings)?;
+        let (old_right, new_right) = self.child::<H>(keys, right, depth + 1, siblings)?;
+
+        let old = Subtree::Internal(internal_hash::<H>(
+            &old_left.hash::<H>(),
+            &old_right.hash::<H>(),
+        ));
+        if new_left == old_left && new_right == old_right {
+            return Ok((old.clone(), old));
+        }
+        // A leaf without siblings moves up the tree, so the new subtree depends on whether
+        // each side is empty or holds a single leaf
+        let new = match (new_left, new_right) {
+            (Subtree::Empty, Subtree::Empty) => Subtree::Empty,
+            (Subtree::Empty, Subtree::Leaf(leaf)) | (Subtree::Leaf(leaf), Subtree::Empty) => {
+                Subtree::Leaf(leaf)
+            }
+            (Subtree::Empty, Subtree::Unknown(_)) | (Subtree::Unknown(_), Subtree::Empty) => {
+                return Err(MultiProofError::UnknownSibling {
+                    probe: probe_key(&keys.hashes[indices[0]], depth, right.is_empty()),
+                });
+            }
+            (new_left, new_right) => Subtree::Internal(internal_hash::<H>(
+                &new_left.hash::<H>(),
+                &new_right.hash::<H>(),
+            )),
+        };
+        Ok((old, new))
+    }
+
+    /// Computes the child subtree at `depth`, taking its hash from the siblings if no path goes through it.
+    fn child<'a, H: SimpleHasher>(
+        &self,
+        keys: &ProvenKeys,
+        indices: &[usize],
+        depth: usize,
+        siblings: &mut impl Iterator<Item = &'a [u8; 32]>,
+    ) -> Result<(Subtree, Subtree), MultiProofError> {
+        if indices.is_empty() {
+            let sibling = siblings.next().copied().ok_or_else(|| {
+                MultiProofError::InvalidProof("the proof is missing siblings".to_string())
+            })?;
+            let sibling = Subtree::from_sibling(sibling);
+            Ok((sibling.clone(), sibling))
+        } else {
+            self.subtree::<H>(keys, indices, depth, siblings)
+        }
+    }
+}
+
+/// Builds the subtree at `depth` which contains exactly `leaves`.
+fn build_subtree<H: SimpleHasher>(mut leaves: Vec<ProofLeaf>, depth: usize) -> Subtree {
+    if leaves.len() <= 1 {
+        return leaves.pop().map_or(Subtree::Empty, Subtree::Leaf);
+    }
+    // Keys are distinct, so the leaves end up on different sides before running out of bits
+    let (right, left): (Vec<_>, Vec<_>) = leaves
+        .into_iter()
+        .partition(|leaf| bit(&leaf.key_hash, depth));
+    let left = build_subtree::<H>(left, depth + 1);
+    let right = build_subtree::<H>(right, depth + 1);
+    Subtree::Internal(internal_hash::<H>(&left.hash::<H>(), &right.hash::<H>()))
+}
+
+/// A key inside the child subtree of the node at `depth` on the path of `key`, on the given side.
+fn probe_key(key: &KeyHash, depth: usize, right: bool) -> [u8; 32] {
+    let mut probe = [0u8; 32];
+    for d in (0..depth).filter(|&d| bit(&key.0, d)) {
+        probe[d / 8] |= 0x80 >> (d % 8);
+    }
+    if right {
+        probe[depth / 8] |= 0x80 >> (depth % 8);
+    }
+    probe
+}
+
+/// Checks that the end of a key's path is consistent with the value which was read.
+fn verify_path_end<H: SimpleHasher>(
+    key: &KeyHash,
+    value: Option<Option<&[u8]>>,
+    path: &PathEnd,
+) -> Result<(), String> {
+    let value = match value {
+        Some(value) => value,
+        // The value is unknown, so the path only needs to lead to the key
+        None => {
+            return match &path.leaf {
+                Some(leaf) if leaf.key_hash != key.0 => check_on_path(leaf, key, path),
+                _ => Ok(()),
+            }
+        }
+    };
+    match (value, &path.leaf) {
+        (Some(value), Some(leaf)) => {
+            if leaf.key_hash != key.0 {
+                return Err("the path ends at the leaf of a different key".to_string());
+            }
+            if leaf.value_hash != H::hash(value) {
+                return Err("the value does not match the leaf".to_string());
+            }
+            Ok(())
+        }
+        (Some(_), None) => Err("the path ends at an empty subtree".to_string()),
+        (None, Some(leaf)) => {
+            if leaf.key_hash == key.0 {
+                return Err("the key exists in the tree".to_string());
+            }
+            check_on_path(leaf, key, path)
+        }
+        (None, None) => Ok(()),
+    }
+}
+
+/// The leaf must be on the path of the key, otherwise it says nothing about the key.
+fn check_on_path(leaf: &ProofLeaf, key: &KeyHash, path: &PathEnd) -> Result<(), String> {
+    if common_prefix_bits(&leaf.key_hash, &key.0) < path.depth as usize {
+        return Err("the leaf is not on the path of the key".to_string());
+    }
+    Ok(())
+}
+
+/// Pushes the siblings needed to verify the keys in `indices` in the order `subtree_hash` consumes them.
+fn collect_siblings(
+    keys: &[KeyHash],
+    proofs: &[RawProof],
+    indices: &[usize],
+    depth: usize,
+    siblings: &mut Vec<[u8; 32]>,
+) -> Result<(), anyhow::Error> {
+    let num_ended = indices
+        .iter()
+        .filter(|&&i| proofs[i].siblings.len() == depth)
+        .count();
+    if num_ended > 0 {
+        anyhow::ensure!(
+            num_ended == indices.len(),
+            "Proofs disagree about the subtree at depth {}",
+            depth
+        );
+        return Ok(());
+    }
+
+    let (left, right) = indices.split_at(indices.partition_point(|&i| !bit(&keys[i].0, depth)));
+    for (side, other) in [(left, right), (right, left)] {
+        if side.is_empty() {
+            let proof = &proofs[other[0]];
+            siblings.push(proof.siblings[proof.siblings.len() - 1 - depth]);
+        } else {
+            collect_siblings(keys, proofs, side, depth + 1, siblings)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the indices of `keys`, ordered by key so that every subtree covers a contiguous range.
+fn sorted_indices(keys: &[KeyHash]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..keys.len()).collect();
+    indices.sort_by_key(|&i| keys[i].0);
+    indices
+}
+
+/// The bit of the key at the given depth, starting from the most significant bit.
+fn bit(key: &[u8; 32], depth: usize) -> bool {
+    (key[depth / 8] >> (7 - depth % 8)) & 1 == 1
+}
+
+fn common_prefix_bits(a: &[u8; 32], b: &[u8; 32]) -> usize {
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        if x != y {
+            return i * 8 + (x ^ y).leading_zeros() as usize;
+        }
+    }
+    256
+}
+
+fn leaf_hash<H: SimpleHasher>(leaf: &ProofLeaf) -> [u8; 32] {
+    let mut hasher = H::new();
+    hasher.update(LEAF_DOMAIN_SEPARATOR);
+    hasher.update(&leaf.key_hash);
+    hasher.update(&leaf.value_hash);
+    hasher.finalize()
+}
+
+fn internal_hash<H: SimpleHasher>(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = H::new();
+    hasher.update(INTERNAL_DOMAIN_SEPARATOR);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::Sha256;
+
+    use super::*;
+
+    fn key_with_first_byte(byte: u8) -> KeyHash {
+        let mut key = [0u8; 32];
+        key[0] = byte;
+        KeyHash(key)
+    }
+
+    /// A tree with a leaf in each half, and a read of an absent key which ends at the empty
+    /// subtree `01...`, next to the leaf `00...`.
+    fn sample_tree() -> (
+        RootHash,
+        SparseMerkleMultiProof,
+        Vec<(KeyHash, Option<Vec<u8>>)>,
+    ) {
+        let left_key = key_with_first_byte(0b0000_0000);
+        let right_key = key_with_first_byte(0b1000_0000);
+        let absent_key = key_with_first_byte(0b0100_0000);
+        let left = ProofLeaf {
+            key_hash: left_key.0,
+            value_hash: Sha256::hash(b"left"),
+        };
+        let right = ProofLeaf {
+            key_hash: right_key.0,
+            value_hash: Sha256::hash(b"right"),
+        };
+
+        let left_subtree = internal_hash::<Sha256>(&leaf_hash::<Sha256>(&left), &PLACEHOLDER_HASH);
+        let root = internal_hash::<Sha256>(&left_subtree, &leaf_hash::<Sha256>(&right));
+
+        let proof = SparseMerkleMultiProof {
+            paths: vec![
+                PathEnd {
+                    leaf: Some(left),
+                    depth: 2,
+                },
+                PathEnd {
+                    leaf: Some(right),
+                    depth: 1,
+                },
+                PathEnd {
+                    leaf: None,
+                    depth: 2,
+                },
+            ],
+            probes: vec![],
+            siblings: vec![],
+        };
+        let reads = vec![
+            (left_key, Some(b"left".to_vec())),
+            (right_key, Some(b"right".to_vec())),
+            (absent_key, None),
+        ];
+        (RootHash(root), proof, reads)
+    }
+
+    fn as_reads(reads: &[(KeyHash, Option<Vec<u8>>)]) -> Vec<(KeyHash, Option<&[u8]>)> {
+        reads
+            .iter()
+            .map(|(key, value)| (*key, value.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_shared_paths() {
+        let (root, proof, reads) = sample_tree();
+        proof.verify::<Sha256>(root, &as_reads(&reads)).unwrap();
+
+        // Proving a subset of the keys requires the hashes of the subtrees which are no longer covered
+        let mut partial = proof.clone();
+        partial.paths.truncate(1);
+        partial.siblings = vec![
+            PLACEHOLDER_HASH,
+            leaf_hash::<Sha256>(&ProofLeaf {
+                key_hash: reads[1].0 .0,
+                value_hash: Sha256::hash(b"right"),
+            }),
+        ];
+        partial
+            .verify::<Sha256>(root, &as_reads(&reads[..1]))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_rejects_invalid_proofs() {
+        let (root, proof, mut reads) = sample_tree();
+
+        reads[1].1 = Some(b"wrong".to_vec());
+        assert!(matches!(
+            proof.verify::<Sha256>(root, &as_reads(&reads)),
+            Err(MultiProofError::InvalidKey { index: 1, .. })
+        ));
+
+        let (_, _, reads) = sample_tree();
+        let mut extra_sibling = proof.clone();
+        extra_sibling.siblings.push([0; 32]);
+        assert!(matches!(
+            extra_sibling.verify::<Sha256>(root, &as_reads(&reads)),
+            Err(MultiProofError::InvalidProof(_))
+        ));
+
+        // Claiming that the left leaf is alone in its half of the tree changes the computed root
+        let mut wrong_depth = proof;
+        wrong_depth.paths[0].depth = 1;
+        wrong_depth.paths[2] = wrong_depth.paths[0].clone();
+        assert!(matches!(
+            wrong_depth.verify::<Sha256>(root, &as_reads(&reads)),
+            Err(MultiProofError::InvalidProof(_))
+        ));
+    }
+
+    /// A tree with the leaves `0000...`, `0100...` and `1000...`.
+    fn canonical_tree() -> (RootHash, [ProofLeaf; 3]) {
+        let leaves = [0b0000_0000u8, 0b0100_0000, 0b1000_0000].map(|byte| ProofLeaf {
+            key_hash: key_with_first_byte(byte).0,
+            value_hash: Sha256::hash([byte]),
+        });
+        let left = internal_hash::<Sha256>(
+            &leaf_hash::<Sha256>(&leaves[0]),
+            &leaf_hash::<Sha256>(&leaves[1]),
+        );
+        let root = internal_hash::<Sha256>(&left, &leaf_hash::<Sha256>(&leaves[2]));
+        (RootHash(root), leaves)
+    }
+
+    #[test]
+    fn test_update_splits_leaves() {
+        let (root, [a, b, c]) = canonical_tree();
+        // The new key shares the path of `b` down to depth 2
+        let new_key = key_with_first_byte(0b0110_0000);
+        let proof = SparseMerkleMultiProof {
+            paths: vec![PathEnd {
+                leaf: Some(b.clone()),
+                depth: 2,
+            }],
+            probes: vec![],
+            siblings: vec![leaf_hash::<Sha256>(&a), leaf_hash::<Sha256>(&c)],
+        };
+
+        let new_root = proof
+            .verify_update::<Sha256>(root, &[], &[(new_key, Some(&b"new"[..]))])
+            .unwrap();
+
+        let new_leaf = ProofLeaf {
+            key_hash: new_key.0,
+            value_hash: Sha256::hash(b"new"),
+        };
+        let split =
+            internal_hash::<Sha256>(&leaf_hash::<Sha256>(&b), &leaf_hash::<Sha256>(&new_leaf));
+        let left = internal_hash::<Sha256>(&leaf_hash::<Sha256>(&a), &split);
+        assert_eq!(
+            new_root,
+            internal_hash::<Sha256>(&left, &leaf_hash::<Sha256>(&c))
+        );
+    }
+
+    #[test]
+    fn test_update_moves_leaves_up() {
+        let (root, [a, b, c]) = canonical_tree();
+        let deleted = [(KeyHash(b.key_hash), None)];
+        let mut proof = SparseMerkleMultiProof {
+            paths: vec![PathEnd {
+                leaf: Some(b),
+                depth: 2,
+            }],
+            probes: vec![],
+            siblings: vec![leaf_hash::<Sha256>(&a), leaf_hash::<Sha256>(&c)],
+        };
+
+        // Without a path through the sibling of the deleted leaf, it's unknown whether the sibling moves up
+        let probe = match proof.verify_update::<Sha256>(root, &[], &deleted) {
+            Err(MultiProofError::UnknownSibling { probe }) => probe,
+            other => panic!("Expected an unknown sibling, got {:?}", other),
+        };
+        assert_eq!(probe, a.key_hash);
+
+        proof.paths.push(PathEnd {
+            leaf: Some(a.clone()),
+            depth: 2,
+        });
+        proof.probes.push(probe);
+        proof.siblings = vec![leaf_hash::<Sha256>(&c)];
+        let new_root = proof.verify_update::<Sha256>(root, &[], &deleted).unwrap();
+        assert_eq!(
+            new_root,
+            internal_hash::<Sha256>(&leaf_hash::<Sha256>(&a), &leaf_hash::<Sha256>(&c))
+        );
+
+        // The probe is authenticated like any other path
+        proof.siblings = vec![leaf_hash::<Sha256>(&a)];
+        assert!(matches!(
+            proof.verify_update::<Sha256>(root, &[], &deleted),
+            Err(MultiProofError::InvalidProof(_))
+        ));
+    }
+}
@@ -1,15 +1,15 @@
 use std::{fs, path::Path, sync::Arc};
 
 use crate::config::Config;
+use crate::metrics::JMT_COMMIT_LATENCY_SECONDS;
 use crate::witness::Witness;
 use crate::{
     internal_cache::OrderedReadsAndWrites,
     storage::{StorageKey, StorageValue},
-    tree_db::TreeReadLogger,
-    MerkleProofSpec, Storage,
+    MerkleProofSpec, SparseMerkleMultiProof, Storage,
 };
 use jmt::{storage::TreeWriter, JellyfishMerkleTree, KeyHash, PhantomHasher, SimpleHasher};
-use sov_db::state_db::StateDB;
+use sov_db::{rocks_db_config::ColumnFamilyStats, state_db::StateDB};
 
 pub struct ProverStorage<S: MerkleProofSpec> {
     db: StateDB,
@@ -37,6 +37,21 @@ impl<S: MerkleProofSpec> ProverStorage<S> {
         Self::with_db(db)
     }
 
+    /// Opens the storage configured by `config` as a read-only secondary of the process executing slots,
+    /// for example to serve RPC queries. See [`StateDB::secondary`].
+    pub fn secondary(
+        config: &Config,
+        secondary_path: impl AsRef<Path>,
+    ) -> Result<Self, anyhow::Error> {
+        let db = StateDB::secondary(&config.path, secondary_path, &config.rocksdb.state)?;
+        Self::with_db(db)
+    }
+
+    /// Catches a secondary storage up with the latest version written by the primary
+    pub fn catch_up_with_primary(&self) -> Result<(), anyhow::Error> {
+        self.db.catch_up_with_primary()
+    }
+
     fn with_db(db: StateDB) -> Result<Self, anyhow::Error> {
         Ok(Self {
             db,
@@ -44,6 +59,27 @@ impl<S: MerkleProofSpec> ProverStorage<S> {
         })
     }
 
+    /// Rolls the state back to the most recent version with the provided root. Used on startup to discard
+    /// the state changes of a slot which was executed but never committed to the ledger.
+    pub fn rollback_to_root(&self, root: [u8; 32]) -> Result<(), anyhow::Error> {
+        self.db.rollback_to_root::<S::Hasher>(root)?;
+        Ok(())
+    }
+
+    /// Returns the state root written by the latest call to [`Storage::validate_and_commit`], so that a node
+    /// can resume from its committed state without executing a slot. Fails if nothing was committed yet.
+    pub fn get_state_root(&self) -> Result<[u8; 32], anyhow::Error> {
+        let latest_version = self.db.get_next_version() - 1;
+        let root =
+            JellyfishMerkleTree::<_, S::Hasher>::new(&self.db).get_root_hash(latest_version)?;
+        Ok(root.0)
+    }
+
+    /// Returns the rocksdb stats of each table of the state db
+    pub fn rocksdb_stats(&self) -> Result<Vec<ColumnFamilyStats>, anyhow::Error> {
+        self.db.rocksdb_stats()
+    }
+
     fn read_value(&self, key: StorageKey) -> Option<StorageValue> {
         match self
             .db
@@ -61,7 +97,8 @@ impl<S: MerkleProofSpec> Storage for ProverStorage<S> {
     type RuntimeConfig = Config;
 
     fn with_config(config: Self::RuntimeConfig) -> Result<Self, anyhow::Error> {
-        Self::with_path(config.path.as_path())
+        let db = StateDB::with_config(config.path.as_path(), &config.rocksdb.state)?;
+        Self::with_db(db)
     }
 
     fn get(&self, key: StorageKey, witness: &Self::Witness) -> Option<StorageValue> {
@@ -75,10 +112,8 @@ impl<S: MerkleProofSpec> Storage for ProverStorage<S> {
         state_accesses: OrderedReadsAndWrites,
         witness: &Self::Witness,
     ) -> Result<[u8; 32], anyhow::Error> {
+        let _timer = JMT_COMMIT_LATENCY_SECONDS.start_timer();
         let latest_version = self.db.get_next_version() - 1;
-        witness.add_hint(latest_version);
-
-        let read_logger = TreeReadLogger::with_db_and_witness(self.db.clone(), witness);
         let untracked_jmt = JellyfishMerkleTree::<_, S::Hasher>::new(&self.db);
 
         // Handle empty untracked_jmt
@@ -97,41 +132,64 @@ impl<S: MerkleProofSpec> Storage for ProverStorage<S> {
                 .expect("db write must succeed");
         }
 
-        // For each value that's been read from the tree, read it from the logged JMT to populate hints
-        for (key, read_value) in state_accesses.ordered_reads {
-            let key_hash = KeyHash(S::Hasher::hash(key.key.as_ref()));
-            // TODO: Switch to the batch read API once it becomes available
-            let (result, proof) = untracked_jmt.get_with_proof(key_hash, latest_version)?;
-            if result.as_ref() != read_value.as_ref().map(|f| f.value.as_ref()) {
-                anyhow::bail!("Bug! Incorrect value read from jmt");
-            }
-            witness.add_hint(proof);
-        }
-
-        let tracked_jmt = JellyfishMerkleTree::<_, S::Hasher>::new(&read_logger);
-        // Compute the jmt update from the write batch
-        let batch = state_accesses
+        // Prove all the values that have been read from the tree and the paths of all the written keys
+        // with a single multiproof, which is enough to verify the reads and to compute the new root
+        let (proof, proven_root) = {
+            let reads: Vec<(KeyHash, Option<&[u8]>)> = state_accesses
+                .ordered_reads
+                .iter()
+                .map(|(key, value)| {
+                    (
+                        KeyHash(S::Hasher::hash(key.key.as_ref())),
+                        value.as_ref().map(|v| v.value.as_slice()),
+                    )
+                })
+                .collect();
+            let writes: Vec<(KeyHash, Option<&[u8]>)> = state_accesses
+                .ordered_writes
+                .iter()
+                .map(|(key, value)| {
+                    (
+                        KeyHash(S::Hasher::hash(key.key.as_ref())),
+                        value.as_ref().map(|v| v.value.as_slice()),
+                    )
+                })
+                .collect();
+            SparseMerkleMultiProof::prove_update(&untracked_jmt, latest_version, &reads, &writes)?
+        };
+        witness.add_hint(proof);
+
+        // Compute the jmt update from the write batch. The preimages of the written keys are stored
+        // together with the update, so that nothing is left behind if the update fails
+        let mut preimages = Vec::with_capacity(state_accesses.ordered_writes.len());
+        let batch: Vec<_> = state_accesses
             .ordered_writes
             .into_iter()
             .map(|(key, value)| {
                 let key_hash = KeyHash(S::Hasher::hash(key.key.as_ref()));
-                self.db
-                    .put_preimage(key_hash, key.key.as_ref())
-                    .expect("preimage must succeed");
+                preimages.push((key_hash, key.key.as_ref().clone()));
                 (
                     key_hash,
                     value.map(|v| Arc::try_unwrap(v.value).unwrap_or_else(|arc| (*arc).clone())),
                 )
-            });
+            })
+            .collect();
 
         let next_version = self.db.get_next_version();
 
-        let (new_root, tree_update) = tracked_jmt
+        let (new_root, tree_update) = untracked_jmt
             .put_value_set(batch, next_version)
             .expect("JMT update must succeed");
+        // Otherwise the witness would be rejected in zk mode
+        anyhow::ensure!(
+            new_root.0 == proven_root,
+            "Bug! The multiproof computes root 0x{} but the jmt update produced 0x{}",
+            hex::encode(proven_root),
+            hex::encode(new_root.0)
+        );
 
         self.db
-            .write_node_batch(&tree_update.node_batch)
+            .write_node_batch_with_preimages(&tree_update.node_batch, &preimages)
             .expect("db write must succeed");
         self.db.inc_next_version();
         Ok(new_root.0)
@@ -217,6 +275,40 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_secondary_catches_up() {
+        let path = sov_schema_db::temppath::TempPath::new();
+        let secondary_path = sov_schema_db::temppath::TempPath::new();
+        let config = Config {
+            path: path.path().to_path_buf(),
+            rocksdb: Default::default(),
+        };
+        let primary = ProverStorage::<DefaultStorageSpec>::with_config(config.clone()).unwrap();
+        let secondary =
+            ProverStorage::<DefaultStorageSpec>::secondary(&config, &secondary_path).unwrap();
+
+        let key = StorageKey::from("key");
+        for value in ["value_0", "value_1"] {
+            let value = StorageValue::from(value);
+            let mut storage = WorkingSet::new(primary.clone());
+            storage.set(key.clone(), value.clone());
+            let (cache, witness) = storage.freeze();
+            primary
+                .validate_and_commit(cache, &witness)
+                .expect("storage is valid");
+
+            secondary.catch_up_with_primary().unwrap();
+            assert_eq!(
+                secondary.db.get_next_version(),
+                primary.db.get_next_version()
+            );
+            assert_eq!(
+                value,
+                secondary.get(key.clone(), &Default::default()).unwrap()
+            );
+        }
+    }
+
     #[test]
     fn test_restart_lifecycle() {
         let path = sov_schema_db::temppath::TempPath::new();
@@ -1,5 +1,7 @@
 use std::path::Path;
 
+use borsh::{BorshDeserialize, BorshSerialize};
+
 use super::*;
 use crate::{ArrayWitness, DefaultStorageSpec, ProverStorage};
 
@@ -199,3 +201,241 @@ fn test_witness_roundtrip() {
             .expect("ZK validation should succeed");
     };
 }
+
+#[test]
+fn test_witness_roundtrip_with_many_reads() {
+    let path = sov_schema_db::temppath::TempPath::new();
+    let state_map = StateMap::<u32, u32>::new(Prefix::new(vec![0]));
+    let storage = ProverStorage::<DefaultStorageSpec>::with_path(&path).unwrap();
+
+    let prev_root = {
+        let mut working_set = WorkingSet::new(storage.clone());
+        for key in 0..50 {
+            state_map.set(&key, &(key + 1), &mut working_set);
+        }
+        let (cache_log, witness) = working_set.freeze();
+        storage
+            .validate_and_commit(cache_log, &witness)
+            .expect("Native jmt validation should succeed")
+    };
+
+    // Reads both present and absent keys, whose proofs share most of their paths
+    fn execute<S: Storage>(state_map: &StateMap<u32, u32>, working_set: &mut WorkingSet<S>) {
+        for key in 0..100 {
+            let _ = state_map.get(&key, working_set);
+        }
+        state_map.set(&3, &33, working_set);
+    }
+
+    let (witness, native_root) = {
+        let mut working_set = WorkingSet::new(storage.clone());
+        execute(&state_map, &mut working_set);
+        let (cache_log, witness) = working_set.freeze();
+        let root = storage
+            .validate_and_commit(cache_log, &witness)
+            .expect("Native jmt validation should succeed");
+        (witness, root)
+    };
+
+    let storage = ZkStorage::<DefaultStorageSpec>::new(prev_root);
+    let mut working_set = WorkingSet::with_witness(storage.clone(), witness);
+    execute(&state_map, &mut working_set);
+    let (cache_log, witness) = working_set.freeze();
+    let zk_root = storage
+        .validate_and_commit(cache_log, &witness)
+        .expect("ZK validation should succeed");
+
+    assert_eq!(zk_root, native_root);
+}
+
+/// Reads some keys, and then updates, inserts and deletes others.
+fn execute_updates<S: Storage>(state_map: &StateMap<u32, u32>, working_set: &mut WorkingSet<S>) {
+    for key in 0..10 {
+        let _ = state_map.get(&key, working_set);
+    }
+    state_map.set(&3, &33, working_set);
+    for key in 50..60 {
+        state_map.set(&key, &key, working_set);
+    }
+    for key in 10..30 {
+        state_map.delete(&key, working_set);
+    }
+}
+
+/// Commits 50 keys and then runs `execute_updates` natively. Returns the roots before and after
+/// the updates, together with their witness.
+fn native_updates(path: impl AsRef<Path>) -> ([u8; 32], [u8; 32], ArrayWitness) {
+    let state_map = StateMap::<u32, u32>::new(Prefix::new(vec![0]));
+    let storage = ProverStorage::<DefaultStorageSpec>::with_path(path).unwrap();
+
+    let prev_root = {
+        let mut working_set = WorkingSet::new(storage.clone());
+        for key in 0..50 {
+            state_map.set(&key, &(key + 1), &mut working_set);
+        }
+        let (cache_log, witness) = working_set.freeze();
+        storage
+            .validate_and_commit(cache_log, &witness)
+            .expect("Native jmt validation should succeed")
+    };
+
+    let mut working_set = WorkingSet::new(storage.clone());
+    execute_updates(&state_map, &mut working_set);
+    let (cache_log, witness) = working_set.freeze();
+    let native_root = storage
+        .validate_and_commit(cache_log, &witness)
+        .expect("Native jmt validation should succeed");
+    (prev_root, native_root, witness)
+}
+
+fn zk_updates(prev_root: [u8; 32], witness: ArrayWitness) -> Result<[u8; 32], anyhow::Error> {
+    let state_map = StateMap::<u32, u32>::new(Prefix::new(vec![0]));
+    let storage = ZkStorage::<DefaultStorageSpec>::new(prev_root);
+    let mut working_set = WorkingSet::with_witness(storage.clone(), witness);
+    execute_updates(&state_map, &mut working_set);
+    let (cache_log, witness) = working_set.freeze();
+    storage.validate_and_commit(cache_log, &witness)
+}
+
+/// Applies `forge` to the multiproof, which is the last hint of the witness.
+fn forge_multiproof(
+    witness: ArrayWitness,
+    forge: impl FnOnce(&mut SparseMerkleMultiProof),
+) -> ArrayWitness {
+    let mut hints = witness.into_hints();
+    let mut proof = SparseMerkleMultiProof::try_from_slice(&hints.pop().unwrap()).unwrap();
+    forge(&mut proof);
+    hints.push(proof.try_to_vec().unwrap());
+    ArrayWitness::from_hints(hints)
+}
+
+#[test]
+fn test_witness_roundtrip_with_updates() {
+    let path = sov_schema_db::temppath::TempPath::new();
+    let (prev_root, native_root, witness) = native_updates(&path);
+
+    let zk_root = zk_updates(prev_root, witness).expect("ZK validation should succeed");
+    assert_eq!(zk_root, native_root);
+}
+
+#[derive(Clone)]
+struct CompactStorageSpec;
+
+impl MerkleProofSpec for CompactStorageSpec {
+    type Witness = CompactWitness;
+
+    type Hasher = sha2::Sha256;
+}
+
+#[test]
+fn test_compact_witness_roundtrip_with_updates() {
+    let path = sov_schema_db::temppath::TempPath::new();
+    let state_map = StateMap::<u32, u32>::new(Prefix::new(vec![0]));
+    let storage = ProverStorage::<CompactStorageSpec>::with_path(&path).unwrap();
+
+    let prev_root = {
+        let mut working_set = WorkingSet::new(storage.clone());
+        for key in 0..50 {
+            state_map.set(&key, &(key + 1), &mut working_set);
+        }
+        let (cache_log, witness) = working_set.freeze();
+        storage
+            .validate_and_commit(cache_log, &witness)
+            .expect("Native jmt validation should succeed")
+    };
+
+    let (encoded_witness, native_root) = {
+        let mut working_set = WorkingSet::new(storage.clone());
+        execute_updates(&state_map, &mut working_set);
+        let (cache_log, witness) = working_set.freeze();
+        let root = storage
+            .validate_and_commit(cache_log, &witness)
+            .expect("Native jmt validation should succeed");
+        (witness.encode(), root)
+    };
+
+    // The zk side only sees the encoded witness
+    let witness = CompactWitness::decode(&encoded_witness).unwrap();
+    let storage = ZkStorage::<CompactStorageSpec>::new(prev_root);
+    let mut working_set = WorkingSet::with_witness(storage.clone(), witness);
+    execute_updates(&state_map, &mut working_set);
+    let (cache_log, witness) = working_set.freeze();
+    let zk_root = storage
+        .validate_and_commit(cache_log, &witness)
+        .expect("ZK validation should succeed");
+    assert_eq!(zk_root, native_root);
+}
+
+#[test]
+fn test_forged_witness_is_rejected() {
+    // Each forgery changes the tree the writes are applied to, which would change the new root if accepted
+    let forgeries: Vec<Box<dyn FnOnce(&mut SparseMerkleMultiProof)>> = vec![
+        Box::new(|proof| proof.siblings[0][0] ^= 1),
+        Box::new(|proof| {
+            let leaf = proof
+                .paths
+                .iter_mut()
+                .rev()
+                .find_map(|path| path.leaf.as_mut())
+                .unwrap();
+            leaf.value_hash[0] ^= 1;
+        }),
+        // Claims that the last key's path ends right below the root, next to untouched subtrees
+        Box::new(|proof| {
+            let path = proof.paths.last_mut().unwrap();
+            path.leaf = None;
+            path.depth = 1;
+        }),
+        Box::new(|proof| {
+            proof.paths.pop();
+        }),
+    ];
+
+    let path = sov_schema_db::temppath::TempPath::new();
+    let (prev_root, _, witness) = native_updates(&path);
+    let hints = witness.into_hints();
+    let proof_index = hints.len() - 1;
+
+    for forge in forgeries {
+        let forged = forge_multiproof(ArrayWitness::from_hints(hints.clone()), forge);
+        let err = zk_updates(prev_root, forged).expect_err("Forged witness must be rejected");
+        let mismatch = err
+            .downcast_ref::<HintMismatch>()
+            .expect("Forged multiproof is reported as a hint mismatch");
+        assert_eq!(mismatch.hint_index, proof_index);
+    }
+}
+
+#[test]
+fn test_tree_witness_reader_replays_rightmost_leaf() {
+    use jmt::storage::{TreeReader, TreeWriter};
+    use jmt::{JellyfishMerkleTree, KeyHash};
+    use sha2::Sha256;
+    use sov_db::state_db::StateDB;
+
+    // The native tree logs each answer as a hint, which the zk reader must replay in order
+    let db = StateDB::temporary();
+    let witness = ArrayWitness::default();
+    let empty = db.get_rightmost_leaf().unwrap();
+    witness.add_hint(empty.clone());
+
+    let keys = [KeyHash([3; 32]), KeyHash([9; 32])];
+    for key in keys {
+        db.put_preimage(key, &key.0.to_vec()).unwrap();
+    }
+    let (_, tree_update) = JellyfishMerkleTree::<_, Sha256>::new(&db)
+        .put_value_set(keys.iter().map(|key| (*key, Some(vec![1u8]))), 0)
+        .unwrap();
+    db.write_node_batch(&tree_update.node_batch).unwrap();
+    let rightmost = db.get_rightmost_leaf().unwrap();
+    assert_eq!(rightmost.as_ref().unwrap().1.key_hash(), KeyHash([9; 32]));
+    witness.add_hint(rightmost.clone());
+
+    let reader = TreeWitnessReader::new(&witness);
+    assert_eq!(reader.get_rightmost_leaf().unwrap(), empty);
+    assert_eq!(reader.get_rightmost_leaf().unwrap(), rightmost);
+    let err = reader
+        .get_rightmost_leaf()
+        .expect_err("The witness is exhausted");
+    assert_eq!(err.downcast_ref::<HintMismatch>().unwrap().hint_index, 2);
+}
@@ -4,14 +4,52 @@ use serde::{Deserialize, Serialize};
 use std::sync::atomic::AtomicUsize;
 use std::sync::Mutex;
 
+use crate::zk_storage::HintMismatch;
+
+/// The reason the next hint could not be taken from a witness.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HintError {
+    #[error("the witness has no hint at index {index}")]
+    Missing { index: usize },
+    #[error("hint {index} could not be deserialized: {reason}")]
+    Malformed { index: usize, reason: String },
+}
+
+impl HintError {
+    /// The index of the hint which could not be used
+    pub fn index(&self) -> usize {
+        match self {
+            HintError::Missing { index } | HintError::Malformed { index, .. } => *index,
+        }
+    }
+}
+
 // TODO: Refactor witness trait so it only require Serialize / Deserialize
 //   https://github.com/Sovereign-Labs/sovereign/issues/263
 pub trait Witness: Default + Serialize {
     fn add_hint<T: BorshSerialize>(&self, hint: T);
-    fn get_hint<T: BorshDeserialize>(&self) -> T;
+
+    /// Returns the next hint, or an error if the witness is exhausted or the next hint is not a `T`.
+    fn try_get_hint<T: BorshDeserialize>(&self) -> Result<T, HintError>;
+
+    fn get_hint<T: BorshDeserialize>(&self) -> T {
+        self.try_get_hint()
+            .unwrap_or_else(|e| panic!("Invalid witness: {}", e))
+    }
+
+    /// The index of the hint which will be returned by the next call to `get_hint`.
+    fn next_hint_index(&self) -> usize;
+
     fn merge(&self, rhs: &Self);
 }
 
+/// A [`TreeReader`] which answers every query with the next hint of the witness, so the hints must be
+/// produced by logging the answers of the native tree in the same order. Each query consumes one hint:
+/// - `get_node_option`: an `Option<Vec<u8>>` holding the borsh-serialized node
+/// - `get_value_option`: an `Option<OwnedValue>`
+/// - `get_rightmost_leaf`: an `Option<(NodeKey, LeafNode)>`
+///
+/// The hints are not authenticated, so callers must verify anything derived from them against a trusted root.
 #[derive(Debug)]
 pub struct TreeWitnessReader<'a, T: Witness>(&'a T);
 
@@ -26,7 +64,8 @@ impl<'a, T: Witness> TreeReader for TreeWitnessReader<'a, T> {
         &self,
         _node_key: &jmt::storage::NodeKey,
     ) -> anyhow::Result<Option<jmt::storage::Node>> {
-        let serialized_node_opt: Option<Vec<u8>> = self.0.get_hint();
+        let serialized_node_opt: Option<Vec<u8>> =
+            self.0.try_get_hint().map_err(tree_hint_mismatch)?;
         match serialized_node_opt {
             Some(val) => Ok(Some(jmt::storage::Node::deserialize_reader(&mut &val[..])?)),
             None => Ok(None),
@@ -38,34 +77,66 @@ impl<'a, T: Witness> TreeReader for TreeWitnessReader<'a, T> {
         _max_version: jmt::Version,
         _key_hash: jmt::KeyHash,
     ) -> anyhow::Result<Option<jmt::OwnedValue>> {
-        Ok(self.0.get_hint())
+        Ok(self.0.try_get_hint().map_err(tree_hint_mismatch)?)
     }
 
     fn get_rightmost_leaf(
         &self,
     ) -> anyhow::Result<Option<(jmt::storage::NodeKey, jmt::storage::LeafNode)>> {
-        unimplemented!()
+        Ok(self.0.try_get_hint().map_err(tree_hint_mismatch)?)
     }
 }
 
+/// Tree nodes aren't tied to a single storage key, so mismatches are reported without one.
+fn tree_hint_mismatch(e: HintError) -> HintMismatch {
+    HintMismatch::new(e.index(), None, e.to_string())
+}
+
 #[derive(Default, Debug, Serialize, Deserialize)]
 pub struct ArrayWitness {
     next_idx: AtomicUsize,
     hints: Mutex<Vec<Vec<u8>>>,
 }
 
+impl ArrayWitness {
+    /// The total number of hints in the witness, including the ones which have already been consumed.
+    pub fn num_hints(&self) -> usize {
+        self.hints.lock().unwrap().len()
+    }
+
+    pub(crate) fn into_hints(self) -> Vec<Vec<u8>> {
+        self.hints.into_inner().unwrap()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn from_hints(hints: Vec<Vec<u8>>) -> Self {
+        Self {
+            next_idx: AtomicUsize::new(0),
+            hints: Mutex::new(hints),
+        }
+    }
+}
+
 impl Witness for ArrayWitness {
     fn add_hint<T: BorshSerialize>(&self, hint: T) {
         self.hints.lock().unwrap().push(hint.try_to_vec().unwrap())
     }
 
-    fn get_hint<T: BorshDeserialize>(&self) -> T {
-        let idx = self
+    fn try_get_hint<T: BorshDeserialize>(&self) -> Result<T, HintError> {
+        let index = self
             .next_idx
             .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         let hints_lock = self.hints.lock().unwrap();
-        T::deserialize_reader(&mut std::io::Cursor::new(&hints_lock[idx]))
-            .expect("Hint deserialization should never fail")
+        let hint = hints_lock.get(index).ok_or(HintError::Missing { index })?;
+        // Hints are serialized individually, so a hint with trailing bytes belongs to a different access
+        T::try_from_slice(hint).map_err(|e| HintError::Malformed {
+            index,
+            reason: e.to_string(),
+        })
+    }
+
+    fn next_hint_index(&self) -> usize {
+        self.next_idx.load(std::sync::atomic::Ordering::SeqCst)
     }
 
     fn merge(&self, rhs: &Self) {
@@ -1,14 +1,46 @@
-use std::sync::Arc;
-
-use crate::witness::{TreeWitnessReader, Witness};
-use jmt::{JellyfishMerkleTree, KeyHash, PhantomHasher, SimpleHasher, Version};
+use crate::witness::Witness;
+use jmt::{KeyHash, PhantomHasher, SimpleHasher};
+use tracing::error;
 
 use crate::{
     internal_cache::OrderedReadsAndWrites,
     storage::{StorageKey, StorageValue},
-    MerkleProofSpec, Storage,
+    MerkleProofSpec, MultiProofError, SparseMerkleMultiProof, Storage,
 };
 
+/// A storage access in zk mode which didn't match the next hint of the witness. This means that the
+/// witness was produced by a different sequence of storage accesses than the one being replayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HintMismatch {
+    /// The position of the offending hint in the witness
+    pub hint_index: usize,
+    /// The storage key being accessed, if the hint belongs to a single key
+    pub key: Option<Vec<u8>>,
+    pub reason: String,
+}
+
+impl HintMismatch {
+    pub fn new(hint_index: usize, key: Option<&[u8]>, reason: String) -> Self {
+        Self {
+            hint_index,
+            key: key.map(|k| k.to_vec()),
+            reason,
+        }
+    }
+}
+
+impl std::fmt::Display for HintMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Hint mismatch at index {}", self.hint_index)?;
+        if let Some(key) = &self.key {
+            write!(f, " while accessing key 0x{}", hex::encode(key))?;
+        }
+        write!(f, ": {}", self.reason)
+    }
+}
+
+impl std::error::Error for HintMismatch {}
+
 pub struct ZkStorage<S: MerkleProofSpec> {
     prev_state_root: [u8; 32],
     _phantom_hasher: PhantomHasher<S::Hasher>,
@@ -41,8 +73,15 @@ impl<S: MerkleProofSpec> Storage for ZkStorage<S> {
         Ok(Self::new(config))
     }
 
-    fn get(&self, _key: StorageKey, witness: &S::Witness) -> Option<StorageValue> {
-        witness.get_hint()
+    fn get(&self, key: StorageKey, witness: &S::Witness) -> Option<StorageValue> {
+        witness.try_get_hint().unwrap_or_else(|e| {
+            let mismatch =
+                HintMismatch::new(e.index(), Some(key.as_ref().as_slice()), e.to_string());
+            error!("{}", mismatch);
+            // Reads can't return an error, so the mismatch is used as the panic payload.
+            // This allows native callers to recover it with `std::panic::catch_unwind`.
+            std::panic::panic_any(mismatch)
+        })
     }
 
     fn validate_and_commit(
@@ -50,46 +89,53 @@ impl<S: MerkleProofSpec> Storage for ZkStorage<S> {
         state_accesses: OrderedReadsAndWrites,
         witness: &Self::Witness,
     ) -> Result<[u8; 32], anyhow::Error> {
-        let latest_version: Version = witness.get_hint();
-        let reader = TreeWitnessReader::new(witness);
-
-        // For each value that's been read from the tree, verify the provided smt proof
-        for (key, read_value) in state_accesses.ordered_reads {
-            let key_hash = KeyHash(S::Hasher::hash(key.key.as_ref()));
-            // TODO: Switch to the batch read API once it becomes available
-            let proof: jmt::proof::SparseMerkleProof<S::Hasher> = witness.get_hint();
-            match read_value {
-                Some(val) => proof.verify_existence(
-                    jmt::RootHash(self.prev_state_root),
-                    key_hash,
-                    val.value.as_ref(),
-                )?,
-                None => proof.verify_nonexistence(jmt::RootHash(self.prev_state_root), key_hash)?,
-            }
-        }
-
-        // Compute the jmt update from the write batch
-        let batch = state_accesses
+        // Verify all the values that have been read from the tree, and compute the new root from
+        // the paths of the written keys. Both are authenticated by a single multiproof.
+        let proof_index = witness.next_hint_index();
+        let proof: SparseMerkleMultiProof = witness
+            .try_get_hint()
+            .map_err(|e| HintMismatch::new(e.index(), None, e.to_string()))?;
+        let reads: Vec<(KeyHash, Option<&[u8]>)> = state_accesses
+            .ordered_reads
+            .iter()
+            .map(|(key, read_value)| {
+                (
+                    KeyHash(S::Hasher::hash(key.key.as_ref())),
+                    read_value.as_ref().map(|val| val.value.as_slice()),
+                )
+            })
+            .collect();
+        let writes: Vec<(KeyHash, Option<&[u8]>)> = state_accesses
             .ordered_writes
-            .into_iter()
+            .iter()
             .map(|(key, value)| {
-                let key_hash = KeyHash(S::Hasher::hash(key.key.as_ref()));
                 (
-                    key_hash,
-                    value.map(|v| Arc::try_unwrap(v.value).unwrap_or_else(|arc| (*arc).clone())),
+                    KeyHash(S::Hasher::hash(key.key.as_ref())),
+                    value.as_ref().map(|val| val.value.as_slice()),
                 )
-            });
-
-        let next_version = latest_version + 1;
-        // TODO: Make updates verifiable. Currently, writes don't verify that the provided siblings existed in the old tree
-        // because the TreeReader is trusted
-        let jmt = JellyfishMerkleTree::<_, S::Hasher>::new(&reader);
-
-        let (new_root, _tree_update) = jmt
-            .put_value_set(batch, next_version)
-            .expect("JMT update must succeed");
-
-        Ok(new_root.0)
+            })
+            .collect();
+
+        let new_root = proof
+            .verify_update::<S::Hasher>(jmt::RootHash(self.prev_state_root), &reads, &writes)
+            .map_err(|e| {
+                let key = match &e {
+                    // Reads are proven first, followed by writes and then by probes
+                    MultiProofError::InvalidKey { index, .. } => state_accesses
+                        .ordered_reads
+                        .iter()
+                        .map(|(key, _)| key)
+                        .chain(state_accesses.ordered_writes.iter().map(|(key, _)| key))
+                        .nth(*index)
+                        .map(|key| key.key.as_slice()),
+                    MultiProofError::InvalidProof(_) | MultiProofError::UnknownSibling { .. } => {
+                        None
+                    }
+                };
+                HintMismatch::new(proof_index, key, e.to_string())
+            })?;
+
+        Ok(new_root)
     }
 
     fn is_empty(&self) -> bool {
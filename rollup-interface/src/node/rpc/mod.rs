@@ -80,6 +80,48 @@ pub enum EventGroupIdentifier {
     Key(Vec<u8>),
 }
 
+/// The position of an event in the index of events by key. Returned with each page of a
+/// [`EventsByKeyQuery`] to resume the query from the next event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventCursor {
+    pub tx_number: u64,
+    pub event_number: u64,
+}
+
+/// A query for the events with a given key, optionally restricted to a range of slots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventsByKeyQuery {
+    pub key: EventKey,
+    /// The first slot whose events are returned. Defaults to the first slot
+    #[serde(default)]
+    pub start_slot: Option<u64>,
+    /// The last slot whose events are returned, inclusive. Defaults to the latest slot
+    #[serde(default)]
+    pub end_slot: Option<u64>,
+    /// The `next_cursor` of the previous page. Defaults to the first event in the range of slots
+    #[serde(default)]
+    pub cursor: Option<EventCursor>,
+    /// The maximum number of events to return. Defaults to the largest page allowed by the provider
+    #[serde(default)]
+    pub limit: Option<u64>,
+}
+
+/// An event, together with the numbers identifying it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventResponse {
+    pub number: u64,
+    pub tx_number: u64,
+    pub event: Event,
+}
+
+/// A page of the events matching an [`EventsByKeyQuery`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventsPage {
+    pub events: Vec<EventResponse>,
+    /// The cursor from which to request the next page, or `None` if there are no more matching events
+    pub next_cursor: Option<EventCursor>,
+}
+
 /// An identifier that specifies a single slot
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -133,6 +175,47 @@ pub struct TxResponse<Tx> {
     pub custom_receipt: Tx,
 }
 
+/// Where a transaction was included, and what it did
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TxInclusion<Tx> {
+    pub slot_number: u64,
+    /// The hash of the DA block which included the transaction
+    #[serde(with = "rpc_hex")]
+    pub slot_hash: [u8; 32],
+    pub batch_number: u64,
+    #[serde(with = "rpc_hex")]
+    pub batch_hash: [u8; 32],
+    pub tx_number: u64,
+    pub tx: TxResponse<Tx>,
+    /// The events emitted by the transaction, in order
+    pub events: Vec<Event>,
+}
+
+/// The status of a transaction, looked up by hash
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "status")]
+pub enum TxStatus<Tx> {
+    /// The transaction is unknown to the node
+    NotFound,
+    /// The transaction was accepted by the node's sequencer, but isn't included in a DA block yet
+    Pending,
+    /// The transaction was included and applied
+    Included(TxInclusion<Tx>),
+    /// The transaction was included, but its changes were reverted
+    Reverted(TxInclusion<Tx>),
+}
+
+/// Implemented by the custom receipts of transactions, to tell whether a transaction was reverted
+pub trait TxReceiptStatus {
+    fn is_reverted(&self) -> bool;
+}
+
+impl TxReceiptStatus for () {
+    fn is_reverted(&self) -> bool {
+        false
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ItemOrHash<T> {
@@ -164,6 +247,7 @@ pub trait LedgerRpcProvider {
         &self,
         event_ids: &[EventIdentifier],
     ) -> Result<Vec<Option<Event>>, anyhow::Error>;
+    fn get_events_by_key(&self, query: &EventsByKeyQuery) -> Result<EventsPage, anyhow::Error>;
     fn get_slot_by_hash<B: DeserializeOwned, T: DeserializeOwned>(
         &self,
         hash: &[u8; 32],
@@ -179,6 +263,10 @@ pub trait LedgerRpcProvider {
         hash: &[u8; 32],
         query_mode: QueryMode,
     ) -> Result<Option<TxResponse<T>>, anyhow::Error>;
+    fn get_tx_status<T: DeserializeOwned + TxReceiptStatus>(
+        &self,
+        hash: &[u8; 32],
+    ) -> Result<TxStatus<T>, anyhow::Error>;
     fn get_slot_by_number<B: DeserializeOwned, T: DeserializeOwned>(
         &self,
         number: u64,
@@ -6,7 +6,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     da::BlobTransactionTrait,
-    traits::AddressTrait,
+    services::da::SlotData,
+    traits::{AddressTrait, BlockHeaderTrait, CanonicalHash},
     zk::traits::{Matches, Zkvm},
 };
 
@@ -121,3 +122,63 @@ impl<Address: AddressTrait> TestBlob<Address> {
         Self { address, data }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestHash(pub [u8; 32]);
+
+impl AsRef<[u8]> for TestHash {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestBlockHeader {
+    pub prev_hash: TestHash,
+    pub hash: TestHash,
+}
+
+impl CanonicalHash for TestBlockHeader {
+    type Output = TestHash;
+
+    fn hash(&self) -> Self::Output {
+        self.hash.clone()
+    }
+}
+
+impl BlockHeaderTrait for TestBlockHeader {
+    type Hash = TestHash;
+
+    fn prev_hash(&self) -> Self::Hash {
+        self.prev_hash.clone()
+    }
+}
+
+/// A DA layer block which can be committed to the ledger in tests
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestBlock {
+    pub header: TestBlockHeader,
+}
+
+impl TestBlock {
+    pub fn new(hash: [u8; 32], prev_hash: [u8; 32]) -> Self {
+        Self {
+            header: TestBlockHeader {
+                prev_hash: TestHash(prev_hash),
+                hash: TestHash(hash),
+            },
+        }
+    }
+}
+
+impl SlotData for TestBlock {
+    type BlockHeader = TestBlockHeader;
+
+    fn hash(&self) -> [u8; 32] {
+        self.header.hash.0
+    }
+
+    fn header(&self) -> &Self::BlockHeader {
+        &self.header
+    }
+}